@@ -0,0 +1,589 @@
+//! Code generation for partial structs with `#[partial(Name, compact)]`.
+//!
+//! A compact partial struct stores each field's value in a `MaybeUninit<T>`, rather than as an
+//! `Option<T>`, and tracks which fields are set in a bitset. This saves space for types without a
+//! niche, such as integers and arrays, where `Option<T>` is larger than `T`.
+//!
+//! The values and the bitset are stored together in a storage struct within a private module, so
+//! that code in the user's module cannot mark an uninitialized value as set without `unsafe`. The
+//! storage struct is generic over the fields' types, which the partial struct names in the user's
+//! module, so that they resolve there even if they are not reachable from the private module.
+//! The partial struct wraps the storage struct, and since its fields cannot be accessed as
+//! `Option<T>`, it exposes accessors instead and does not support options that depend on the
+//! fields' layout.
+
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Attribute, Meta, ext::IdentExt};
+
+use crate::{
+    BaseField, Context, base_methods, merge_bounded_generics, merge_field, options::MergeStrategy,
+    partial_field_vis, partial_summary, utils,
+};
+
+/// Generates the compact partial struct and all of its implementations.
+///
+/// `convertible` is false if the base struct cannot be converted into the partial struct, and
+/// `complete` is false if the partial struct cannot be converted into the base struct.
+pub fn compact_output(
+    cx: &Context<'_>,
+    meta: &[Meta],
+    attr_index: usize,
+    convertible: bool,
+    complete: bool,
+    primary: bool,
+) -> TokenStream {
+    let storage = storage(cx);
+
+    let merge_impl = merge_impl(cx);
+
+    let partial_struct = partial_struct(cx, meta, attr_index);
+
+    let partial_methods = partial_methods(cx);
+
+    let partial_accessors = partial_accessors(cx);
+
+    let partial_default = partial_default(cx);
+
+    let partial_merge_impl = partial_merge_impl(cx);
+
+    let partial_from = convertible.then(|| partial_from(cx));
+
+    let partial_try_into = complete.then(|| partial_try_into(cx));
+
+    let base_methods = (primary && convertible).then(|| base_methods(cx));
+
+    quote! {
+        #storage
+
+        #merge_impl
+
+        #partial_struct
+
+        #partial_methods
+
+        #partial_accessors
+
+        #partial_default
+
+        #partial_merge_impl
+
+        #partial_from
+
+        #partial_try_into
+
+        #base_methods
+    }
+}
+
+/// Returns the name of the private module containing the storage struct.
+fn storage_mod(cx: &Context<'_>) -> Ident {
+    format_ident!("__mergeme_{}", cx.partial_name.unraw())
+}
+
+/// Returns the name of the storage struct's type parameter for the field at `index`.
+fn storage_param(index: usize) -> Ident {
+    format_ident!("T{}", index)
+}
+
+/// Returns the index of the byte within the bitset and the mask of the bit that tracks whether the
+/// field at `index` is set.
+fn bit(index: usize) -> (Literal, Literal) {
+    (
+        Literal::usize_unsuffixed(index / 8),
+        Literal::u8_suffixed(1 << (index % 8)),
+    )
+}
+
+/// Returns the names of a field's accessors: the getter, mutable getter, setter, builder-style
+/// setter, and taker.
+fn accessor_names(field: &BaseField<'_>) -> (Ident, Ident, Ident, Ident, Ident) {
+    let ident = &field.partial_ident;
+    let name = ident.unraw();
+
+    (
+        ident.clone(),
+        format_ident!("{}_mut", name),
+        format_ident!("set_{}", name),
+        format_ident!("with_{}", name),
+        format_ident!("take_{}", name),
+    )
+}
+
+/// Generates the private module containing the storage struct, which owns the values and the
+/// bitset, and is the only generated code that uses `unsafe`.
+///
+/// The storage struct upholds that a field's value is initialized if and only if its bit is set.
+/// Its fields are private to the module, so the partial struct can only access them through the
+/// storage struct's methods, which share the names of the partial struct's accessors.
+///
+/// Each field's type is a type parameter of the storage struct, so that the module never names the
+/// fields' types itself.
+fn storage(cx: &Context<'_>) -> TokenStream {
+    let Context { fields, .. } = cx;
+
+    let module = storage_mod(cx);
+    let set_len = fields.len().div_ceil(8);
+    let field_names: Vec<_> = fields.iter().map(|field| &field.partial_ident).collect();
+    let field_types: Vec<_> = (0..fields.len()).map(storage_param).collect();
+
+    let is_empty = utils::all(
+        (0..set_len)
+            .map(Literal::usize_unsuffixed)
+            .map(|byte| quote!(self.__mergeme_set[#byte] == 0)),
+    );
+
+    let is_complete = utils::all((0..fields.len()).map(|index| {
+        let (byte, mask) = bit(index);
+        quote!((self.__mergeme_set[#byte] & #mask) != 0)
+    }));
+
+    let bytes = (0..set_len).map(Literal::usize_unsuffixed);
+
+    // Every `unsafe` block is only reached when the field's bit is set, so the value is
+    // initialized. The bit is cleared before the value is dropped or moved out, so that a panic
+    // while dropping it cannot cause it to be dropped again.
+    let accessors = fields.iter().enumerate().map(|(index, field)| {
+        let ident = &field.partial_ident;
+        let ty = storage_param(index);
+        let (byte, mask) = bit(index);
+        let (getter, getter_mut, set, _, take) = accessor_names(field);
+
+        quote! {
+            pub fn #getter(&self) -> ::core::option::Option<&#ty> {
+                if (self.__mergeme_set[#byte] & #mask) != 0 {
+                    ::core::option::Option::Some(unsafe { self.#ident.assume_init_ref() })
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            pub fn #getter_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                if (self.__mergeme_set[#byte] & #mask) != 0 {
+                    ::core::option::Option::Some(unsafe { self.#ident.assume_init_mut() })
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            pub fn #set(&mut self, value: #ty) {
+                if (self.__mergeme_set[#byte] & #mask) != 0 {
+                    self.__mergeme_set[#byte] &= !#mask;
+                    unsafe { self.#ident.assume_init_drop() };
+                }
+
+                self.#ident.write(value);
+                self.__mergeme_set[#byte] |= #mask;
+            }
+
+            pub fn #take(&mut self) -> ::core::option::Option<#ty> {
+                if (self.__mergeme_set[#byte] & #mask) != 0 {
+                    self.__mergeme_set[#byte] &= !#mask;
+                    ::core::option::Option::Some(unsafe { self.#ident.assume_init_read() })
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+    });
+
+    let takes = fields.iter().map(|field| accessor_names(field).4);
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case, unsafe_code)]
+        mod #module {
+            pub struct Storage<#(#field_types),*> {
+                #(#field_names: ::core::mem::MaybeUninit<#field_types>,)*
+                __mergeme_set: [u8; #set_len],
+            }
+
+            #[automatically_derived]
+            impl<#(#field_types),*> ::core::default::Default for Storage<#(#field_types),*> {
+                fn default() -> Self {
+                    Self {
+                        #(#field_names: ::core::mem::MaybeUninit::uninit(),)*
+                        __mergeme_set: [0; #set_len],
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl<#(#field_types),*> ::core::ops::Drop for Storage<#(#field_types),*> {
+                fn drop(&mut self) {
+                    #(::core::mem::drop(self.#takes());)*
+                }
+            }
+
+            impl<#(#field_types),*> Storage<#(#field_types),*> {
+                pub const fn is_empty(&self) -> bool {
+                    #is_empty
+                }
+
+                pub const fn is_complete(&self) -> bool {
+                    #is_complete
+                }
+
+                pub const fn count_set(&self) -> usize {
+                    0 #(+ self.__mergeme_set[#bytes].count_ones() as usize)*
+                }
+
+                #(#accessors)*
+            }
+        }
+    }
+}
+
+/// Generates the compact partial struct itself, which wraps the storage struct.
+fn partial_struct(cx: &Context<'_>, meta: &[Meta], attr_index: usize) -> TokenStream {
+    let Context {
+        input,
+        partial_generics,
+        partial_name,
+        partial_vis,
+        docs,
+        ..
+    } = cx;
+
+    let module = storage_mod(cx);
+    let generics = partial_generics;
+    let (_, _, where_clause) = generics.split_for_impl();
+    let field_types = cx.fields.iter().map(|field| field.value_ty());
+    let attrs = utils::ordered_attrs(
+        utils::forwarded_attrs(&input.attrs, *docs),
+        meta.iter().map(|meta| (attr_index, meta)),
+    );
+    let summary = partial_summary(cx);
+
+    quote! {
+        #summary
+        #(#attrs)*
+        #partial_vis struct #partial_name #generics #where_clause {
+            storage: #module::Storage<#(#field_types),*>,
+        }
+    }
+}
+
+/// Generates `is_empty()`, `is_complete()`, and `count_set()` for the compact partial struct.
+fn partial_methods(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        partial_vis,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            /// Returns true if every field is unset, meaning that merging this would not change
+            /// anything.
+            #partial_vis const fn is_empty(&self) -> bool {
+                self.storage.is_empty()
+            }
+
+            /// Returns true if every field is set, meaning that this can be converted into the
+            /// base struct with `TryFrom`.
+            #partial_vis const fn is_complete(&self) -> bool {
+                self.storage.is_complete()
+            }
+
+            /// Returns the number of fields that are set, meaning the number of fields that would
+            /// be changed by merging this.
+            #partial_vis const fn count_set(&self) -> usize {
+                self.storage.count_set()
+            }
+        }
+    }
+}
+
+/// Generates the getters, setters, and takers for each field of the compact partial struct.
+fn partial_accessors(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    let accessors = fields.iter().map(|field| {
+        let vis = partial_field_vis(cx, field);
+        let ty = field.value_ty();
+        let (getter, getter_mut, set, with, take) = accessor_names(field);
+
+        // The accessors stand in for the field, so they inherit its deprecation.
+        let deprecated: Vec<_> = field
+            .field
+            .attrs
+            .iter()
+            .filter(|attr: &&Attribute| attr.path().is_ident("deprecated"))
+            .collect();
+
+        let name = field.partial_ident.unraw();
+        let getter_doc = format!(" Returns a reference to `{name}`, or `None` if it is not set.");
+        let getter_mut_doc =
+            format!(" Returns a mutable reference to `{name}`, or `None` if it is not set.");
+        let set_doc = format!(" Sets `{name}` to `value`.");
+        let take_doc = format!(" Unsets `{name}`, returning its value if it was set.");
+
+        quote! {
+            #[doc = #getter_doc]
+            #(#deprecated)*
+            #vis fn #getter(&self) -> ::core::option::Option<&#ty> {
+                self.storage.#getter()
+            }
+
+            #[doc = #getter_mut_doc]
+            #(#deprecated)*
+            #vis fn #getter_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                self.storage.#getter_mut()
+            }
+
+            #[doc = #set_doc]
+            #(#deprecated)*
+            #vis fn #set(&mut self, value: #ty) -> &mut Self {
+                self.storage.#set(value);
+                self
+            }
+
+            #[doc = #set_doc]
+            #(#deprecated)*
+            #[must_use]
+            #vis fn #with(mut self, value: #ty) -> Self {
+                self.storage.#set(value);
+                self
+            }
+
+            #[doc = #take_doc]
+            #(#deprecated)*
+            #vis fn #take(&mut self) -> ::core::option::Option<#ty> {
+                self.storage.#take()
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+}
+
+/// Generates `impl Default for Partial`, where every field is unset.
+///
+/// Unlike regular partial structs, this is always generated, since it is the only way to create a
+/// compact partial struct besides converting the base struct.
+fn partial_default(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::default::Default for #partial_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    storage: ::core::default::Default::default(),
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl Merge<Partial> for Base`, taking each set field out of the compact partial
+/// struct.
+fn merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let merge_fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let (_, _, _, _, take) = accessor_names(field);
+        let merge = merge_field(krate, field);
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = other.#take() {
+                #merge
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics #krate::Merge<#partial_name #ty_generics> for #struct_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn merge_in_place(&mut self, mut other: #partial_name #ty_generics) {
+                #(#merge_fields)*
+            }
+        }
+    }
+}
+
+/// Generates `impl Merge<Partial> for Partial`, which combines compact partial structs using the
+/// same strategy as the base struct's fields.
+fn partial_merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let merge_fields = fields.iter().map(|field| {
+        let (_, getter_mut, set, _, take) = accessor_names(field);
+
+        let merge = match field.options.strategy {
+            MergeStrategy::Overwrite => quote! {
+                self.#set(value);
+            },
+            MergeStrategy::Merge => {
+                let merge =
+                    utils::merge_value(krate, field.value_ty(), quote!(existing), quote!(value));
+
+                quote! {
+                    match self.#getter_mut() {
+                        ::core::option::Option::Some(existing) => {
+                            #merge
+                        }
+                        ::core::option::Option::None => {
+                            self.#set(value);
+                        }
+                    }
+                }
+            }
+            MergeStrategy::TryWith(_) => {
+                unreachable!(
+                    "previously verified that compact partial structs have no fallible fields"
+                )
+            }
+        };
+
+        quote! {
+            if let ::core::option::Option::Some(value) = other.#take() {
+                #merge
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics #krate::Merge<#partial_name #ty_generics> for #partial_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn merge_in_place(&mut self, mut other: #partial_name #ty_generics) {
+                #(#merge_fields)*
+            }
+        }
+    }
+}
+
+/// Generates `impl From<Base> for Partial`, which sets every field.
+fn partial_from(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_names: Vec<_> = fields.iter().map(|field| field.ident).collect();
+    let setters = fields.iter().map(|field| accessor_names(field).2);
+
+    // Skipped fields are not in `fields`, so they are discarded by `..`.
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#struct_name #ty_generics> for #partial_name #ty_generics #where_clause {
+            fn from(base: #struct_name #ty_generics) -> Self {
+                let #struct_name { #(#field_names,)* .. } = base;
+                let mut partial = <Self as ::core::default::Default>::default();
+
+                #(partial.#setters(#field_names);)*
+
+                partial
+            }
+        }
+    }
+}
+
+/// Generates `impl TryFrom<Partial> for Base`, which succeeds if every field is set.
+fn partial_try_into(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_names: Vec<_> = fields.iter().map(|field| field.ident).collect();
+    let field_strs = fields
+        .iter()
+        .map(|field| field.partial_ident.unraw().to_string());
+    let takes = fields.iter().map(|field| accessor_names(field).4);
+
+    let field_values = fields.iter().map(|field| {
+        let ident = field.ident;
+
+        match field.options.with {
+            Some(_) => {
+                let value = field.convert(quote!(#ident));
+                quote!(#ident: #value)
+            }
+            None => quote!(#ident),
+        }
+    });
+
+    // If there are no fields, the first match arm is irrefutable.
+    let missing_arm = (!fields.is_empty()).then(|| {
+        quote! {
+            (#(#field_names,)*) => ::core::result::Result::Err(#krate::MissingFields::new(
+                &[#(#field_strs,)*],
+                [#(#field_names.is_none(),)*],
+            )),
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::TryFrom<#partial_name #ty_generics> for #struct_name #ty_generics #where_clause {
+            type Error = #krate::MissingFields;
+
+            #[allow(unused_mut)]
+            fn try_from(mut partial: #partial_name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                match (#(partial.#takes(),)*) {
+                    (#(::core::option::Option::Some(#field_names),)*) => {
+                        ::core::result::Result::Ok(Self { #(#field_values,)* })
+                    }
+                    #missing_arm
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,646 @@
+//! Code generation for `#[derive(Merge)]` on enums.
+//!
+//! The partial enum has the same variants as the base enum, with every field wrapped in an
+//! `Option<T>`. Merging a partial enum into a base enum of the same variant merges each field,
+//! while merging one of a different variant replaces the base enum, but only if every field of the
+//! partial enum's variant is set. Otherwise there would be no value for the missing fields, so the
+//! merge does nothing.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    DataEnum, DeriveInput, Error, Field, Fields, Generics, Member, Path, Result, Variant,
+    Visibility, spanned::Spanned,
+};
+
+use crate::{
+    options::{self, FieldOptions, MergeStrategy, PartialOptions, PartialTarget},
+    utils,
+};
+
+/// The shared state used to generate a partial enum and its implementations.
+struct Context<'a> {
+    /// The path to the `mergeme` crate.
+    krate: Path,
+
+    /// The base enum that `#[derive(Merge)]` annotates.
+    input: &'a DeriveInput,
+
+    /// The name of the generated partial enum.
+    partial_name: Ident,
+
+    /// The generics of the partial enum, which are the base enum's without `Merge` bounds.
+    partial_generics: Generics,
+
+    /// The visibility of the generated partial enum.
+    partial_vis: Visibility,
+
+    /// Whether doc comments should be copied to the partial enum.
+    docs: bool,
+
+    /// The variants of the base enum.
+    variants: &'a [EnumVariant<'a>],
+}
+
+/// A variant of the base enum, alongside its fields.
+struct EnumVariant<'a> {
+    /// The variant itself.
+    variant: &'a Variant,
+
+    /// The fields of the variant, which are empty for unit variants.
+    fields: Vec<EnumField<'a>>,
+}
+
+impl EnumVariant<'_> {
+    /// Returns a pattern or expression for this variant of `path`, binding or using each field's
+    /// value with the corresponding name in `names`.
+    ///
+    /// This always uses braces, like `Path::Variant { 0: value }`, which works for named, tuple,
+    /// and unit variants alike.
+    fn with_fields(&self, path: &TokenStream, names: &[TokenStream]) -> TokenStream {
+        let ident = &self.variant.ident;
+        let members = self.fields.iter().map(|field| &field.member);
+
+        quote!(#path::#ident { #(#members: #names),* })
+    }
+
+    /// Returns the names used to bind this variant's fields, prefixed with `prefix` so that they
+    /// cannot shadow each other.
+    fn bindings(&self, prefix: &str) -> Vec<Ident> {
+        (0..self.fields.len())
+            .map(|index| format_ident!("__mergeme_{}_{}", prefix, index))
+            .collect()
+    }
+}
+
+/// A field of a variant of the base enum, alongside its parsed attributes.
+struct EnumField<'a> {
+    /// The name or index of the field.
+    member: Member,
+
+    /// The field itself.
+    field: &'a Field,
+
+    /// The options specified by the field's `#[partial(...)]` and `#[strategy(...)]` attributes.
+    options: FieldOptions,
+}
+
+/// Derives `Merge` for an enum, generating a partial enum for each `#[partial(...)]` attribute.
+pub fn derive_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    // Like structs, independent mistakes in the enum's and fields' attributes are all reported at
+    // once.
+    let mut errors = utils::Errors::default();
+
+    let partials = errors.record(options::partial_options(input));
+    let partial_names = options::partial_names(input, partials.as_deref());
+
+    let mut variants = Vec::new();
+
+    for variant in data.variants.iter() {
+        let mut fields = Vec::new();
+
+        for (index, field) in variant.fields.iter().enumerate() {
+            let options = options::field_options(field, &partial_names)
+                .and_then(|options| reject_field_options(field, &options).map(|()| options));
+
+            let Some(options) = errors.record(options) else {
+                continue;
+            };
+
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+
+            fields.push(EnumField {
+                member,
+                field,
+                options,
+            });
+        }
+
+        variants.push(EnumVariant { variant, fields });
+    }
+
+    errors.finish()?;
+
+    let partials = partials.expect("previously verified that the enum's attributes were parsed");
+    let mut output = TokenStream::new();
+
+    // Like structs, `Mergeable` is only implemented for the first partial enum.
+    for (index, partial) in partials.iter().enumerate() {
+        output.extend(partial_output(input, &variants, partial, index == 0)?);
+    }
+
+    Ok(output)
+}
+
+/// Returns an error if a field uses an option that partial enums do not support.
+fn reject_field_options(field: &Field, options: &FieldOptions) -> Result<()> {
+    let vis = options.common.vis.is_some()
+        || options
+            .targeted
+            .iter()
+            .any(|(_, options)| options.vis.is_some());
+
+    let unsupported = [
+        ("flatten_option", options.flatten_option),
+        ("skip", options.skip),
+        ("env_nested", options.env_nested),
+        ("rename", options.rename.is_some()),
+        ("type", options.ty.is_some()),
+        ("vis", vis),
+    ];
+
+    if let Some((name, _)) = unsupported.iter().find(|(_, used)| *used) {
+        return Err(Error::new_spanned(
+            field,
+            format!("`#[partial({name})]` is not supported on the fields of enums"),
+        ));
+    }
+
+    if options.strategy.is_fallible() {
+        return Err(Error::new_spanned(
+            field,
+            "`#[strategy(try_with = ...)]` is not supported on the fields of enums",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Generates the partial enum and its implementations for a single `#[partial(...)]` attribute.
+fn partial_output(
+    input: &DeriveInput,
+    variants: &[EnumVariant<'_>],
+    partial: &PartialOptions,
+    primary: bool,
+) -> Result<TokenStream> {
+    let attr = &input.attrs[partial.attr_index];
+
+    let unsupported = [
+        ("default", partial.default),
+        ("field_vis", partial.field_vis.is_some()),
+        ("serde_skip_none", partial.serde_skip_none),
+        ("clap", partial.clap),
+        ("compact", partial.compact),
+        ("diff", partial.diff),
+        ("strip", partial.strip),
+        ("impls", !partial.impls.is_empty()),
+        ("builder", partial.builder),
+        ("getters", partial.getters),
+        ("from_env", partial.from_env),
+        ("base_builder", partial.base_builder),
+        ("by_ref", partial.by_ref),
+        ("mergeable", partial.mergeable),
+        ("ops", partial.ops),
+        ("report", partial.report.is_some()),
+        ("track_source", partial.track_source.is_some()),
+        ("error", partial.error.is_some()),
+        ("ref_partial", partial.ref_partial.is_some()),
+    ];
+
+    if let Some((name, _)) = unsupported.iter().find(|(_, used)| *used) {
+        return Err(Error::new_spanned(
+            attr,
+            format!("`{name}` is not supported when deriving `Merge` for enums"),
+        ));
+    }
+
+    let partial_name = match &partial.target {
+        PartialTarget::Generated(name) => name.clone(),
+        PartialTarget::Existing(_) => {
+            return Err(Error::new_spanned(
+                attr,
+                "`#[partial(existing = ...)]` is not supported when deriving `Merge` for enums",
+            ));
+        }
+    };
+
+    let cx = Context {
+        krate: partial
+            .krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(::mergeme)),
+        input,
+        partial_name,
+        partial_generics: utils::without_merge_bounds(&input.generics),
+        partial_vis: partial.vis.clone().unwrap_or_else(|| input.vis.clone()),
+        docs: !partial.no_docs,
+        variants,
+    };
+
+    let partial_enum = partial_enum(&cx, partial);
+
+    let partial_methods = partial_methods(&cx);
+
+    let merge_impl = merge_impl(&cx);
+
+    let partial_merge_impl = partial_merge_impl(&cx);
+
+    let partial_from = partial_from(&cx);
+
+    let mergeable_impl = (primary && utils::at_least_as_visible(&cx.partial_vis, &input.vis))
+        .then(|| mergeable_impl(&cx));
+
+    Ok(quote! {
+        #partial_enum
+
+        #partial_methods
+
+        #merge_impl
+
+        #partial_merge_impl
+
+        #partial_from
+
+        #mergeable_impl
+    })
+}
+
+/// Generates `impl Mergeable for Base`, whose `Partial` is the first partial enum.
+fn mergeable_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        ..
+    } = cx;
+
+    let enum_name = &input.ident;
+    let generics = merge_bounded_generics(cx);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::Mergeable for #enum_name #ty_generics #where_clause {
+            type Partial = #partial_name #ty_generics;
+        }
+    }
+}
+
+/// Returns the base enum's generics with `T: Merge<T>` added for the type of every
+/// `#[strategy(merge)]` field that uses a generic type parameter.
+fn merge_bounded_generics(cx: &Context<'_>) -> Generics {
+    let krate = &cx.krate;
+    let mut generics = cx.input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    for field in cx.variants.iter().flat_map(|variant| &variant.fields) {
+        let ty = &field.field.ty;
+
+        if field.options.strategy.is_merge() && utils::uses_type_params(ty, &cx.input.generics) {
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=> #ty: #krate::Merge<#ty>));
+        }
+    }
+
+    generics
+}
+
+/// Generates the partial enum itself.
+fn partial_enum(cx: &Context<'_>, partial: &PartialOptions) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        partial_generics,
+        partial_vis,
+        docs,
+        variants,
+        ..
+    } = cx;
+
+    let where_clause = &partial_generics.where_clause;
+    let attrs = utils::ordered_attrs(
+        utils::forwarded_attrs(&input.attrs, *docs),
+        partial.meta.iter().map(|meta| (partial.attr_index, meta)),
+    );
+
+    let summary = docs.then(|| {
+        let summary = format!(
+            " A partial version of `{}`, where every field is optional.",
+            input.ident,
+        );
+        let separator = input
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("doc"))
+            .then(|| quote!(#[doc = ""]));
+
+        quote! {
+            #[doc = #summary]
+            #separator
+        }
+    });
+
+    let inherited_derives = &partial.inherited_derives;
+    let inherited_derives =
+        (!inherited_derives.is_empty()).then(|| quote!(#[derive(#(#inherited_derives),*)]));
+
+    let partial_variants = variants.iter().map(|variant| {
+        let ident = &variant.variant.ident;
+        let variant_attrs =
+            utils::forwarded_attrs(&variant.variant.attrs, *docs).map(|(_, attr)| attr);
+
+        let fields = variant.fields.iter().map(|field| {
+            let (meta, _) = field.options.for_partial(partial_name);
+            let attrs =
+                utils::ordered_attrs(utils::forwarded_attrs(&field.field.attrs, *docs), meta);
+            let Field {
+                ident,
+                colon_token,
+                ty,
+                ..
+            } = field.field;
+
+            quote_spanned! {field.field.span()=>
+                #(#attrs)*
+                #ident #colon_token ::core::option::Option<#ty>
+            }
+        });
+
+        let fields = match &variant.variant.fields {
+            Fields::Named(_) => quote!({ #(#fields,)* }),
+            Fields::Unnamed(_) => quote!(( #(#fields,)* )),
+            Fields::Unit => TokenStream::new(),
+        };
+
+        quote! {
+            #(#variant_attrs)*
+            #ident #fields
+        }
+    });
+
+    // Like partial structs, fields of type `Option<T>` intentionally become `Option<Option<T>>`.
+    quote! {
+        #summary
+        #inherited_derives
+        #(#attrs)*
+        #[allow(clippy::option_option)]
+        #partial_vis enum #partial_name #partial_generics #where_clause {
+            #(#partial_variants,)*
+        }
+    }
+}
+
+/// Generates `is_complete()` for the partial enum.
+fn partial_methods(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_name,
+        partial_generics,
+        partial_vis,
+        variants,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+    let partial_path = quote!(#partial_name);
+
+    let arms = variants.iter().map(|variant| {
+        let bindings = variant.bindings("self");
+        let pattern = variant.with_fields(&partial_path, &to_tokens(&bindings));
+        let complete = utils::all(bindings.iter().map(|binding| quote!(#binding.is_some())));
+
+        quote!(#pattern => #complete,)
+    });
+
+    quote! {
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            /// Returns true if every field of the current variant is set, meaning that merging this
+            /// into a different variant would replace it.
+            #partial_vis const fn is_complete(&self) -> bool {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl Merge<Partial> for Base`.
+///
+/// If both enums are the same variant, each field is merged using its strategy. Otherwise, the base
+/// enum is replaced with the partial enum's variant if every field of it is set.
+fn merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        variants,
+        ..
+    } = cx;
+
+    let enum_name = &input.ident;
+    let generics = merge_bounded_generics(cx);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let partial_path = quote!(#partial_name);
+    let self_path = quote!(Self);
+
+    let same_variant_arms = variants.iter().map(|variant| {
+        let bindings = variant.bindings("self");
+        let other_bindings = variant.bindings("other");
+        let self_pattern = variant.with_fields(&self_path, &to_tokens(&bindings));
+        let other_pattern = variant.with_fields(&partial_path, &to_tokens(&other_bindings));
+
+        let merge_fields = variant
+            .fields
+            .iter()
+            .zip(bindings.iter().zip(other_bindings.iter()))
+            .map(|(field, (binding, other))| {
+                let merge = match field.options.strategy {
+                    MergeStrategy::Overwrite => quote!(*#binding = #other;),
+                    MergeStrategy::Merge => {
+                        utils::merge_value(krate, &field.field.ty, quote!(#binding), quote!(#other))
+                    }
+                    MergeStrategy::TryWith(_) => {
+                        unreachable!("previously verified that enum fields are not fallible")
+                    }
+                };
+
+                quote! {
+                    if let ::core::option::Option::Some(#other) = #other {
+                        #merge
+                    }
+                }
+            });
+
+        quote! {
+            (#self_pattern, #other_pattern) => {
+                #(#merge_fields)*
+                ::core::option::Option::None
+            }
+        }
+    });
+
+    let replacement_arms = variants.iter().map(|variant| {
+        let bindings = variant.bindings("other");
+        let somes: Vec<_> = bindings
+            .iter()
+            .map(|binding| quote!(::core::option::Option::Some(#binding)))
+            .collect();
+        let pattern = variant.with_fields(&partial_path, &somes);
+        let value = variant.with_fields(&self_path, &to_tokens(&bindings));
+
+        quote!(#pattern => ::core::option::Option::Some(#value),)
+    });
+
+    // The replacement is assigned after the match, since `self` is borrowed within it.
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics #krate::Merge<#partial_name #ty_generics> for #enum_name #ty_generics #where_clause {
+            fn merge_in_place(&mut self, other: #partial_name #ty_generics) {
+                #[allow(unreachable_patterns)]
+                let replacement = match (&mut *self, other) {
+                    #(#same_variant_arms)*
+                    (_, other) => match other {
+                        #(#replacement_arms)*
+                        _ => ::core::option::Option::None,
+                    },
+                };
+
+                if let ::core::option::Option::Some(replacement) = replacement {
+                    *self = replacement;
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl Merge<Partial> for Partial`.
+///
+/// If both enums are the same variant, each field is combined like the fields of partial structs.
+/// Otherwise, this is replaced with `other`, like merging a different variant into the base enum
+/// would.
+fn partial_merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        variants,
+        ..
+    } = cx;
+
+    let generics = merge_bounded_generics(cx);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let partial_path = quote!(#partial_name);
+
+    let same_variant_arms = variants.iter().map(|variant| {
+        let bindings = variant.bindings("self");
+        let other_bindings = variant.bindings("other");
+        let self_pattern = variant.with_fields(&partial_path, &to_tokens(&bindings));
+        let other_pattern = variant.with_fields(&partial_path, &to_tokens(&other_bindings));
+
+        let merge_fields = variant
+            .fields
+            .iter()
+            .zip(bindings.iter().zip(other_bindings.iter()))
+            .map(|(field, (binding, other))| {
+                let merge = match field.options.strategy {
+                    MergeStrategy::Overwrite => quote! {
+                        *#binding = ::core::option::Option::Some(#other);
+                    },
+                    MergeStrategy::Merge => {
+                        let merge = utils::merge_value(
+                            krate,
+                            &field.field.ty,
+                            quote!(existing),
+                            quote!(#other),
+                        );
+
+                        quote! {
+                            match #binding {
+                                ::core::option::Option::Some(existing) => {
+                                    #merge
+                                }
+                                ::core::option::Option::None => {
+                                    *#binding = ::core::option::Option::Some(#other);
+                                }
+                            }
+                        }
+                    }
+                    MergeStrategy::TryWith(_) => {
+                        unreachable!("previously verified that enum fields are not fallible")
+                    }
+                };
+
+                quote! {
+                    if let ::core::option::Option::Some(#other) = #other {
+                        #merge
+                    }
+                }
+            });
+
+        quote! {
+            (#self_pattern, #other_pattern) => {
+                #(#merge_fields)*
+                ::core::option::Option::None
+            }
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::Merge<#partial_name #ty_generics> for #partial_name #ty_generics #where_clause {
+            fn merge_in_place(&mut self, other: #partial_name #ty_generics) {
+                #[allow(unreachable_patterns)]
+                let replacement = match (&mut *self, other) {
+                    #(#same_variant_arms)*
+                    (_, other) => ::core::option::Option::Some(other),
+                };
+
+                if let ::core::option::Option::Some(replacement) = replacement {
+                    *self = replacement;
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl From<Base> for Partial`, which wraps every field of the variant in `Some`.
+fn partial_from(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        variants,
+        ..
+    } = cx;
+
+    let enum_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let enum_path = quote!(#enum_name);
+    let partial_path = quote!(#partial_name);
+
+    let arms = variants.iter().map(|variant| {
+        let bindings = variant.bindings("base");
+        let pattern = variant.with_fields(&enum_path, &to_tokens(&bindings));
+        let somes: Vec<_> = bindings
+            .iter()
+            .map(|binding| quote!(::core::option::Option::Some(#binding)))
+            .collect();
+        let value = variant.with_fields(&partial_path, &somes);
+
+        quote!(#pattern => #value,)
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#enum_name #ty_generics> for #partial_name #ty_generics #where_clause {
+            fn from(base: #enum_name #ty_generics) -> Self {
+                match base {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Converts each identifier into a [`TokenStream`], so it can be passed to
+/// [`EnumVariant::with_fields()`].
+fn to_tokens(idents: &[Ident]) -> Vec<TokenStream> {
+    idents.iter().map(|ident| quote!(#ident)).collect()
+}
@@ -1,13 +1,16 @@
+mod compact;
+mod enums;
+mod options;
 mod utils;
 
+use options::{
+    FieldOptions, MergeStrategy, PartialImpl, PartialOptions, PartialTarget, ReportMode,
+};
 use proc_macro2::{Ident, TokenStream};
-use quote::{ToTokens, quote, quote_spanned};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
 use syn::{
-    Data, DeriveInput, Error, Field, Fields, Meta, Result, Token,
-    parse::{Parse, ParseStream},
-    parse_macro_input,
-    punctuated::Punctuated,
-    spanned::Spanned,
+    Data, DeriveInput, Error, Field, Fields, Generics, Meta, Path, Result, Token, Type, Visibility,
+    ext::IdentExt, parse_macro_input, punctuated::Punctuated, spanned::Spanned,
 };
 
 /// Automatically implements `Merge` for a given structure.
@@ -37,6 +40,64 @@ use syn::{
 /// }
 /// ```
 ///
+/// # Generated Items
+///
+/// Besides the partial struct and `impl Merge<PartialConfig> for Config`, this derive generates
+/// the items below. If any field uses `#[strategy(try_with = "...")]`, `TryMerge` is implemented
+/// instead of `Merge`.
+///
+/// - `impl Merge<PartialConfig> for PartialConfig`, which combines two partial structs. Each field
+///   uses the same strategy as the base struct's field, so merging two partials then merging the
+///   result into the base is the same as merging each partial into the base in order.
+/// - `impl From<Config> for PartialConfig`, which wraps every field of the base struct in `Some`.
+///   `Config::into_partial()` does the same. If multiple partial structs are generated, it returns
+///   the first one. These are not generated if any field has `#[partial(type = ...)]`.
+/// - `impl TryFrom<PartialConfig> for Config`, which succeeds if every field of the partial struct
+///   is `Some`. Otherwise it returns a `MissingFields` error listing every field that is `None`.
+///   This is not implemented if any field has `#[partial(skip)]`. Without the `alloc` feature,
+///   structs with more than 128 fields fail to compile.
+/// - `PartialConfig::is_empty()`, which returns true if every field of the partial struct is
+///   `None`.
+/// - `PartialConfig::is_complete()`, which returns true if every field of the partial struct that
+///   is not `#[partial(flatten_option)]` is `Some`.
+/// - `PartialConfig::count_set()`, which returns the number of fields of the partial struct that
+///   are `Some`.
+/// - `PartialConfig::diff()`, if `#[partial(Name, diff)]` is specified.
+/// - `Config::strip()`, if `#[partial(Name, strip)]` is specified.
+/// - `impl Clone`, `impl Debug`, and `impl PartialEq` for `PartialConfig`, if they are listed in
+///   `#[partial(Name, impls(...))]`.
+/// - `PartialConfig::with_<field>()` and `PartialConfig::set_<field>()`, if
+///   `#[partial(Name, builder)]` is specified.
+/// - `PartialConfig::<field>()` and `PartialConfig::<field>_mut()`, if
+///   `#[partial(Name, getters)]` is specified.
+/// - `ConfigBuilder` and `Config::builder()`, if `#[partial(Name, base_builder)]` is specified.
+/// - `impl FromEnv for PartialConfig`, if `#[partial(Name, from_env)]` is specified.
+/// - `impl MergeRef<PartialConfig> for Config` and `impl Merge<&PartialConfig> for Config`, if
+///   `#[partial(Name, by_ref)]` is specified.
+/// - `PartialPartialConfig` and all of its items, if `#[partial(Name, mergeable)]` is specified.
+/// - `impl Mergeable for Config`, whose `Partial` is the first partial struct, unless any field
+///   uses `#[strategy(try_with = "...")]` or the partial struct is less visible than `Config`.
+/// - `impl BitOr<PartialConfig>` and `impl BitOrAssign<PartialConfig>` for `Config` and
+///   `PartialConfig`, if `#[partial(Name, ops)]` is specified.
+/// - `PartialConfigRef<'a>` and `impl Merge<PartialConfigRef<'_>> for Config`, if
+///   `#[partial(Name, ref_partial(PartialConfigRef))]` is specified.
+/// - `Config::merge_in_place_reporting()`, if `#[partial(Name, report)]` is specified.
+/// - `ConfigSources` and `Config::merge_in_place_from()`, if
+///   `#[partial(Name, track_source(Source))]` is specified.
+///
+/// If `#[partial(Name, compact)]` is specified, the partial struct's fields are private, so it
+/// instead has `<field>()`, `<field>_mut()`, `set_<field>()`, `with_<field>()`, and
+/// `take_<field>()` accessors, and always implements `Default`.
+///
+/// These are not generated when `#[partial(existing = ...)]` is used. If multiple partial structs
+/// are generated, each of them receives its own set of these items.
+///
+/// The partial struct and its fields copy the doc comments and `#[deprecated]` attributes of the
+/// base struct and its fields. The partial struct's doc comment is introduced with a summary line
+/// noting that it is a partial version of the base struct. Attributes specified with
+/// `#[partial(...)]` keep their position relative to the copied attributes, so
+/// `#[partial(doc = "...")]` can be used to extend a field's documentation.
+///
 /// # Attributes
 ///
 /// - `#[partial(Name)]`, `#[partial(Name, ...)]` (struct)
@@ -50,8 +111,364 @@ use syn::{
 ///   prefixed with "Partial". Attributes to be applied to the partial struct may optionally be
 ///   specified after name, separated by commas.
 ///
+///   This attribute may be repeated with different names to generate multiple partial structs,
+///   each with their own attributes and implementations. The options below apply to the partial
+///   struct of the attribute they are specified in.
+///
 ///   *Required*
 ///
+/// - `#[partial(Name, default)]` (struct)
+///
+///   *What*: This implements `Default` for the partial struct, setting every field to `None`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `default` to the list after the partial struct's name. Unlike
+///   `#[partial(Name, derive(Default))]`, this does not require the fields' types to implement
+///   `Default`, nor does it add `Default` bounds to generic parameters. It cannot be combined with
+///   `derive(Default)`. This allows the partial struct to be constructed with `mergeme::partial!`,
+///   such as `partial!(PartialConfig { version: 3 })`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, serde_skip_none)]` (struct)
+///
+///   *What*: This skips fields of the partial struct that are `None` when it is serialized with
+///   serde, so that only the fields that are set are written.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `serde_skip_none` to the list after the partial struct's name, alongside
+///   `derive(Serialize)`. This adds `#[serde(skip_serializing_if = "Option::is_none")]` to every
+///   field, except for fields whose `#[partial(serde(...))]` already uses `skip`,
+///   `skip_serializing`, or `skip_serializing_if`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, clap)]` (struct)
+///
+///   *What*: This allows the partial struct to derive `clap::Parser` or `clap::Args`, so that it
+///   can be parsed from command-line arguments where every flag that is not passed is `None`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `clap` to the list after the partial struct's name, alongside
+///   `derive(clap::Parser)`, and add `#[partial(arg(...))]` to fields as needed. clap only treats a
+///   field as optional if its type is written as `Option<T>`, so this writes the partial struct's
+///   fields that way instead of as `::core::option::Option<T>`. `Option` must therefore not be
+///   shadowed where the struct is defined. Doc comments are copied to the partial struct, so they
+///   become each argument's help.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, compact)]` (struct)
+///
+///   *What*: This stores the partial struct's fields without `Option`, tracking which of them are
+///   set in a bitset instead. This makes the partial struct smaller when its fields' types have no
+///   niche, such as integers and arrays, for which `Option<T>` needs extra space.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `compact` to the list after the partial struct's name. Since the fields may be
+///   uninitialized, they are private and accessed with `<field>()`, `<field>_mut()`,
+///   `set_<field>()`, `with_<field>()`, and `take_<field>()`. The partial struct always implements
+///   `Default`, but cannot derive or implement other traits, nor be combined with options that
+///   depend on its fields, such as `builder`, `getters`, `diff`, or `serde_skip_none`. This
+///   generates `unsafe` code, so it cannot be used in crates with `#![forbid(unsafe_code)]`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, diff)]` (struct)
+///
+///   *What*: This generates `PartialConfig::diff(old: &Config, new: &Config) -> PartialConfig`,
+///   which returns a partial struct containing only the fields of `new` that differ from `old`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `diff` to the list after the partial struct's name. Every field's type must
+///   implement `Clone` and `PartialEq`. Merging the result into `old` produces `new`, so this
+///   cannot be used if any field has `#[strategy(merge)]`, whose values would be combined instead
+///   of replaced, or `#[partial(flatten_option)]`, which cannot change a value back to `None`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, strip)]` (struct)
+///
+///   *What*: This generates `Config::strip(&self, partial: &PartialConfig) -> PartialConfig`, which
+///   returns the fields of the base struct that are not set in `partial`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `strip` to the list after the partial struct's name. Every field's type must
+///   implement `Clone`. If `partial` was merged into the base struct, the result contains every
+///   field that it did not override. The values that were overridden cannot be recovered, since
+///   the base struct does not store them. This may only be specified for one partial struct.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, getters)]` (struct)
+///
+///   *What*: This generates getters on the partial struct that return references to each field's
+///   value if it is set.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `getters` to the list after the partial struct's name. For a field `name: T`, this
+///   generates `name(&self) -> Option<&T>` and `name_mut(&mut self) -> Option<&mut T>`, with the
+///   same visibility as the partial struct's field. Fields whose type is written as `String` or
+///   `Vec<T>` return `Option<&str>` or `Option<&[T]>` from the shared getter instead. Renamed
+///   fields use their new name.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, from_env)]` (struct)
+///
+///   *What*: This implements `FromEnv` for the partial struct, so that
+///   `PartialConfig::from_env("APP")` reads each field from an environment variable such as
+///   `APP_PORT`, leaving the fields whose variables are not set as `None`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `from_env` to the list after the partial struct's name, and enable `mergeme`'s
+///   `env` feature. Each variable is named after the prefix and the partial struct's field in
+///   uppercase, separated by an underscore, and is parsed with `FromStr`. Fields with
+///   `#[partial(env_nested)]` are instead read with their own prefix. `from_env()` returns an
+///   `EnvError` if a variable is set but cannot be parsed.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, base_builder)]` (struct)
+///
+///   *What*: This generates a builder for the base struct, which stores its values in the partial
+///   struct.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `base_builder` to the list after the partial struct's name. This generates a
+///   `ConfigBuilder` struct created with `Config::builder()`, with a setter for each field named
+///   after the field that accepts any value that converts `Into` the field's type. `build()`
+///   returns the same `MissingFields` error as `TryFrom<PartialConfig>` if any field was not set.
+///   This may only be specified for one partial struct.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, by_ref)]` (struct)
+///
+///   *What*: This implements `MergeRef<PartialConfig>` and `Merge<&PartialConfig>` for the base
+///   struct, so that the same partial struct can be merged into many base structs without being
+///   consumed.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `by_ref` to the list after the partial struct's name. Only the fields that are
+///   `Some` are cloned, then merged the same way as `Merge<PartialConfig>`, so every field's type
+///   must implement `Clone`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, ops)]` (struct)
+///
+///   *What*: This implements `BitOr<PartialConfig>` and `BitOrAssign<PartialConfig>` for the base
+///   struct and the partial struct, so that `config | partial` is shorthand for
+///   `config.merge(partial)` and `config |= partial` for `config.merge_in_place(partial)`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `ops` to the list after the partial struct's name. `|` binds more loosely than
+///   method calls and field access, so `(config | partial).name` needs parentheses, but more
+///   tightly than comparisons, so `config | partial == other` compares the merged struct. This
+///   cannot be used with `existing` or enums.
+///
+///   The orphan rule forbids implementing a foreign trait like `BitOr` for a bare type parameter,
+///   so `mergeme` cannot implement it for every type that implements `Merge`, even behind a
+///   feature. The implementations are generated for each struct instead.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, mergeable)]` (struct)
+///
+///   *What*: This derives `Merge` for the partial struct as well, generating a partial struct of
+///   the partial struct named `PartialPartialConfig`, so that any number of override sources can
+///   be layered before being merged into `PartialConfig`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `mergeable` to the list after the partial struct's name. Every field of
+///   `PartialPartialConfig` has the same type as in `PartialConfig`, and a field that is `None`
+///   leaves the existing value alone. Fields with `#[strategy(merge)]` are merged in both. This
+///   only recurses once, so `PartialPartialConfig` does not derive `Merge` itself.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, ref_partial(RefName))]` (struct)
+///
+///   *What*: This generates a second, borrowed partial struct named `RefName`, whose fields are
+///   `Option<&'a T>`, so that values can be merged into the base struct without first being
+///   copied into an owned partial struct.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `ref_partial(...)` to the list after the partial struct's name, with the name of
+///   the borrowed partial struct inside the parenthesis. This implements `Merge<RefName<'_>>` for
+///   the base struct, which clones the values that are set, so every field's type must implement
+///   `Clone`. The borrowed partial struct implements `Clone`, `Copy`, and `Default`, and can be
+///   created from a reference to the partial struct or the base struct with `From`.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, report)]`, `#[partial(Name, report(compare))]` (struct)
+///
+///   *What*: This generates
+///   `Config::merge_in_place_reporting(&mut self, other: PartialConfig) -> MergeReport<N>`, which
+///   merges `other` like `Merge::merge_in_place()` and reports which fields were set.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `report` to the list after the partial struct's name. `N` is the number of fields.
+///   With `report(compare)`, each set field is also compared before and after merging, so that
+///   fields overwritten with an equal value are not reported as changed. This requires every
+///   field's type to implement `Clone` and `PartialEq`. This may only be specified for one partial
+///   struct.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, track_source(Source))]` (struct)
+///
+///   *What*: This records which partial struct last set each field of the base struct, so that
+///   layered configuration can report where each value came from.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `track_source(Source)` to the list after the partial struct's name, where `Source`
+///   is a type implementing `Clone`, such as an enum of configuration layers. This generates a
+///   `ConfigSources` struct with an `Option<Source>` field for each field of the base struct, which
+///   implements `Default`, and
+///   `Config::merge_in_place_from(&mut self, other: PartialConfig, source: Source, sources: &mut ConfigSources)`,
+///   which merges `other` and records `source` for every field that `other` sets. Fields with
+///   `#[strategy(merge)]` record the last source that contributed to them. This may only be
+///   specified for one partial struct.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, error = Type)]` (struct)
+///
+///   *What*: This is the error type of the `TryMerge` implementations generated when a field uses
+///   `#[strategy(try_with = "...")]`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `error = Type` to the list after the partial struct's name. Every function passed
+///   to `try_with` must return an error that converts into `Type` with `From`.
+///
+///   *Required* if any field uses `#[strategy(try_with = "...")]`. Otherwise, it is an error to
+///   specify this.
+///
+/// - `#[partial(Name, inherit_derives)]` (struct)
+///
+///   *What*: This derives the same traits for the partial struct as the base struct, out of
+///   `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, and `Hash`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `inherit_derives` to the list after the partial struct's name, and derive the
+///   traits in a separate `#[derive(...)]` after `#[derive(Merge)]`. Traits derived before or
+///   alongside `Merge` cannot be seen by this macro, so they are not inherited. Traits can be
+///   excluded with `inherit_derives(exclude(PartialEq, ...))`. Traits that are already derived or
+///   listed in `impls(...)` for the partial struct are not inherited twice.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, impls(Clone, Debug, PartialEq))]` (struct)
+///
+///   *What*: This implements `Clone`, `Debug`, and/or `PartialEq` for the partial struct, with
+///   bounds on the fields' types rather than on the generic parameters.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: List the traits to implement inside `impls(...)`. Unlike `derive(...)`, which requires
+///   every generic parameter to implement the trait, this only requires each field's type to
+///   implement it. For example, a `PartialConfig<T>` with an `Arc<T>` field implements `Clone`
+///   even if `T` does not. The `Debug` implementation only prints fields that are `Some`, without
+///   the `Some(...)` wrapper, and ends with `..` if any field is `None`. A trait cannot be both
+///   listed here and derived.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, builder)]` (struct)
+///
+///   *What*: This generates `with_<field>()` and `set_<field>()` methods for every field of the
+///   partial struct, which set the field to `Some(value)`.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `builder` to the list after the partial struct's name. `with_<field>()` takes and
+///   returns `self`, so it can be chained after `PartialConfig::default()`, while `set_<field>()`
+///   takes and returns `&mut self`. Both accept the base field's type, even for fields with
+///   `#[strategy(merge)]`, and have the same visibility as the partial struct's field.
+///
+///   *Optional*
+///
+/// - `#[partial(Name, vis = ...)]` (struct)
+///
+///   *What*: This overrides the visibility of the partial struct.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: The visibility, such as `pub` or `pub(crate)`, should be specified after `vis =`. It
+///   may also be written as a string, such as `vis = "pub(crate)"`.
+///
+///   *Optional*: The partial struct defaults to the same visibility as the base struct.
+///
+/// - `#[partial(Name, field_vis = ...)]` (struct)
+///
+///   *What*: This overrides the visibility of every field of the partial struct.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: The visibility should be specified after `field_vis =`, in the same format as `vis`.
+///   Fields with their own `#[partial(vis = ...)]` use that visibility instead.
+///
+///   *Optional*: The partial struct's fields default to the same visibility as the base struct's
+///   fields.
+///
+/// - `#[partial(Name, no_docs)]` (struct)
+///
+///   *What*: This stops doc comments from being copied to the partial struct and its fields.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: Add `no_docs` to the list after the partial struct's name. Custom documentation can
+///   still be specified with `#[partial(Name, no_docs, doc = "...")]` and
+///   `#[partial(doc = "...")]` on fields. `#[deprecated]` attributes are still copied.
+///
+///   *Optional*: Doc comments are copied by default.
+///
+/// - `#[partial(existing = path::to::Partial)]` (struct)
+///
+///   *What*: This uses an existing partial struct instead of generating a new one, only
+///   implementing `Merge` for it.
+///
+///   *Where*: This should annotate the struct itself, in place of `#[partial(Name, ...)]`.
+///
+///   *How*: The path to the existing partial struct should be specified after `existing =`. The
+///   existing struct must have fields with the same names as the base struct, each wrapped in an
+///   `Option`. No other options may be specified, and fields may not have `#[partial(...)]`
+///   attributes.
+///
+///   *Optional*: This replaces `#[partial(Name, ...)]`. The `crate` option may still be specified.
+///
+/// - `#[partial(Name, crate = "...")]` (struct)
+///
+///   *What*: This specifies the path to the `mergeme` crate used by the generated code.
+///
+///   *Where*: This should annotate the struct itself, after the partial struct's name.
+///
+///   *How*: The path should be specified as a string after `crate =`, such as
+///   `crate = "crate::utils::mergeme"`. This is useful when `mergeme` is re-exported by another
+///   crate or renamed in `Cargo.toml`.
+///
+///   *Optional*: The path defaults to `::mergeme`.
+///
 /// - `#[partial(...)]` (field)
 ///
 ///   *What*: This specifies attributes that should annotate fields within the partial struct.
@@ -59,10 +476,108 @@ use syn::{
 ///   *Where*: This should annotate fields within the struct.
 ///
 ///   *How*: This accepts a comma-separated list of attributes to be applied to the partial's field
-///   inside the parenthesis. At least one attribute is required.
+///   inside the parenthesis. At least one attribute is required, and the same attribute may not be
+///   applied to a field twice, except for doc comments.
+///
+///   If multiple partial structs are generated, the list may start with the name of one of them,
+///   such as `#[partial(PartialConfigFile, serde(alias = "v"))]`, to only apply the attributes to
+///   that partial struct's field. Otherwise the attributes apply to every partial struct.
 ///
 ///   *Optional*
 ///
+/// - `#[partial(vis = ...)]` (field)
+///
+///   *What*: This overrides the visibility of the partial struct's field.
+///
+///   *Where*: This should annotate fields within the struct.
+///
+///   *How*: The visibility, such as `pub` or `pub(crate)`, should be specified after `vis =`. It
+///   may also be written as a string, such as `vis = "pub"`. This may be combined with attributes
+///   in the same `#[partial(...)]`, and may target a specific partial struct by starting with its
+///   name. A targeted visibility takes precedence over an untargeted one.
+///
+///   *Optional*: The partial struct's fields default to the visibility specified by
+///   `#[partial(Name, field_vis = ...)]`, or the same visibility as the base struct's fields.
+///
+/// - `#[partial(flatten_option)]` (field)
+///
+///   *What*: This keeps a field of type `Option<T>` as `Option<T>` in the partial struct, rather
+///   than wrapping it in another `Option`.
+///
+///   *Where*: This should annotate fields of type `Option<T>` within the struct.
+///
+///   *How*: Add `flatten_option` to the field's `#[partial(...)]`. A flattened field that is `None`
+///   leaves the base's field unchanged, while `Some(value)` sets it to `Some(value)`. This means a
+///   flattened field cannot clear the base's field back to `None`. If that is needed, leave the
+///   field unflattened, where `Some(None)` clears the base's field. Combined with
+///   `#[strategy(merge)]`, this deeply merges the field: `Some(value)` is merged into the base's
+///   value if it is `Some`, rather than replacing it. This applies to every partial struct, so it
+///   cannot target a specific one.
+///
+///   *Optional*: Fields default to being wrapped in another `Option`.
+///
+/// - `#[partial(rename = "...")]` (field)
+///
+///   *What*: This changes the name of the field within the partial struct.
+///
+///   *Where*: This should annotate the struct's fields.
+///
+///   *How*: The value should be a string containing the new name. Keywords such as `"type"` become
+///   raw identifiers such as `r#type`, which serde and similar crates see as `type`. The generated
+///   items use the new name wherever they refer to the partial struct, including the `with_*()`
+///   and `set_*()` setters and the names reported by `MissingFields`, while merging maps the field
+///   back to the base field. This applies to every partial struct, so it cannot target a specific
+///   one.
+///
+///   *Optional*: Fields default to the same name as the base field.
+///
+/// - `#[partial(type = Option<T>, with = "path::to::function")]` (field)
+///
+///   *What*: This changes the type of the field within the partial struct, converting its value
+///   into the base field's type when it is merged.
+///
+///   *Where*: This should annotate the struct's fields.
+///
+///   *How*: `type` should be the partial struct's field type, which must be an `Option<T>`, and
+///   `with` should be a string containing the path to a function `fn(T) -> BaseTy`. The function is
+///   called when merging into the base struct and in `TryFrom<PartialConfig>`, while partial
+///   structs merged with each other keep the value as `T`. Since the base field's value cannot be
+///   converted back into `T`, `From<Config>` and `into_partial()` are not generated, and `diff` and
+///   `strip` cannot be used. This applies to every partial struct, and cannot be combined with
+///   `flatten_option` or `#[strategy(merge)]`.
+///
+///   *Optional*: Fields default to `Option<BaseTy>`.
+///
+/// - `#[partial(skip)]` (field)
+///
+///   *What*: This leaves a field out of the partial struct, so that merging never changes it.
+///
+///   *Where*: This should annotate the struct's fields.
+///
+///   *How*: Add `skip` to the field's `#[partial(...)]`. The field is omitted from the partial
+///   struct and every generated method, and `From<Config>` discards its value. Since the base
+///   struct cannot be built without the field, `TryFrom<PartialConfig>` is not implemented, and
+///   `base_builder` cannot be used. This applies to every partial struct, and cannot be combined
+///   with `#[strategy(...)]` or other `#[partial(...)]` options.
+///
+///   *Optional*: Fields default to being included in the partial struct.
+///
+/// - `#[partial(env_nested)]` (field)
+///
+///   *What*: This reads the field from environment variables with its own prefix when the partial
+///   struct is read with `from_env`, for fields whose type is itself a partial struct.
+///
+///   *Where*: This should annotate the struct's fields.
+///
+///   *How*: Add `env_nested` to the field's `#[partial(...)]`. The field's type must implement
+///   `FromEnv`, such as a partial struct with `#[partial(Name, from_env)]`. Its variables are
+///   separated from the field's name by two underscores, such as `APP_SERVER__PORT` for the field
+///   `port` of the field `server` with the prefix `APP`. The field is `None` if none of these
+///   variables are set. This applies to every partial struct, and requires one of them to have
+///   the `from_env` option.
+///
+///   *Optional*: Fields default to being read from a single variable.
+///
 /// - `#[strategy(overwrite | merge)]` (field)
 ///
 ///   *What*: This specifies how this field should be merged.
@@ -75,6 +590,24 @@ use syn::{
 ///
 ///   *Optional*: Fields without this attribute default to `overwrite`.
 ///
+/// - `#[strategy(try_with = "path::to::function")]` (field)
+///
+///   *What*: This merges the field with a function that may fail, such as one that checks a limit.
+///
+///   *Where*: This should annotate the struct's fields.
+///
+///   *How*: Add `try_with = "..."` in parenthesis, with the path to a function with the signature
+///   `fn(&mut T, T) -> Result<(), E>`, where `T` is the field's type. The function is called with
+///   the base's value and the partial's value when the partial's is `Some`. If any field uses
+///   this, `TryMerge<PartialConfig>` is implemented for the base struct and the partial struct
+///   instead of `Merge<PartialConfig>`, so every partial struct must specify an error type with
+///   `#[partial(Name, error = Type)]`, and `E` must convert into it with `From`. Merging stops at
+///   the first field that fails, so the fields before it will have already been merged. This
+///   cannot be used with `compact`, `diff`, `by_ref`, `mergeable`, `ops`, `report`,
+///   `track_source`, `ref_partial`, or `existing`.
+///
+///   *Optional*
+///
 /// # Examples
 ///
 /// ```
@@ -97,27 +630,46 @@ use syn::{
 /// }
 /// ```
 ///
-/// Struct and field attributes can be applied to the partial struct using the `#[partial(...)]`
-/// attribute. This is commonly used to implement `Default` for the partial struct, as its fields
-/// are all `Option<T>`s.
+/// Since the fields of the partial struct are all `Option<T>`s, it is commonly useful to implement
+/// `Default` for it. `#[partial(Name, default)]` does this without requiring each field's type to
+/// implement `Default`.
 ///
 /// ```
 /// # use mergeme_derive::Merge;
 /// #
+/// // Note that `Fin` does not implement `Default`.
+/// struct Fin;
+///
 /// #[derive(Merge)]
 /// // Implement `Default` for the partial struct.
-/// #[partial(PartialFish, derive(Default))]
+/// #[partial(PartialFish, default)]
 /// struct Fish {
-///     fins: u8,
+///     fin: Fin,
 ///     memory: f32,
 ///     leashed: bool,
 /// }
+///
+/// let partial_fish = PartialFish::default();
+///
+/// assert!(partial_fish.fin.is_none());
+/// assert!(partial_fish.memory.is_none());
+/// assert!(partial_fish.leashed.is_none());
+/// ```
+///
+/// Struct and field attributes can be applied to the partial struct using the `#[partial(...)]`
+/// attribute.
+///
+/// ```
+/// # use mergeme_derive::Merge;
 /// #
-/// # let partial_fish = PartialFish::default();
-/// #
-/// # assert!(partial_fish.fins.is_none());
-/// # assert!(partial_fish.memory.is_none());
-/// # assert!(partial_fish.leashed.is_none());
+/// #[derive(Merge)]
+/// // Implement `Debug` and `Clone` for the partial struct.
+/// #[partial(PartialFish, derive(Debug, Clone))]
+/// struct Fish {
+///     fins: u8,
+///     memory: f32,
+///     leashed: bool,
+/// }
 /// ```
 ///
 /// ```
@@ -137,6 +689,40 @@ use syn::{
 /// }
 /// ```
 ///
+/// If a partial struct already exists, `#[partial(existing = ...)]` can be used to only implement
+/// `Merge` for it. This is useful when adopting `#[derive(Merge)]` incrementally.
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// mod patch {
+///     // This struct is written by hand, and may have its own trait implementations.
+///     pub struct ConfigPatch {
+///         pub name: Option<String>,
+///         pub version: Option<u32>,
+///     }
+/// }
+///
+/// #[derive(Merge)]
+/// #[partial(existing = patch::ConfigPatch)]
+/// struct Config {
+///     name: String,
+///     version: u32,
+/// }
+/// #
+/// # let config = Config {
+/// #     name: "mergeme".to_string(),
+/// #     version: 1,
+/// # }
+/// # .merge(patch::ConfigPatch {
+/// #     name: None,
+/// #     version: Some(2),
+/// # });
+/// #
+/// # assert_eq!(config.name, "mergeme");
+/// # assert_eq!(config.version, 2);
+/// ```
+///
 /// Be warned that the fields of partial structs are all `Option<T>`s. This may make certain
 /// attributes like `#[serde(default)]` behave differently.
 ///
@@ -173,59 +759,276 @@ use syn::{
 /// # assert_eq!(partial_tricky.corrected_value, Some(0));
 /// ```
 ///
-/// Simple generics are supported, however only generic types that can merge with themselves can
-/// be annotated with `#[strategy(merge)]`.
+/// A base struct can be converted back into its partial struct with `into_partial()`, such as to
+/// re-serialize a resolved configuration. Every field of the partial struct will be `Some`.
 ///
 /// ```
-/// # use mergeme_derive::Merge;
+/// # use mergeme::Merge;
 /// #
 /// #[derive(Merge)]
-/// #[partial(PartialNamedData)]
-/// struct NamedData<T> {
+/// #[partial(PartialConfig)]
+/// struct Config {
 ///     name: String,
-///     data: T,
+///     version: u32,
 /// }
+///
+/// let config = Config {
+///     name: "mergeme".to_string(),
+///     version: 2,
+/// };
+///
+/// let partial: PartialConfig = config.into_partial();
+///
+/// assert_eq!(partial.name.as_deref(), Some("mergeme"));
+/// assert_eq!(partial.version, Some(2));
+/// assert!(partial.is_complete());
 /// ```
 ///
+/// Constructing a partial struct where most fields are `None` can be verbose.
+/// `#[partial(Name, builder)]` generates setters that can be chained instead.
+///
 /// ```
 /// # use mergeme::Merge;
 /// #
 /// #[derive(Merge)]
-/// #[partial(PartialNamedData)]
-/// // `T: Merge<T>` means any type that can be merged with itself.
-/// struct NamedData<T: Merge<T>>
-/// {
+/// #[partial(PartialConfig, default, builder)]
+/// struct Config {
 ///     name: String,
+///     version: u32,
 ///     #[strategy(merge)]
-///     data: T,
+///     dependencies: Vec<String>,
 /// }
+///
+/// let partial = PartialConfig::default()
+///     .with_version(3)
+///     .with_dependencies(vec!["syn".to_string()]);
+///
+/// assert_eq!(partial.name, None);
+/// assert_eq!(partial.version, Some(3));
+///
+/// let mut partial = partial;
+/// partial.set_name("mergeme".to_string()).set_version(4);
+///
+/// assert_eq!(partial.name.as_deref(), Some("mergeme"));
+/// assert_eq!(partial.version, Some(4));
 /// ```
 ///
-/// Unit structs can also derive `Merge`, however there is little point in doing so.
+/// The base struct itself can also be constructed with a builder using
+/// `#[partial(Name, base_builder)]`, which fails if any field is not set.
 ///
 /// ```
-/// # use mergeme_derive::Merge;
+/// # use mergeme::{Merge, MissingFields};
 /// #
 /// #[derive(Merge)]
-/// #[partial(PartialConfig)]
-/// struct Config;
-/// ```
+/// #[partial(PartialConfig, base_builder)]
+/// struct Config {
+///     name: String,
+///     version: u32,
+/// }
 ///
-/// # Errors
+/// // Integer literals need a suffix, since setters accept any type that converts `Into` the
+/// // field's type.
+/// let config = Config::builder().name("mergeme").version(1u32).build()?;
 ///
-/// This macro only works on named structs. Enums, unions, or tuple structs will not compile.
+/// assert_eq!(config.name, "mergeme");
+/// assert_eq!(config.version, 1);
 ///
-/// ```compile_fail
+/// let error = Config::builder().name("mergeme").build().err().unwrap();
+///
+/// assert_eq!(error.to_string(), "missing field `version`");
+/// # Ok::<(), MissingFields>(())
+/// ```
+///
+/// `#[partial(Name, ops)]` makes `config | partial` shorthand for `config.merge(partial)`, and
+/// `config |= partial` for `config.merge_in_place(partial)`.
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// #[derive(Merge, PartialEq, Debug)]
+/// #[partial(PartialConfig, default, ops)]
+/// struct Config {
+///     name: String,
+///     version: u32,
+/// }
+///
+/// let mut config = Config {
+///     name: "mergeme".to_string(),
+///     version: 1,
+/// };
+///
+/// config |= PartialConfig {
+///     version: Some(2),
+///     ..Default::default()
+/// };
+///
+/// let partial = PartialConfig {
+///     name: Some("mergeme_derive".to_string()),
+///     ..Default::default()
+/// };
+///
+/// // `|` binds more loosely than field access, so `config | partial.name` would try to merge an
+/// // `Option<String>`. The merged struct must be wrapped in parentheses instead.
+/// assert_eq!((config | partial).name, "mergeme_derive");
+///
+/// // `|` binds more tightly than `==`, so this compares the merged struct.
+/// let config = Config {
+///     name: "mergeme".to_string(),
+///     version: 1,
+/// };
+///
+/// assert!(config | PartialConfig::default() == Config {
+///     name: "mergeme".to_string(),
+///     version: 1,
+/// });
+/// ```
+///
+/// Multiple partial structs can be generated by repeating `#[partial(...)]`, which is useful when
+/// configuration is layered from several sources that each need different attributes. Field
+/// attributes can target one of the partial structs by starting with its name.
+///
+/// ```
+/// # use mergeme::Merge;
+/// # use serde::Deserialize;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfigFile, derive(Deserialize))]
+/// #[partial(PartialConfigCli, default)]
+/// struct Config {
+///     // `PartialConfigCli` does not derive `Deserialize`, so `#[serde(...)]` must only be applied
+///     // to `PartialConfigFile`.
+///     #[partial(PartialConfigFile, serde(alias = "v"))]
+///     version: u32,
+///
+///     name: String,
+/// }
+///
+/// let mut config = Config {
+///     version: 1,
+///     name: "mergeme".to_string(),
+/// };
+///
+/// config.merge_in_place(PartialConfigCli {
+///     version: Some(2),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(config.version, 2);
+/// ```
+///
+/// Simple generics are supported, however only generic types that can merge with themselves can
+/// be annotated with `#[strategy(merge)]`. The `T: Merge<T>` bound this requires is added to the
+/// implementations that merge the field, so it does not need to be written on the struct, and the
+/// partial struct never requires it.
+///
+/// ```
+/// # use mergeme_derive::Merge;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialNamedData)]
+/// struct NamedData<T> {
+///     name: String,
+///     data: T,
+/// }
+/// ```
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialNamedData)]
+/// // `Merge<PartialNamedData<T>>` is only implemented when `T: Merge<T>`.
+/// struct NamedData<T> {
+///     name: String,
+///     #[strategy(merge)]
+///     data: T,
+/// }
+/// ```
+///
+/// Fields may be conditionally compiled with `#[cfg(...)]`, and attributes like `#[strategy(...)]`
+/// and `#[partial(...)]` may be conditionally applied with `#[cfg_attr(...)]`. The compiler
+/// evaluates these before `#[derive(Merge)]` runs, so disabled fields are omitted from the partial
+/// struct and its implementations entirely.
+///
+/// ```
+/// # use mergeme_derive::Merge;
+/// #
+/// # struct TlsConfig;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig)]
+/// struct Config {
+///     name: String,
+///
+///     // `PartialConfig` only has a `tls` field when the `tls` feature is enabled.
+///     #[cfg(feature = "tls")]
+///     tls: TlsConfig,
+///
+///     // This field is only merged when the `merge-dependencies` feature is enabled.
+///     #[cfg_attr(feature = "merge-dependencies", strategy(merge))]
+///     dependencies: Vec<String>,
+/// }
+/// ```
+///
+/// Unit structs can also derive `Merge`, however there is little point in doing so.
+///
+/// ```
 /// # use mergeme_derive::Merge;
 /// #
 /// #[derive(Merge)]
-/// #[partial(PartialChoice)]
-/// enum Choice {
-///     A,
-///     B,
+/// #[partial(PartialConfig)]
+/// struct Config;
+/// ```
+///
+/// # Enums
+///
+/// Enums can also derive `Merge`, generating a partial enum with the same variants, where every
+/// field is optional. Merging a partial enum into a base enum of the same variant merges each
+/// field, while merging one of a different variant replaces the base enum entirely, but only if
+/// every field of that variant is set. If any field is missing, there is no value to fill it in
+/// with, so the merge does nothing. `PartialBackend::is_complete()` returns whether a merge would
+/// replace a different variant.
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// #[derive(Merge, Debug, PartialEq)]
+/// #[partial(PartialBackend)]
+/// enum Backend {
+///     Sqlite {
+///         path: String,
+///         #[strategy(merge)]
+///         pragmas: Vec<String>,
+///     },
+///     Postgres(String),
+///     Memory,
 /// }
+///
+/// let backend = Backend::Sqlite {
+///     path: "config.db".to_string(),
+///     pragmas: Vec::new(),
+/// };
+///
+/// // The same variant is merged field by field.
+/// let backend = backend.merge(PartialBackend::Sqlite {
+///     path: None,
+///     pragmas: Some(vec!["foreign_keys = on".to_string()]),
+/// });
+///
+/// // A different variant replaces the base enum, if it is complete.
+/// let backend = backend.merge(PartialBackend::Postgres(Some("postgres://localhost".to_string())));
+///
+/// assert_eq!(backend, Backend::Postgres("postgres://localhost".to_string()));
 /// ```
 ///
+/// Partial enums implement `Merge` with each other and `From` the base enum, but only support the
+/// `vis`, `crate`, `no_docs`, and `inherit_derives` options, and `#[strategy(...)]` and attributes
+/// within `#[partial(...)]` on their fields.
+///
+/// # Errors
+///
+/// This macro only works on named structs and enums. Unions or tuple structs will not compile.
+///
 /// ```compile_fail
 /// # use mergeme_derive::Merge;
 /// #
@@ -234,7 +1037,7 @@ use syn::{
 /// struct Config(bool, u8, Vec<String>);
 /// ```
 ///
-/// This macro requires a single `#[partial(...)]` attribute on the struct itself.
+/// This macro requires at least one `#[partial(...)]` attribute on the struct itself.
 ///
 /// ```compile_fail
 /// # use mergeme_derive::Merge;
@@ -251,9 +1054,9 @@ use syn::{
 /// # use mergeme_derive::Merge;
 /// #
 /// #[derive(Merge)]
-/// // Too many `#[partial(...)]`s.
-/// #[partial(PartialConfig1)]
-/// #[partial(PartialConfig2)]
+/// // Each partial struct must have a unique name.
+/// #[partial(PartialConfig)]
+/// #[partial(PartialConfig)]
 /// struct Config {
 ///     name: String,
 ///     dependencies: Vec<String>,
@@ -286,19 +1089,9 @@ pub fn derive_merge(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
 /// The implementation of `#[derive(Merge)]`.
 fn derive_merge_inner(input: DeriveInput) -> Result<TokenStream> {
-    let struct_name = &input.ident;
-    let struct_vis = &input.vis;
-    let struct_generics = &input.generics;
-    let (impl_generics, ty_generics, where_clause) = struct_generics.split_for_impl();
-
     let struct_fields = match &input.data {
         Data::Struct(data_struct) => &data_struct.fields,
-        Data::Enum(_) => {
-            return Err(Error::new_spanned(
-                input,
-                "`#[derive(Merge)]` only works on structs, not enums",
-            ));
-        }
+        Data::Enum(data_enum) => return enums::derive_enum(&input, data_enum),
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 input,
@@ -314,163 +1107,2085 @@ fn derive_merge_inner(input: DeriveInput) -> Result<TokenStream> {
         ));
     }
 
-    let (partial_name, partial_meta) =
-        partial_name_and_meta(&input).map(|(name, meta)| (name, meta.into_iter()))?;
+    // Independent mistakes in the struct's and fields' attributes are all reported at once.
+    let mut errors = utils::Errors::default();
+
+    let partials = errors.record(options::partial_options(&input));
+    let partial_names = options::partial_names(&input, partials.as_deref());
 
-    let partial_fields = partial_fields(struct_fields)?;
+    if partials.is_some() && partial_names.is_empty() {
+        errors.record(reject_field_partial_attrs(struct_fields));
+    }
 
-    let merge_in_place = merge_in_place(struct_fields)?;
+    let mut fields = Vec::new();
 
-    let output = quote! {
-        impl #impl_generics ::mergeme::Merge<#partial_name #ty_generics> for #struct_name #ty_generics #where_clause {
-            fn merge_in_place(&mut self, other: #partial_name #ty_generics) {
-                #merge_in_place
-            }
+    for field in struct_fields {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("previously verified that this is a named struct, not a tuple struct");
+
+        if let Some(options) = errors.record(options::field_options(field, &partial_names)) {
+            fields.push(BaseField {
+                ident,
+                partial_ident: options.rename.clone().unwrap_or_else(|| ident.clone()),
+                field,
+                options,
+            });
         }
+    }
+
+    // The remaining checks need every attribute to have been parsed.
+    errors.finish()?;
 
-        #(#[#partial_meta])*
-        #struct_vis struct #partial_name #struct_generics #where_clause {
-            #partial_fields
+    let partials = partials.expect("previously verified that the struct's attributes were parsed");
+    let mut errors = utils::Errors::default();
+
+    // Skipped fields are left out of the partial struct entirely, so none of the generated code
+    // should see them.
+    let skipped_field = fields
+        .iter()
+        .find(|field| field.options.skip)
+        .map(|field| field.ident);
+
+    fields.retain(|field| !field.options.skip);
+
+    // The builder is finished using `TryFrom`, which cannot fill in skipped fields.
+    if let Some(ident) = skipped_field
+        && partials.iter().any(|partial| partial.base_builder)
+    {
+        errors.push(Error::new_spanned(
+            ident,
+            "`base_builder` cannot be used with `#[partial(skip)]` fields, since the partial struct cannot build the base struct without them",
+        ));
+    }
+
+    // Merging a `diff()` into a merged field would combine the values instead of replacing them,
+    // and a flattened field cannot be cleared, so the result would not equal the new struct.
+    if partials.iter().any(|partial| partial.diff) {
+        let merged_field = fields
+            .iter()
+            .find(|field| field.options.strategy.is_merge());
+
+        if let Some(field) = merged_field {
+            errors.push(Error::new_spanned(
+                field.ident,
+                "`diff` cannot be used with `#[strategy(merge)]` fields, since merging the difference would combine values instead of replacing them",
+            ));
         }
-    };
 
-    Ok(output)
-}
+        let flattened_field = fields.iter().find(|field| field.options.flatten_option);
 
-fn partial_name_and_meta(input: &DeriveInput) -> Result<(Ident, Punctuated<Meta, Token![,]>)> {
-    let mut name: Option<Ident> = None;
-    let mut meta: Punctuated<Meta, Token![,]> = Punctuated::new();
+        if let Some(field) = flattened_field {
+            errors.push(Error::new_spanned(
+                field.ident,
+                "`diff` cannot be used with `#[partial(flatten_option)]` fields, since they cannot change a value back to `None`",
+            ));
+        }
+    }
 
-    for attr in input.attrs.iter() {
-        if attr.path().is_ident("partial") {
-            attr.parse_args_with(|input: ParseStream<'_>| {
-                if name.is_some() {
-                    return Err(Error::new_spanned(
-                        attr,
-                        "multiple `#[partial(...)]` attributes on the struct is disallowed",
+    // Fallible fields can only be merged by `TryMerge`, which needs an error type for every partial
+    // struct, and cannot be used by options that generate infallible merges.
+    if let Some(field) = fields
+        .iter()
+        .find(|field| field.options.strategy.is_fallible())
+    {
+        for partial in &partials {
+            let name = match &partial.target {
+                PartialTarget::Generated(name) => name,
+                PartialTarget::Existing(_) => {
+                    errors.push(Error::new_spanned(
+                        field.ident,
+                        "`#[strategy(try_with = ...)]` cannot be used with `#[partial(existing = ...)]`",
                     ));
+                    continue;
                 }
+            };
 
-                name = Some(input.parse()?);
+            let unsupported = [
+                ("compact", partial.compact),
+                ("diff", partial.diff),
+                ("by_ref", partial.by_ref),
+                ("mergeable", partial.mergeable),
+                ("ops", partial.ops),
+                ("report", partial.report.is_some()),
+                ("track_source", partial.track_source.is_some()),
+                ("ref_partial", partial.ref_partial.is_some()),
+            ];
 
-                if input.parse::<Token![,]>().is_ok() {
-                    let punctuated = input.parse_terminated(Meta::parse, Token![,])?;
-                    meta.extend(punctuated.into_pairs());
-                }
+            if let Some((option, _)) = unsupported.iter().find(|(_, used)| *used) {
+                errors.push(Error::new_spanned(
+                    field.ident,
+                    format!("`{option}` cannot be used with `#[strategy(try_with = ...)]` fields"),
+                ));
+            }
 
-                Ok(())
-            })?;
+            if partial.error.is_none() {
+                errors.push(Error::new_spanned(
+                    field.ident,
+                    format!(
+                        "`#[strategy(try_with = ...)]` requires an error type for `TryMerge`, e.g. `#[partial({name}, error = MyError)]`"
+                    ),
+                ));
+            }
         }
+    } else if let Some(error) = partials.iter().find_map(|partial| partial.error.as_ref()) {
+        errors.push(Error::new_spanned(
+            error,
+            "`error` is only used when a field has `#[strategy(try_with = ...)]`",
+        ));
     }
 
-    match name {
-        Some(name) => Ok((name, meta)),
-        None => Err(Error::new_spanned(
-            utils::DeriveInputWithoutData(input),
-            "expected `#[partial(...)]`",
-        )),
+    // Compact partial structs store each field's value directly, so they cannot store a flattened
+    // field's `None` separately from it being unset.
+    if partials.iter().any(|partial| partial.compact)
+        && let Some(field) = fields.iter().find(|field| field.options.flatten_option)
+    {
+        errors.push(Error::new_spanned(
+            field.ident,
+            "`compact` cannot be used with `#[partial(flatten_option)]` fields",
+        ));
     }
-}
 
-fn partial_fields(fields: &Fields) -> Result<TokenStream> {
-    let mut stream = TokenStream::new();
+    if !partials.iter().any(|partial| partial.from_env)
+        && let Some(field) = fields.iter().find(|field| field.options.env_nested)
+    {
+        errors.push(Error::new_spanned(
+            field.ident,
+            "`env_nested` is only used when a partial struct has the `from_env` option",
+        ));
+    }
 
-    for field in fields {
-        let Field {
-            attrs,
-            vis,
-            mutability: _,
-            ident,
-            colon_token,
-            ty,
-        } = field;
+    // `diff()` and `strip()` create the partial struct from the base struct's values, which cannot
+    // be converted into an overridden type.
+    if let Some(field) = fields.iter().find(|field| field.options.ty.is_some()) {
+        for partial in &partials {
+            let option = match partial {
+                PartialOptions { diff: true, .. } => "diff",
+                PartialOptions { strip: true, .. } => "strip",
+                _ => continue,
+            };
 
-        let mut field_meta: Punctuated<Meta, Token![,]> = Punctuated::new();
+            errors.push(Error::new_spanned(
+                field.ident,
+                format!(
+                    "`{option}` cannot be used with `#[partial(type = ...)]` fields, since the base field's value cannot be converted into the partial struct's"
+                ),
+            ));
 
-        for attr in attrs {
-            if attr.path().is_ident("partial") {
-                attr.parse_args_with(|input: ParseStream<'_>| {
-                    let punctuated = input.parse_terminated(Meta::parse, Token![,])?;
-                    field_meta.extend(punctuated);
+            break;
+        }
+    }
 
-                    Ok(())
-                })?;
-            }
+    errors.finish()?;
+
+    let mut output = TokenStream::new();
+
+    // The first generated partial struct is the one returned by `into_partial()`.
+    let mut primary = true;
+
+    for partial in partials {
+        let is_generated = matches!(partial.target, PartialTarget::Generated(_));
+
+        output.extend(partial_output(
+            &input,
+            &fields,
+            skipped_field.is_some(),
+            partial,
+            primary && is_generated,
+        ));
+
+        primary &= !is_generated;
+    }
+
+    Ok(output)
+}
+
+/// Generates the partial struct and all of its implementations for a single `#[partial(...)]`
+/// attribute.
+///
+/// If `primary` is true, inherent methods that return the partial struct and `impl Mergeable` are
+/// also generated for the base struct. If `skipped` is true, some fields were left out of
+/// `fields`, so the base struct cannot be built from the partial struct.
+fn partial_output(
+    input: &DeriveInput,
+    fields: &[BaseField<'_>],
+    skipped: bool,
+    partial: PartialOptions,
+    primary: bool,
+) -> TokenStream {
+    let PartialOptions {
+        target: partial_target,
+        meta: partial_meta,
+        attr_index,
+        vis: partial_vis,
+        field_vis,
+        default: partial_default,
+        no_docs,
+        serde_skip_none,
+        clap,
+        compact,
+        diff,
+        strip,
+        impls,
+        builder,
+        getters,
+        from_env,
+        base_builder,
+        by_ref,
+        mergeable,
+        ops,
+        report,
+        track_source,
+        error,
+        ref_partial,
+        inherited_derives,
+        krate,
+    } = partial;
+
+    let krate = krate.unwrap_or_else(|| syn::parse_quote!(::mergeme));
+
+    let partial_name = match partial_target {
+        PartialTarget::Generated(name) => name,
+        PartialTarget::Existing(path) => {
+            let struct_name = &input.ident;
+            let generics = merge_bounded_generics(&krate, &input.generics, fields);
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let merge_in_place = merge_in_place(&krate, fields);
+
+            return quote! {
+                #[allow(deprecated)]
+                #[automatically_derived]
+                impl #impl_generics #krate::Merge<#path> for #struct_name #ty_generics #where_clause {
+                    fn merge_in_place(&mut self, other: #path) {
+                        #merge_in_place
+                    }
+                }
+            };
         }
+    };
+
+    let cx = Context {
+        krate,
+        input,
+        partial_name,
+        partial_generics: utils::without_merge_bounds(&input.generics),
+        partial_vis: partial_vis.unwrap_or_else(|| input.vis.clone()),
+        field_vis,
+        docs: !no_docs,
+        serde_skip_none,
+        clap,
+        error,
+        fields,
+    };
 
-        let field_meta = field_meta.into_iter();
+    // Overridden fields cannot be converted from the base field's type back into the partial
+    // struct's.
+    let convertible = fields.iter().all(|field| field.options.ty.is_none());
 
-        let partial_ty = quote_spanned!(ty.span()=> ::core::option::Option<#ty>);
+    let ops_impls = ops.then(|| ops_impls(&cx));
 
-        let field = quote_spanned! {field.span()=>
-            #(#[#field_meta])*
-            #vis #ident #colon_token #partial_ty,
+    // `Mergeable::Partial` would expose the partial struct if it were less visible than the base
+    // struct.
+    let mergeable_impl =
+        (primary && cx.error.is_none() && utils::at_least_as_visible(&cx.partial_vis, &input.vis))
+            .then(|| mergeable_impl(&cx));
+
+    if compact {
+        let meta: Vec<Meta> = partial_meta.into_iter().collect();
+        let output =
+            compact::compact_output(&cx, &meta, attr_index, convertible, !skipped, primary);
+
+        return quote! {
+            #output
+
+            #ops_impls
+
+            #mergeable_impl
         };
+    }
+
+    let merge_impl = merge_impl(&cx);
+
+    let merge_ref_impl = by_ref.then(|| merge_ref_impl(&cx));
+
+    let partial_struct =
+        partial_struct(&cx, partial_meta, attr_index, &inherited_derives, mergeable);
+
+    let partial_methods = partial_methods(&cx);
+
+    let partial_merge_impl = partial_merge_impl(&cx);
+
+    let partial_from = convertible.then(|| partial_from(&cx));
+
+    let partial_try_into = (!skipped).then(|| partial_try_into(&cx));
+
+    let partial_default = partial_default.then(|| partial_default_impl(&cx));
+
+    let partial_diff = diff.then(|| partial_diff(&cx));
+
+    let base_strip = strip.then(|| base_strip(&cx));
+
+    let partial_builder = builder.then(|| partial_builder(&cx));
+
+    let partial_getters = getters.then(|| partial_getters(&cx));
+
+    let partial_from_env = from_env.then(|| partial_from_env(&cx));
+
+    let base_builder = base_builder.then(|| base_builder_impl(&cx));
+
+    let base_report = report.map(|mode| base_report(&cx, mode));
+
+    let base_sources = track_source.map(|source| base_sources(&cx, &source));
+
+    let partial_ref = ref_partial.map(|name| partial_ref(&cx, &name, convertible));
+
+    let partial_impls = impls.into_iter().map(|partial_impl| match partial_impl {
+        PartialImpl::Clone => partial_clone_impl(&cx),
+        PartialImpl::Debug => partial_debug_impl(&cx),
+        PartialImpl::PartialEq => partial_eq_impl(&cx),
+    });
 
-        stream.extend(field);
+    let base_methods = (primary && convertible).then(|| base_methods(&cx));
+
+    quote! {
+        #merge_impl
+
+        #merge_ref_impl
+
+        #ops_impls
+
+        #mergeable_impl
+
+        #partial_struct
+
+        #partial_methods
+
+        #partial_merge_impl
+
+        #partial_from
+
+        #partial_try_into
+
+        #partial_default
+
+        #partial_diff
+
+        #base_strip
+
+        #(#partial_impls)*
+
+        #partial_builder
+
+        #partial_getters
+
+        #partial_from_env
+
+        #base_builder
+
+        #base_report
+
+        #base_sources
+
+        #partial_ref
+
+        #base_methods
     }
+}
+
+/// The shared state used to generate the partial struct and its implementations.
+struct Context<'a> {
+    /// The path to the `mergeme` crate.
+    krate: Path,
+
+    /// The base struct that `#[derive(Merge)]` annotates.
+    input: &'a DeriveInput,
+
+    /// The name of the generated partial struct.
+    partial_name: Ident,
+
+    /// The generics of the partial struct, which are the base struct's without `Merge` bounds.
+    ///
+    /// These are used for the partial struct and implementations that only involve it.
+    /// Implementations that involve the base struct or merge fields use the base struct's generics.
+    partial_generics: Generics,
+
+    /// The visibility of the generated partial struct.
+    partial_vis: Visibility,
+
+    /// The default visibility of the generated partial struct's fields, if it should differ from
+    /// the base fields'.
+    field_vis: Option<Visibility>,
+
+    /// Whether doc comments should be copied to the partial struct.
+    docs: bool,
+
+    /// Whether fields that are `None` should be skipped when serializing the partial struct.
+    serde_skip_none: bool,
+
+    /// Whether the partial struct's fields should be written as `Option<T>`, so that clap's derive
+    /// macros recognize them as optional.
+    clap: bool,
+
+    /// The error type of the `TryMerge` implementations, which replace the `Merge`
+    /// implementations if any field has `#[strategy(try_with = "...")]`.
+    error: Option<Type>,
 
-    Ok(stream)
+    /// The fields of the base struct.
+    fields: &'a [BaseField<'a>],
 }
 
-fn merge_in_place(fields: &Fields) -> Result<TokenStream> {
-    #[derive(Default)]
-    enum MergeStrategy {
-        #[default]
-        Overwrite,
-        Merge,
-    }
+/// A named field of the base struct, alongside its parsed attributes.
+struct BaseField<'a> {
+    /// The name of the field.
+    ident: &'a Ident,
 
-    let merge_in_place = fields.iter().map(|field| -> Result<_> {
-        let mut strategy = MergeStrategy::default();
+    /// The name of the field within the partial struct, which differs from `ident` if the field
+    /// has `#[partial(rename = "...")]`.
+    partial_ident: Ident,
 
-        for attr in field.attrs.iter() {
-            if attr.path().is_ident("strategy") {
-                attr.parse_nested_meta(|meta| {
-                    if meta.path.is_ident("overwrite") {
-                        strategy = MergeStrategy::Overwrite;
-                        return Ok(());
-                    }
+    /// The field itself.
+    field: &'a Field,
 
-                    if meta.path.is_ident("merge") {
-                        strategy = MergeStrategy::Merge;
-                        return Ok(());
-                    }
+    /// The options specified by the field's `#[partial(...)]` and `#[strategy(...)]` attributes.
+    options: FieldOptions,
+}
 
-                    Err(Error::new(
-                        meta.path.span(),
-                        "expected `#[strategy(overwrite)]` or `#[strategy(merge)]`",
-                    ))
-                })?;
-            }
+impl BaseField<'_> {
+    /// Returns the type of the value stored within the partial struct's `Option<T>` field.
+    ///
+    /// This is the base field's type, unless the field is flattened, in which case it is the type
+    /// within the base field's `Option<T>`, or its type is overridden, in which case it is the type
+    /// within the overridden `Option<T>`.
+    fn value_ty(&self) -> &Type {
+        if let Some(ty) = &self.options.ty {
+            utils::option_inner(ty).expect("previously verified that `type` is `Option<T>`")
+        } else if self.options.flatten_option {
+            utils::option_inner(&self.field.ty)
+                .expect("previously verified that flattened fields are `Option<T>`")
+        } else {
+            &self.field.ty
         }
+    }
 
-        let field_name = field
-            .ident
-            .as_ref()
-            .expect("previously verified that this is a named struct, not a tuple struct");
-
-        let merge = match strategy {
-            MergeStrategy::Overwrite => quote! {
-                self.#field_name = #field_name;
-            },
-            MergeStrategy::Merge => quote! {
-                ::mergeme::Merge::merge_in_place(&mut self.#field_name, #field_name);
-            },
+    /// Converts `value`, a value stored within the partial struct's `Option<T>` field, into the
+    /// base field's type.
+    ///
+    /// This calls the `with` function if the field's type is overridden, and otherwise returns
+    /// `value` unchanged. The function is coerced to a function pointer first, so that an
+    /// incorrect signature is reported at the `with` option.
+    fn convert(&self, value: TokenStream) -> TokenStream {
+        let Some(with) = &self.options.with else {
+            return value;
         };
 
-        Ok(quote! {
-            if let ::core::option::Option::Some(#field_name) = other.#field_name {
-                #merge
+        let value_ty = self.value_ty();
+        let ty = &self.field.ty;
+
+        quote_spanned! {with.span()=>
+            {
+                let with: fn(#value_ty) -> #ty = #with;
+                with(#value)
             }
-        })
-    });
+        }
+    }
 
-    let mut stream = TokenStream::new();
+    /// Wraps `value`, a value of the base field's type, so that it can be stored in the partial
+    /// struct's field.
+    ///
+    /// This is `Some(value)`, unless the field is flattened.
+    fn wrap(&self, value: TokenStream) -> TokenStream {
+        if self.options.flatten_option {
+            value
+        } else {
+            quote!(::core::option::Option::Some(#value))
+        }
+    }
+}
 
-    for merge_field in merge_in_place {
-        merge_field?.to_tokens(&mut stream);
+/// Returns an error if any field has a `#[partial(...)]` attribute.
+///
+/// This is used when only existing partial structs are used, since there is no generated field to
+/// apply the attributes to.
+fn reject_field_partial_attrs(fields: &Fields) -> Result<()> {
+    for field in fields {
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("partial") {
+                return Err(Error::new_spanned(
+                    attr,
+                    "field-level `#[partial(...)]` attributes cannot be used with `#[partial(existing = ...)]`, since no partial struct is generated",
+                ));
+            }
+        }
     }
 
-    Ok(stream)
+    Ok(())
+}
+
+/// Generates the partial struct itself.
+///
+/// If `mergeable` is true, the partial struct also derives `Merge`, generating its own partial
+/// struct. Every field is flattened so that both partial structs have the same fields.
+fn partial_struct(
+    cx: &Context<'_>,
+    meta: Punctuated<Meta, Token![,]>,
+    attr_index: usize,
+    inherited_derives: &[Path],
+    mergeable: bool,
+) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        partial_vis,
+        docs,
+        serde_skip_none,
+        clap,
+        ..
+    } = cx;
+
+    let generics = &cx.partial_generics;
+    let where_clause = &generics.where_clause;
+    let attrs = utils::ordered_attrs(
+        utils::forwarded_attrs(&input.attrs, *docs),
+        meta.iter().map(|meta| (attr_index, meta)),
+    );
+
+    let summary = partial_summary(cx);
+
+    let fields = cx.fields.iter().map(|base_field| {
+        let BaseField {
+            partial_ident: ident,
+            field,
+            options,
+            ..
+        } = base_field;
+        let (meta, _) = options.for_partial(partial_name);
+        let vis = partial_field_vis(cx, base_field);
+
+        let Field {
+            attrs,
+            colon_token,
+            ty,
+            ..
+        } = field;
+
+        // Fields that already control whether they are serialized are left alone.
+        let skip_none = (*serde_skip_none
+            && !meta.iter().any(|(_, meta)| utils::skips_serializing(meta)))
+        .then(|| quote!(#[serde(skip_serializing_if = "::core::option::Option::is_none")]));
+
+        let attrs = utils::ordered_attrs(utils::forwarded_attrs(attrs, *docs), meta);
+
+        let mergeable_attrs = mergeable.then(|| {
+            let strategy = options
+                .strategy
+                .is_merge()
+                .then(|| quote!(#[strategy(merge)]));
+
+            quote! {
+                #[partial(flatten_option)]
+                #strategy
+            }
+        });
+
+        let partial_ty = if let Some(ty) = &options.ty {
+            quote!(#ty)
+        } else if options.flatten_option {
+            quote!(#ty)
+        } else if *clap {
+            // clap only treats a field as optional if its type is written as `Option<T>`.
+            quote_spanned!(ty.span()=> Option<#ty>)
+        } else {
+            quote_spanned!(ty.span()=> ::core::option::Option<#ty>)
+        };
+
+        quote_spanned! {field.span()=>
+            #(#attrs)*
+            #skip_none
+            #mergeable_attrs
+            #vis #ident #colon_token #partial_ty,
+        }
+    });
+
+    let inherited_derives =
+        (!inherited_derives.is_empty()).then(|| quote!(#[derive(#(#inherited_derives),*)]));
+
+    // The nested partial struct is not `mergeable` itself, so this only recurses once.
+    let mergeable = mergeable.then(|| {
+        let nested_name = format_ident!("Partial{}", partial_name);
+        let krate_str = krate.to_token_stream().to_string();
+
+        quote! {
+            #[derive(#krate::Merge)]
+            #[partial(#nested_name, crate = #krate_str)]
+        }
+    });
+
+    // Fields of type `Option<T>` intentionally become `Option<Option<T>>`, so that an unset field
+    // can be told apart from one set to `None`.
+    quote! {
+        #summary
+        #inherited_derives
+        #mergeable
+        #(#attrs)*
+        #[allow(clippy::option_option)]
+        #partial_vis struct #partial_name #generics #where_clause {
+            #(#fields)*
+        }
+    }
+}
+
+/// Returns the summary line that introduces the partial struct's copied doc comment, if doc
+/// comments are copied.
+fn partial_summary(cx: &Context<'_>) -> Option<TokenStream> {
+    let input = cx.input;
+
+    cx.docs.then(|| {
+        let summary = format!(
+            " A partial version of `{}`, where every field is optional.",
+            input.ident,
+        );
+        let separator = input
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("doc"))
+            .then(|| quote!(#[doc = ""]));
+
+        quote! {
+            #[doc = #summary]
+            #separator
+        }
+    })
+}
+
+/// Returns the visibility of a field within the partial struct.
+///
+/// This is the field's `#[partial(vis = ...)]`, the struct's `#[partial(Name, field_vis = ...)]`,
+/// or the base field's visibility, in that order.
+fn partial_field_vis<'a>(cx: &'a Context<'_>, field: &'a BaseField<'_>) -> &'a Visibility {
+    let (_, vis_override) = field.options.for_partial(&cx.partial_name);
+
+    vis_override
+        .or(cx.field_vis.as_ref())
+        .unwrap_or(&field.field.vis)
+}
+
+/// Generates `with_*()` and `set_*()` methods for each field of the partial struct, as specified
+/// by `#[partial(Name, builder)]`.
+fn partial_builder(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    let setters = fields.iter().map(|field| {
+        let ident = &field.partial_ident;
+        let vis = partial_field_vis(cx, field);
+        let ty = field.value_ty();
+
+        let name = ident.unraw();
+        let with = format_ident!("with_{}", name);
+        let set = format_ident!("set_{}", name);
+        let doc = format!(" Sets `{name}` to `Some(value)`.");
+
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            #vis fn #with(mut self, value: #ty) -> Self {
+                self.#ident = ::core::option::Option::Some(value);
+                self
+            }
+
+            #[doc = #doc]
+            #vis fn #set(&mut self, value: #ty) -> &mut Self {
+                self.#ident = ::core::option::Option::Some(value);
+                self
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            #(#setters)*
+        }
+    }
+}
+
+/// Generates `field()` and `field_mut()` getters for each field of the partial struct, as specified
+/// by `#[partial(Name, getters)]`.
+fn partial_getters(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    let getters = fields.iter().map(|field| {
+        let ident = &field.partial_ident;
+        let vis = partial_field_vis(cx, field);
+        let ty = field.value_ty();
+
+        let name = ident.unraw();
+        let getter_mut = format_ident!("{}_mut", name);
+        let doc = format!(" Returns a reference to `{name}`, or `None` if it is not set.");
+        let doc_mut =
+            format!(" Returns a mutable reference to `{name}`, or `None` if it is not set.");
+
+        // `String` and `Vec<T>` are returned as `&str` and `&[T]`, like most getters in `std`.
+        let getter = match utils::deref_target(ty) {
+            Some(target) => quote! {
+                #vis fn #ident(&self) -> ::core::option::Option<&#target> {
+                    ::core::option::Option::as_deref(&self.#ident)
+                }
+            },
+            None => quote! {
+                #vis fn #ident(&self) -> ::core::option::Option<&#ty> {
+                    ::core::option::Option::as_ref(&self.#ident)
+                }
+            },
+        };
+
+        quote! {
+            #[doc = #doc]
+            #getter
+
+            #[doc = #doc_mut]
+            #vis fn #getter_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                ::core::option::Option::as_mut(&mut self.#ident)
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            #(#getters)*
+        }
+    }
+}
+
+/// Generates `impl FromEnv for Partial`, as specified by `#[partial(Name, from_env)]`.
+///
+/// Each field is read from the variable named after its partial field in uppercase, or from the
+/// variables with that prefix if it has `#[partial(env_nested)]`.
+fn partial_from_env(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        partial_generics,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let mut generics = partial_generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    for field in fields.iter() {
+        let ty = field.value_ty();
+
+        if !utils::uses_type_params(ty, partial_generics) {
+            continue;
+        }
+
+        if field.options.env_nested {
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=> #ty: #krate::FromEnv));
+        } else {
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=> #ty: ::core::str::FromStr));
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=>
+                    <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display
+                ));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_values = fields.iter().map(|field| {
+        let partial_ident = &field.partial_ident;
+        let ty = field.value_ty();
+        let name = partial_ident.unraw().to_string().to_uppercase();
+
+        let read = if field.options.env_nested {
+            quote_spanned!(ty.span()=> #krate::env_nested::<#ty>(prefix, #name))
+        } else {
+            quote_spanned!(ty.span()=> #krate::env_var::<#ty>(prefix, #name))
+        };
+
+        quote!(#partial_ident: #read?,)
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics #krate::FromEnv for #partial_name #ty_generics #where_clause {
+            fn from_env(prefix: &str) -> ::core::result::Result<Self, #krate::EnvError> {
+                ::core::result::Result::Ok(Self {
+                    #(#field_values)*
+                })
+            }
+        }
+    }
+}
+
+/// Generates a builder for the base struct that stores its values in the partial struct, as
+/// specified by `#[partial(Name, base_builder)]`.
+///
+/// The builder is named after the base struct with a `Builder` suffix, and is created with
+/// `Base::builder()`.
+fn base_builder_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let vis = &input.vis;
+    let generics = &input.generics;
+    let where_clause = &generics.where_clause;
+    let (impl_generics, ty_generics, where_clause_impl) = generics.split_for_impl();
+
+    let builder_name = format_ident!("{}Builder", struct_name.unraw());
+    let builder_doc = format!(
+        " A builder for `{}`, created with `{}::builder()`.",
+        struct_name.unraw(),
+        struct_name.unraw(),
+    );
+    let field_names = fields.iter().map(|field| &field.partial_ident);
+
+    let setters = fields.iter().map(|base_field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            field,
+            options,
+        } = base_field;
+        let vis = &field.vis;
+        // Overridden fields accept the partial struct's value, since it cannot be created from the
+        // base field's type.
+        let ty = match options.ty {
+            Some(_) => base_field.value_ty(),
+            None => &field.ty,
+        };
+        let doc = format!(" Sets `{}`.", ident.unraw());
+        let value = base_field.wrap(quote!(::core::convert::Into::into(value)));
+
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            #vis fn #ident(mut self, value: impl ::core::convert::Into<#ty>) -> Self {
+                self.partial.#partial_ident = #value;
+                self
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder_name #generics #where_clause {
+            partial: #partial_name #ty_generics,
+        }
+
+        #[allow(deprecated)]
+        impl #impl_generics #struct_name #ty_generics #where_clause_impl {
+            /// Returns a builder where every field is unset.
+            #vis fn builder() -> #builder_name #ty_generics {
+                #builder_name {
+                    partial: #partial_name {
+                        #(#field_names: ::core::option::Option::None,)*
+                    },
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        impl #impl_generics #builder_name #ty_generics #where_clause_impl {
+            #(#setters)*
+
+            /// Builds the base struct, failing if any field was not set.
+            #vis fn build(self) -> ::core::result::Result<#struct_name #ty_generics, #krate::MissingFields> {
+                ::core::convert::TryFrom::try_from(self.partial)
+            }
+        }
+    }
+}
+
+/// Generates inherent methods for the partial struct.
+fn partial_methods(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        partial_vis,
+        fields,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+
+    let is_empty = utils::all(
+        fields
+            .iter()
+            .map(|BaseField { partial_ident, .. }| quote!(self.#partial_ident.is_none())),
+    );
+
+    // Flattened fields can always be converted into the base field, even if they are `None`.
+    let is_complete = utils::all(
+        fields
+            .iter()
+            .filter(|field| !field.options.flatten_option)
+            .map(|BaseField { partial_ident, .. }| quote!(self.#partial_ident.is_some())),
+    );
+
+    let count_set = fields
+        .iter()
+        .map(|BaseField { partial_ident, .. }| quote!(self.#partial_ident.is_some() as usize));
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            /// Returns true if every field is `None`, meaning that merging this would not change
+            /// anything.
+            #partial_vis const fn is_empty(&self) -> bool {
+                #is_empty
+            }
+
+            /// Returns true if every field that is not `#[partial(flatten_option)]` is `Some`,
+            /// meaning that this can be converted into the base struct with `TryFrom`.
+            #partial_vis const fn is_complete(&self) -> bool {
+                #is_complete
+            }
+
+            /// Returns the number of fields that are `Some`, meaning the number of fields that
+            /// would be changed by merging this.
+            #partial_vis const fn count_set(&self) -> usize {
+                0 #(+ #count_set)*
+            }
+        }
+    }
+}
+
+/// Generates inherent methods for the base struct.
+fn base_methods(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        partial_vis,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Converts this into its partial struct, wrapping every field in `Some`.
+            ///
+            /// This is equivalent to `From::from(self)`.
+            #[must_use]
+            #partial_vis fn into_partial(self) -> #partial_name #ty_generics {
+                ::core::convert::From::from(self)
+            }
+        }
+    }
+}
+
+/// Generates `impl Merge<Partial> for Base`.
+fn merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let merge_in_place = merge_in_place(krate, fields);
+
+    merge_trait_impl(
+        cx,
+        &generics,
+        &quote!(#struct_name #ty_generics),
+        &merge_in_place,
+    )
+}
+
+/// Generates `impl Merge<Partial> for #self_ty`, whose `merge_in_place()` runs `body`.
+///
+/// If any field's strategy is fallible, this instead generates `impl TryMerge<Partial>`, where
+/// `body` returns early with `?` if merging a field fails.
+fn merge_trait_impl(
+    cx: &Context<'_>,
+    generics: &Generics,
+    self_ty: &TokenStream,
+    body: &TokenStream,
+) -> TokenStream {
+    let Context {
+        krate,
+        partial_name,
+        error,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    match error {
+        Some(error) => quote! {
+            #[allow(deprecated)]
+            #[automatically_derived]
+            impl #impl_generics #krate::TryMerge<#partial_name #ty_generics> for #self_ty #where_clause {
+                type Error = #error;
+
+                fn try_merge_in_place(
+                    &mut self,
+                    other: #partial_name #ty_generics,
+                ) -> ::core::result::Result<(), Self::Error> {
+                    #body
+                    ::core::result::Result::Ok(())
+                }
+            }
+        },
+        None => quote! {
+            #[allow(deprecated)]
+            #[automatically_derived]
+            impl #impl_generics #krate::Merge<#partial_name #ty_generics> for #self_ty #where_clause {
+                fn merge_in_place(&mut self, other: #partial_name #ty_generics) {
+                    #body
+                }
+            }
+        },
+    }
+}
+
+/// Generates `impl MergeRef<Partial> for Base` and `impl Merge<&Partial> for Base`, as specified
+/// by `#[partial(Name, by_ref)]`.
+///
+/// Each field that is `Some` is cloned, then merged the same way as `impl Merge<Partial> for Base`.
+/// This requires every field's type to implement `Clone`, rather than every generic parameter.
+fn merge_ref_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let merge_fields = merge_cloned(krate, fields, |ident| quote!(&other.#ident));
+
+    let ref_generics = cloned_merge_generics(cx);
+    let (ref_impl_generics, _, ref_where_clause) = ref_generics.split_for_impl();
+
+    let generics = borrowed_merge_generics(cx);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #ref_impl_generics #krate::MergeRef<#partial_name #ty_generics> for #struct_name #ty_generics #ref_where_clause {
+            fn merge_ref_in_place(&mut self, other: &#partial_name #ty_generics) {
+                #merge_fields
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::Merge<&'__mergeme_other #partial_name #ty_generics> for #struct_name #ty_generics #where_clause {
+            fn merge_in_place(&mut self, other: &'__mergeme_other #partial_name #ty_generics) {
+                #krate::MergeRef::merge_ref_in_place(self, other);
+            }
+        }
+    }
+}
+
+/// Generates `impl Mergeable for Base`, whose `Partial` is the primary partial struct.
+fn mergeable_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::Mergeable for #struct_name #ty_generics #where_clause {
+            type Partial = #partial_name #ty_generics;
+        }
+    }
+}
+
+/// Generates `impl BitOr<Partial>` and `impl BitOrAssign<Partial>` for the base struct and the
+/// partial struct, as specified by `#[partial(Name, ops)]`.
+///
+/// These only call `Merge::merge()` and `Merge::merge_in_place()`, so they have the same bounds as
+/// `impl Merge<Partial>`.
+fn ops_impls(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let partial_ty = quote!(#partial_name #ty_generics);
+
+    let impls = [quote!(#struct_name #ty_generics), partial_ty.clone()].map(|self_ty| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::BitOr<#partial_ty> for #self_ty #where_clause {
+                type Output = Self;
+
+                fn bitor(self, other: #partial_ty) -> Self {
+                    #krate::Merge::merge(self, other)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::BitOrAssign<#partial_ty> for #self_ty #where_clause {
+                fn bitor_assign(&mut self, other: #partial_ty) {
+                    #krate::Merge::merge_in_place(self, other);
+                }
+            }
+        }
+    });
+
+    quote!(#(#impls)*)
+}
+
+/// Returns the base struct's generics for implementations that clone borrowed values before
+/// merging them, with `Clone` bounds on every field's value type.
+fn cloned_merge_generics(cx: &Context<'_>) -> Generics {
+    let mut generics = merge_bounded_generics(&cx.krate, &cx.input.generics, cx.fields);
+    let where_clause = generics.make_where_clause();
+
+    for field in cx.fields {
+        let ty = field.value_ty();
+        where_clause
+            .predicates
+            .push(syn::parse_quote_spanned!(ty.span()=> #ty: ::core::clone::Clone));
+    }
+
+    generics
+}
+
+/// Returns [`cloned_merge_generics()`] with the lifetime `'__mergeme_other` as the first
+/// parameter, for implementations that merge borrowed values.
+fn borrowed_merge_generics(cx: &Context<'_>) -> Generics {
+    let mut generics = cloned_merge_generics(cx);
+    generics
+        .params
+        .insert(0, syn::parse_quote!('__mergeme_other));
+
+    generics
+}
+
+/// Generates code that clones each borrowed value that is set, then merges it into the base
+/// struct.
+///
+/// `source` returns an expression of type `Option<&T>` or `&Option<T>` for the field with the
+/// given name in the partial struct.
+fn merge_cloned(
+    krate: &Path,
+    fields: &[BaseField<'_>],
+    source: impl Fn(&Ident) -> TokenStream,
+) -> TokenStream {
+    let merge_fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let source = source(&field.partial_ident);
+        let merge = merge_field(krate, field);
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = #source {
+                let #ident = ::core::clone::Clone::clone(#ident);
+                #merge
+            }
+        }
+    });
+
+    quote! {
+        #(#merge_fields)*
+    }
+}
+
+/// Generates the borrowed partial struct, as specified by `#[partial(Name, ref_partial(RefName))]`,
+/// whose fields are `Option<&'a T>`.
+///
+/// Besides the struct, this implements `Clone`, `Copy`, and `Default` for it, `Merge` for the base
+/// struct, and `From` for references to the partial struct and, if `convertible` is true, the base
+/// struct.
+fn partial_ref(cx: &Context<'_>, ref_name: &Ident, convertible: bool) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        partial_generics,
+        partial_vis,
+        docs,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+
+    // `'a` reads best in documentation, but cannot be used if the base struct already has it.
+    let lifetime: syn::Lifetime = if partial_generics
+        .lifetimes()
+        .any(|param| param.lifetime.ident == "a")
+    {
+        syn::parse_quote!('__mergeme_ref)
+    } else {
+        syn::parse_quote!('a)
+    };
+
+    let mut generics = partial_generics.clone();
+    generics.params.insert(0, syn::parse_quote!(#lifetime));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, partial_ty_generics, _) = partial_generics.split_for_impl();
+    let (_, base_ty_generics, _) = input.generics.split_for_impl();
+
+    let summary = docs.then(|| {
+        let summary = format!(
+            " A borrowed version of `{partial_name}`, where every field is an optional reference.",
+        );
+
+        quote!(#[doc = #summary])
+    });
+
+    let struct_fields = fields.iter().map(|field| {
+        let ident = &field.partial_ident;
+        let vis = partial_field_vis(cx, field);
+        let ty = field.value_ty();
+
+        quote! {
+            #vis #ident: ::core::option::Option<&#lifetime #ty>,
+        }
+    });
+
+    let field_names: Vec<_> = fields.iter().map(|field| &field.partial_ident).collect();
+
+    let borrow_base = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+
+        if field.options.flatten_option {
+            quote!(#partial_ident: ::core::option::Option::as_ref(&base.#ident))
+        } else {
+            quote!(#partial_ident: ::core::option::Option::Some(&base.#ident))
+        }
+    });
+
+    let from_base = convertible.then(|| {
+        quote! {
+            #[allow(deprecated)]
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<&#lifetime #struct_name #base_ty_generics> for #ref_name #ty_generics #where_clause {
+                fn from(base: &#lifetime #struct_name #base_ty_generics) -> Self {
+                    Self {
+                        #(#borrow_base,)*
+                    }
+                }
+            }
+        }
+    });
+
+    let merge_generics = borrowed_merge_generics(cx);
+    let (merge_impl_generics, merge_ty_generics, merge_where_clause) =
+        merge_generics.split_for_impl();
+    let merge_fields = merge_cloned(krate, fields, |ident| quote!(other.#ident));
+
+    quote! {
+        #summary
+        #partial_vis struct #ref_name #generics #where_clause {
+            #(#struct_fields)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::clone::Clone for #ref_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::marker::Copy for #ref_name #ty_generics #where_clause {}
+
+        #[automatically_derived]
+        impl #impl_generics ::core::default::Default for #ref_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_names: ::core::option::Option::None,)*
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<&#lifetime #partial_name #partial_ty_generics> for #ref_name #ty_generics #where_clause {
+            fn from(partial: &#lifetime #partial_name #partial_ty_generics) -> Self {
+                Self {
+                    #(#field_names: ::core::option::Option::as_ref(&partial.#field_names),)*
+                }
+            }
+        }
+
+        #from_base
+
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #merge_impl_generics #krate::Merge<#ref_name #merge_ty_generics> for #struct_name #base_ty_generics #merge_where_clause {
+            fn merge_in_place(&mut self, other: #ref_name #merge_ty_generics) {
+                #merge_fields
+            }
+        }
+    }
+}
+
+/// Generates the body of `Merge::merge_in_place()` for the base struct.
+fn merge_in_place(krate: &Path, fields: &[BaseField<'_>]) -> TokenStream {
+    let merge_fields = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+        let merge = merge_field(krate, field);
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = other.#partial_ident {
+                #merge
+            }
+        }
+    });
+
+    quote! {
+        #(#merge_fields)*
+    }
+}
+
+/// Generates the code that merges the value `#ident`, taken from the partial struct, into the base
+/// struct's field `self.#ident`.
+fn merge_field(krate: &Path, field: &BaseField<'_>) -> TokenStream {
+    let BaseField { ident, options, .. } = field;
+
+    match &options.strategy {
+        // Flattened fields are `Option<T>` in both structs, so they are merged like the fields of
+        // two partial structs.
+        _ if options.flatten_option => merge_option(krate, field, ident),
+        MergeStrategy::Overwrite => {
+            let value = field.convert(quote!(#ident));
+
+            // Assigning drops the old value in place before the next field is merged, so it is
+            // never held on the stack. `mem::replace()` would move it out first, and produced
+            // larger optimized code when compared on a struct of large enums.
+            quote! {
+                self.#ident = #value;
+            }
+        }
+        MergeStrategy::Merge => utils::merge_value(
+            krate,
+            field.value_ty(),
+            quote!(&mut self.#ident),
+            quote!(#ident),
+        ),
+        MergeStrategy::TryWith(path) => quote! {
+            #path(&mut self.#ident, #ident)?;
+        },
+    }
+}
+
+/// Generates the code that merges the value `#ident` into the `Option<T>` field `self.#ident`.
+///
+/// Overwritten fields are replaced with `Some(#ident)`, while merged and fallible fields combine
+/// both values if the field is already `Some`.
+fn merge_option(krate: &Path, field: &BaseField<'_>, ident: &Ident) -> TokenStream {
+    match &field.options.strategy {
+        MergeStrategy::Overwrite => quote! {
+            self.#ident = ::core::option::Option::Some(#ident);
+        },
+        MergeStrategy::Merge => {
+            let merge =
+                utils::merge_value(krate, field.value_ty(), quote!(existing), quote!(#ident));
+
+            quote! {
+                match &mut self.#ident {
+                    ::core::option::Option::Some(existing) => {
+                        #merge
+                    }
+                    ::core::option::Option::None => {
+                        self.#ident = ::core::option::Option::Some(#ident);
+                    }
+                }
+            }
+        }
+        MergeStrategy::TryWith(path) => quote! {
+            match &mut self.#ident {
+                ::core::option::Option::Some(existing) => {
+                    #path(existing, #ident)?;
+                }
+                ::core::option::Option::None => {
+                    self.#ident = ::core::option::Option::Some(#ident);
+                }
+            }
+        },
+    }
+}
+
+/// Generates `impl Merge<Partial> for Partial`, so that partial structs can be combined before
+/// being merged into the base struct.
+///
+/// Each field is combined using the same strategy as the base struct's field. Overwritten fields
+/// take the other partial's value if it is `Some`, while merged fields combine both values if they
+/// are both `Some`.
+fn partial_merge_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let merge_fields = fields.iter().map(|field| {
+        let ident = &field.partial_ident;
+        let merge = merge_option(krate, field, ident);
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = other.#ident {
+                #merge
+            }
+        }
+    });
+
+    merge_trait_impl(
+        cx,
+        &generics,
+        &quote!(#partial_name #ty_generics),
+        &quote!(#(#merge_fields)*),
+    )
+}
+
+/// Generates `impl From<Base> for Partial`, which wraps every field in `Some`.
+fn partial_from(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_names = fields.iter().map(|field| field.ident);
+    let partial_names = fields.iter().map(|field| &field.partial_ident);
+    let field_values = fields.iter().map(|field| {
+        let ident = field.ident;
+        field.wrap(quote!(#ident))
+    });
+
+    // Skipped fields are not in `fields`, so they are discarded by `..`.
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#struct_name #ty_generics> for #partial_name #ty_generics #where_clause {
+            fn from(base: #struct_name #ty_generics) -> Self {
+                let #struct_name { #(#field_names,)* .. } = base;
+
+                Self {
+                    #(#partial_names: #field_values,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl TryFrom<Partial> for Base`, which fails if any field is `None`.
+fn partial_try_into(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_strs = fields
+        .iter()
+        .map(|field| field.partial_ident.unraw().to_string());
+
+    let field_values = fields.iter().map(|field| {
+        let ident = field.ident;
+
+        match field.options.with {
+            Some(_) => {
+                let value = field.convert(quote!(#ident));
+                quote!(#ident: #value)
+            }
+            None => quote!(#ident),
+        }
+    });
+
+    let field_patterns = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            options,
+            ..
+        } = field;
+
+        if options.flatten_option && *ident == partial_ident {
+            quote!(#ident)
+        } else if options.flatten_option {
+            quote!(#partial_ident: #ident)
+        } else {
+            quote!(#partial_ident: ::core::option::Option::Some(#ident))
+        }
+    });
+
+    // Flattened fields are never missing, since `None` is a valid value for the base field.
+    let field_missing = fields.iter().map(|field| {
+        let partial_ident = &field.partial_ident;
+
+        if field.options.flatten_option {
+            quote!(false)
+        } else {
+            quote!(partial.#partial_ident.is_none())
+        }
+    });
+
+    // If every field is flattened, including when there are no fields, the first match arm is
+    // irrefutable.
+    let missing_arm = fields
+        .iter()
+        .any(|field| !field.options.flatten_option)
+        .then(|| {
+            quote! {
+                partial => ::core::result::Result::Err(#krate::MissingFields::new(
+                    &[#(#field_strs,)*],
+                    [#(#field_missing,)*],
+                )),
+            }
+        });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::TryFrom<#partial_name #ty_generics> for #struct_name #ty_generics #where_clause {
+            type Error = #krate::MissingFields;
+
+            fn try_from(partial: #partial_name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                match partial {
+                    #partial_name { #(#field_patterns,)* } => {
+                        ::core::result::Result::Ok(Self { #(#field_values,)* })
+                    }
+                    #missing_arm
+                }
+            }
+        }
+    }
+}
+
+/// Generates `Partial::diff()`, which creates a partial struct from the fields that differ
+/// between two base structs.
+fn partial_diff(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        partial_vis,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_types = fields.iter().map(|field| &field.field.ty);
+
+    let diff_fields = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+
+        quote! {
+            #partial_ident: if ::core::cmp::PartialEq::ne(&old.#ident, &new.#ident) {
+                ::core::option::Option::Some(::core::clone::Clone::clone(&new.#ident))
+            } else {
+                ::core::option::Option::None
+            },
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #partial_name #ty_generics #where_clause {
+            /// Returns a partial struct containing the fields of `new` that differ from `old`.
+            ///
+            /// Fields that are equal are set to `None`, while fields that differ are set to a
+            /// clone of `new`'s value. Merging the result into `old` produces `new`.
+            #[must_use]
+            #partial_vis fn diff(old: &#struct_name #ty_generics, new: &#struct_name #ty_generics) -> Self
+            where
+                #(#field_types: ::core::clone::Clone + ::core::cmp::PartialEq,)*
+            {
+                Self {
+                    #(#diff_fields)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `Base::strip()`, which creates a partial struct from the fields of the base struct
+/// that were not overridden by a given partial struct.
+fn base_strip(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        input,
+        partial_name,
+        partial_vis,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_types = fields.iter().map(|field| &field.field.ty);
+
+    let strip_fields = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+        let value = field.wrap(quote!(::core::clone::Clone::clone(&self.#ident)));
+
+        quote! {
+            #partial_ident: match partial.#partial_ident {
+                ::core::option::Option::Some(_) => ::core::option::Option::None,
+                ::core::option::Option::None => #value,
+            },
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Returns a partial struct containing the fields of this that are not set in
+            /// `partial`.
+            ///
+            /// Fields that are `Some` in `partial` are set to `None`, while all other fields are
+            /// set to a clone of this struct's value.
+            #[must_use]
+            #partial_vis fn strip(&self, partial: &#partial_name #ty_generics) -> #partial_name #ty_generics
+            where
+                #(#field_types: ::core::clone::Clone,)*
+            {
+                #partial_name {
+                    #(#strip_fields)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `Base::merge_in_place_reporting()`, which merges a partial struct into the base struct
+/// and returns a `MergeReport` of the fields that were set and changed.
+fn base_report(cx: &Context<'_>, mode: ReportMode) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        partial_vis,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_strs = fields
+        .iter()
+        .map(|field| field.partial_ident.unraw().to_string());
+    let field_count = fields.len();
+    let compare = mode == ReportMode::Compare;
+
+    // These names are prefixed so that they cannot shadow the bindings of fields with the same
+    // name.
+    let set = format_ident!("__mergeme_set");
+    let changed = format_ident!("__mergeme_changed");
+    let previous = format_ident!("__mergeme_previous");
+
+    let merge_fields = fields.iter().enumerate().map(|(index, field)| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+        let merge = merge_field(krate, field);
+
+        let (snapshot, check) = if compare {
+            (
+                Some(quote! {
+                    let #previous = ::core::clone::Clone::clone(&self.#ident);
+                }),
+                Some(quote! {
+                    #changed[#index] = ::core::cmp::PartialEq::ne(&#previous, &self.#ident);
+                }),
+            )
+        } else {
+            (None, None)
+        };
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = other.#partial_ident {
+                #set[#index] = true;
+                #snapshot
+                #merge
+                #check
+            }
+        }
+    });
+
+    let (changed_decl, changed_value, where_bounds, docs) = if compare {
+        let field_types = fields.iter().map(|field| &field.field.ty);
+
+        (
+            Some(quote! {
+                #[allow(unused_mut)]
+                let mut #changed = [false; #field_count];
+            }),
+            quote!(::core::option::Option::Some(#changed)),
+            Some(quote! {
+                where
+                    #(#field_types: ::core::clone::Clone + ::core::cmp::PartialEq,)*
+            }),
+            quote! {
+                /// Each field that is set is compared before and after merging, so overwriting a
+                /// field with an equal value is not reported as a change.
+            },
+        )
+    } else {
+        (
+            None,
+            quote!(::core::option::Option::None),
+            None,
+            quote! {
+                /// Values are not compared, so every field that is set is reported as changed.
+            },
+        )
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Merges `other` into this struct, like `Merge::merge_in_place()`, and returns a
+            /// report of which fields were set.
+            ///
+            #docs
+            #partial_vis fn merge_in_place_reporting(
+                &mut self,
+                other: #partial_name #ty_generics,
+            ) -> #krate::MergeReport<#field_count>
+            #where_bounds
+            {
+                #[allow(unused_mut)]
+                let mut #set = [false; #field_count];
+                #changed_decl
+
+                #(#merge_fields)*
+
+                #krate::MergeReport::new(&[#(#field_strs,)*], #set, #changed_value)
+            }
+        }
+    }
+}
+
+/// Generates the `BaseSources` struct and `Base::merge_in_place_from()`, which records the source
+/// of each field that a partial struct sets.
+fn base_sources(cx: &Context<'_>, source: &Type) -> TokenStream {
+    let Context {
+        krate,
+        input,
+        partial_name,
+        partial_vis,
+        fields,
+        ..
+    } = cx;
+
+    let struct_name = &input.ident;
+    let generics = merge_bounded_generics(krate, &input.generics, fields);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let sources_name = format_ident!("{}Sources", struct_name.unraw());
+
+    let struct_doc = format!(
+        " The source of each field of `{}`, as recorded by `{}::merge_in_place_from()`.",
+        struct_name.unraw(),
+        struct_name.unraw(),
+    );
+
+    let field_names: Vec<_> = fields.iter().map(|field| field.ident).collect();
+
+    let field_docs = fields.iter().map(|field| {
+        format!(
+            " The source that last set `{}`, or `None` if it has not been set.",
+            field.ident.unraw(),
+        )
+    });
+
+    let merge_fields = fields.iter().map(|field| {
+        let BaseField {
+            ident,
+            partial_ident,
+            ..
+        } = field;
+        let merge = merge_field(krate, field);
+
+        quote! {
+            if let ::core::option::Option::Some(#ident) = other.#partial_ident {
+                #merge
+                sources.#ident = ::core::option::Option::Some(::core::clone::Clone::clone(&source));
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #struct_doc]
+        #partial_vis struct #sources_name {
+            #(
+                #[doc = #field_docs]
+                #partial_vis #field_names: ::core::option::Option<#source>,
+            )*
+        }
+
+        #[automatically_derived]
+        impl ::core::default::Default for #sources_name {
+            fn default() -> Self {
+                Self {
+                    #(#field_names: ::core::option::Option::None,)*
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Merges `other` into this struct, like `Merge::merge_in_place()`, and records
+            /// `source` in `sources` for every field that `other` sets.
+            #[allow(unused_variables)]
+            #partial_vis fn merge_in_place_from(
+                &mut self,
+                other: #partial_name #ty_generics,
+                source: #source,
+                sources: &mut #sources_name,
+            ) {
+                #(#merge_fields)*
+            }
+        }
+    }
+}
+
+/// Returns the base struct's generics with `T: Merge<T>` added for the value type of every
+/// `#[strategy(merge)]` field that uses a generic type parameter.
+///
+/// This is used by implementations that merge fields, so that `struct Data<T>` does not need to
+/// bound `T` itself. Fields with concrete types are skipped, since an unsatisfied bound on them
+/// would be reported at the derive rather than where the value is merged.
+fn merge_bounded_generics(krate: &Path, generics: &Generics, fields: &[BaseField<'_>]) -> Generics {
+    let mut bounded = generics.clone();
+    let where_clause = bounded.make_where_clause();
+
+    for field in fields {
+        let ty = field.value_ty();
+
+        if field.options.strategy.is_merge() && utils::uses_type_params(ty, generics) {
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=> #ty: #krate::Merge<#ty>));
+        }
+    }
+
+    bounded
+}
+
+/// Returns the partial struct's generics with `bound` added for the value type of every field.
+///
+/// This is used to implement traits for the partial struct without requiring generic parameters
+/// to implement the trait themselves, similar to a "perfect derive."
+fn field_bounded_generics(cx: &Context<'_>, bound: TokenStream) -> Generics {
+    let mut generics = cx.partial_generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    for field in cx.fields {
+        let ty = field.value_ty();
+        where_clause
+            .predicates
+            .push(syn::parse_quote_spanned!(ty.span()=> #ty: #bound));
+    }
+
+    generics
+}
+
+/// Generates `impl Clone for Partial`, as specified by `impls(Clone)`.
+fn partial_clone_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let generics = field_bounded_generics(cx, quote!(::core::clone::Clone));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_names = fields.iter().map(|field| &field.partial_ident);
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::clone::Clone for #partial_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#field_names: ::core::clone::Clone::clone(&self.#field_names),)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl Debug for Partial`, as specified by `impls(Debug)`.
+///
+/// Only fields that are `Some` are printed, without the `Some(...)` wrapper. If any field is
+/// `None`, the output ends with `..` to signal that fields were omitted.
+fn partial_debug_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let generics = field_bounded_generics(cx, quote!(::core::fmt::Debug));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let partial_str = partial_name.unraw().to_string();
+
+    let debug_fields = fields.iter().map(|field| {
+        let ident = &field.partial_ident;
+        let field_str = ident.unraw().to_string();
+
+        quote! {
+            match &self.#ident {
+                ::core::option::Option::Some(value) => {
+                    debug.field(#field_str, value);
+                }
+                ::core::option::Option::None => elided = true,
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Debug for #partial_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut debug = f.debug_struct(#partial_str);
+                #[allow(unused_mut)]
+                let mut elided = false;
+
+                #(#debug_fields)*
+
+                if elided {
+                    debug.finish_non_exhaustive()
+                } else {
+                    debug.finish()
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl PartialEq for Partial`, as specified by `impls(PartialEq)`.
+fn partial_eq_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let generics = field_bounded_generics(cx, quote!(::core::cmp::PartialEq));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let eq = utils::all(fields.iter().map(
+        |BaseField { partial_ident, .. }| {
+            quote!(::core::cmp::PartialEq::eq(&self.#partial_ident, &other.#partial_ident))
+        },
+    ));
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::PartialEq for #partial_name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                #eq
+            }
+        }
+    }
+}
+
+/// Generates `impl Default for Partial`, which sets every field to `None`.
+fn partial_default_impl(cx: &Context<'_>) -> TokenStream {
+    let Context {
+        partial_generics,
+        partial_name,
+        fields,
+        ..
+    } = cx;
+
+    let (impl_generics, ty_generics, where_clause) = partial_generics.split_for_impl();
+    let field_names = fields.iter().map(|field| &field.partial_ident);
+
+    quote! {
+        #[allow(deprecated)]
+        #[automatically_derived]
+        impl #impl_generics ::core::default::Default for #partial_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_names: ::core::option::Option::None,)*
+                }
+            }
+        }
+    }
 }
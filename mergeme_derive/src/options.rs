@@ -0,0 +1,1436 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, Field, Lit, LitStr, Meta, Path, Result,
+    Token, Type, Visibility, parse::ParseStream, punctuated::Punctuated, spanned::Spanned, token,
+};
+
+use crate::utils;
+
+/// The contents of the `#[partial(...)]` attribute on the struct itself.
+pub struct PartialOptions {
+    /// The partial struct that will be merged into the base struct.
+    pub target: PartialTarget,
+
+    /// Attributes that should be applied to the partial struct.
+    pub meta: Punctuated<Meta, Token![,]>,
+
+    /// The index of this `#[partial(...)]` attribute within the struct's attributes, used to keep
+    /// `meta` in the same order relative to copied attributes like doc comments.
+    pub attr_index: usize,
+
+    /// The visibility of the partial struct, if it should differ from the base struct's.
+    pub vis: Option<Visibility>,
+
+    /// The visibility of the partial struct's fields, if it should differ from the base fields'.
+    pub field_vis: Option<Visibility>,
+
+    /// Whether a `Default` implementation should be generated for the partial struct.
+    pub default: bool,
+
+    /// Whether doc comments should not be copied from the base struct to the partial struct.
+    pub no_docs: bool,
+
+    /// Whether `#[serde(skip_serializing_if = "Option::is_none")]` should be added to every field
+    /// of the partial struct that does not already skip serializing.
+    pub serde_skip_none: bool,
+
+    /// Whether the partial struct's fields should be written as `Option<T>` rather than
+    /// `::core::option::Option<T>`, so that clap's derive macros recognize them, as specified by
+    /// `clap`.
+    pub clap: bool,
+
+    /// Whether the partial struct should store its fields without `Option`, tracking which are set
+    /// in a bitset, as specified by `compact`.
+    pub compact: bool,
+
+    /// Whether a `diff()` function should be generated for the partial struct.
+    pub diff: bool,
+
+    /// Whether a `strip()` method returning the partial struct should be generated for the base
+    /// struct.
+    pub strip: bool,
+
+    /// Traits that should be implemented for the partial struct with bounds on the fields' types,
+    /// as specified by `impls(...)`.
+    pub impls: Vec<PartialImpl>,
+
+    /// Whether `with_*()` and `set_*()` methods should be generated for the partial struct.
+    pub builder: bool,
+
+    /// Whether getters returning references to the partial struct's fields should be generated.
+    pub getters: bool,
+
+    /// Whether `FromEnv` should be implemented for the partial struct, as specified by
+    /// `from_env`.
+    pub from_env: bool,
+
+    /// Whether a builder for the base struct, backed by the partial struct, should be generated.
+    pub base_builder: bool,
+
+    /// Whether `Merge<&Partial>` should be implemented for the base struct, cloning the fields that
+    /// are set.
+    pub by_ref: bool,
+
+    /// Whether the partial struct should itself derive `Merge`, generating a partial struct of the
+    /// partial struct, as specified by `mergeable`.
+    pub mergeable: bool,
+
+    /// Whether `BitOr` and `BitOrAssign` should be implemented as shorthands for merging, as
+    /// specified by `ops`.
+    pub ops: bool,
+
+    /// Whether a `merge_in_place_reporting()` method should be generated for the base struct, and
+    /// whether it should compare values to find which fields changed.
+    pub report: Option<ReportMode>,
+
+    /// The type used to record where each field's value came from, as specified by
+    /// `track_source(Source)`.
+    pub track_source: Option<Type>,
+
+    /// The error type of the `TryMerge` implementations, as specified by `error = Type`.
+    pub error: Option<Type>,
+
+    /// The name of the borrowed partial struct with `Option<&T>` fields, as specified by
+    /// `ref_partial(Name)`.
+    pub ref_partial: Option<Ident>,
+
+    /// Traits derived by the base struct that should also be derived by the partial struct, as
+    /// specified by `inherit_derives`.
+    pub inherited_derives: Vec<Path>,
+
+    /// The path to the `mergeme` crate, if it should differ from `::mergeme`.
+    pub krate: Option<Path>,
+}
+
+/// The partial struct that `Merge` is implemented for.
+pub enum PartialTarget {
+    /// A new partial struct with the given name should be generated.
+    Generated(Ident),
+
+    /// An existing partial struct at the given path should be used, and no new struct should be
+    /// generated.
+    Existing(Path),
+}
+
+/// Returns true if `meta` is a single identifier that looks like the name of a partial struct,
+/// such as `PartialConfig`, rather than an attribute.
+///
+/// Attributes are conventionally lowercase, so this checks for an uppercase first letter.
+fn names_partial_struct(meta: &Meta) -> bool {
+    match meta {
+        Meta::Path(path) => path.get_ident().is_some_and(|ident| {
+            ident
+                .to_string()
+                .starts_with(|c: char| c.is_ascii_uppercase())
+        }),
+        _ => false,
+    }
+}
+
+/// Returns the first field-level `#[partial(Name)]` attribute that only contains what looks like
+/// the name of a partial struct, like the struct-level attribute does.
+fn misplaced_partial_name(input: &DeriveInput) -> Option<&Attribute> {
+    let fields: Vec<&Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    };
+
+    fields
+        .into_iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| {
+            attr.path().is_ident("partial")
+                && attr
+                    .parse_args::<Meta>()
+                    .is_ok_and(|meta| names_partial_struct(&meta))
+        })
+}
+
+/// Returns true if both attributes are written the same way, ignoring whitespace.
+///
+/// Doc comments are never considered the same, since repeating a line, such as an empty one, is
+/// meaningful.
+fn same_meta(a: &Meta, b: &Meta) -> bool {
+    if let Meta::NameValue(name_value) = a
+        && name_value.path.is_ident("doc")
+    {
+        return false;
+    }
+
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
+}
+
+/// Parses every `#[partial(...)]` attribute on the struct itself, one for each partial struct.
+pub fn partial_options(input: &DeriveInput) -> Result<Vec<PartialOptions>> {
+    let derive_input = input;
+    let mut options: Vec<PartialOptions> = Vec::new();
+
+    for (attr_index, attr) in input.attrs.iter().enumerate() {
+        if attr.path().is_ident("partial") {
+            match &attr.meta {
+                Meta::Path(_) => {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "expected the partial struct's name in parenthesis, e.g. `#[partial(PartialConfig)]`",
+                    ));
+                }
+                Meta::NameValue(name_value) => {
+                    let example = match &name_value.value {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(name),
+                            ..
+                        }) => name.value(),
+                        _ => "PartialConfig".to_string(),
+                    };
+
+                    return Err(Error::new_spanned(
+                        attr,
+                        format!(
+                            "`#[partial = ...]` is not supported, the partial struct's name should be in parenthesis, e.g. `#[partial({example})]`"
+                        ),
+                    ));
+                }
+                Meta::List(_) => {}
+            }
+
+            attr.parse_args_with(|input: ParseStream<'_>| {
+                // Options like `derive(...)` or literals cannot be the name, which usually means
+                // it was forgotten.
+                if input.is_empty()
+                    || input.peek(Lit)
+                    || (input.peek(syn::Ident) && input.peek2(token::Paren))
+                {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "expected the partial struct's name as the first argument, e.g. `#[partial(PartialConfig, derive(Default))]`",
+                    ));
+                }
+
+                let target = if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                    let key: Ident = input.parse()?;
+
+                    if key != "existing" {
+                        return Err(Error::new_spanned(
+                            key,
+                            "expected the partial struct's name or `existing = path::to::Partial`",
+                        ));
+                    }
+
+                    input.parse::<Token![=]>()?;
+
+                    PartialTarget::Existing(input.parse()?)
+                } else {
+                    let name: Ident = input.parse()?;
+
+                    let duplicate = options.iter().any(|options| {
+                        matches!(&options.target, PartialTarget::Generated(other) if *other == name)
+                    });
+
+                    if duplicate {
+                        return Err(Error::new_spanned(
+                            name,
+                            "a partial struct with this name is already generated",
+                        ));
+                    }
+
+                    PartialTarget::Generated(name)
+                };
+
+                let mut inherit_derives: Option<Vec<Ident>> = None;
+                let mut compact_flag: Option<Ident> = None;
+
+                let mut parsed = PartialOptions {
+                    target,
+                    meta: Punctuated::new(),
+                    attr_index,
+                    vis: None,
+                    field_vis: None,
+                    default: false,
+                    no_docs: false,
+                    serde_skip_none: false,
+                    clap: false,
+                    compact: false,
+                    diff: false,
+                    strip: false,
+                    impls: Vec::new(),
+                    builder: false,
+                    getters: false,
+                    from_env: false,
+                    base_builder: false,
+                    by_ref: false,
+                    mergeable: false,
+                    ops: false,
+                    report: None,
+                    track_source: None,
+                    error: None,
+                    ref_partial: None,
+                    inherited_derives: Vec::new(),
+                    krate: None,
+                };
+
+                while input.parse::<Token![,]>().is_ok() {
+                    if input.is_empty() {
+                        break;
+                    }
+
+                    if utils::peek_flag(input, "default") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.default {
+                            return Err(Error::new_spanned(flag, "duplicate `default` option"));
+                        }
+
+                        parsed.default = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "no_docs") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.no_docs {
+                            return Err(Error::new_spanned(flag, "duplicate `no_docs` option"));
+                        }
+
+                        parsed.no_docs = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "serde_skip_none") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.serde_skip_none {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "duplicate `serde_skip_none` option",
+                            ));
+                        }
+
+                        parsed.serde_skip_none = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "clap") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.clap {
+                            return Err(Error::new_spanned(flag, "duplicate `clap` option"));
+                        }
+
+                        parsed.clap = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "compact") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.compact {
+                            return Err(Error::new_spanned(flag, "duplicate `compact` option"));
+                        }
+
+                        parsed.compact = true;
+                        compact_flag = Some(flag);
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "diff") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.diff {
+                            return Err(Error::new_spanned(flag, "duplicate `diff` option"));
+                        }
+
+                        parsed.diff = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "strip") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.strip {
+                            return Err(Error::new_spanned(flag, "duplicate `strip` option"));
+                        }
+
+                        // `strip()` is a method on the base struct, so it can only return one
+                        // partial struct.
+                        if options.iter().any(|options| options.strip) {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "`strip` may only be specified for one partial struct",
+                            ));
+                        }
+
+                        parsed.strip = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "builder") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.builder {
+                            return Err(Error::new_spanned(flag, "duplicate `builder` option"));
+                        }
+
+                        parsed.builder = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "getters") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.getters {
+                            return Err(Error::new_spanned(flag, "duplicate `getters` option"));
+                        }
+
+                        parsed.getters = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "from_env") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.from_env {
+                            return Err(Error::new_spanned(flag, "duplicate `from_env` option"));
+                        }
+
+                        parsed.from_env = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "base_builder") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.base_builder {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "duplicate `base_builder` option",
+                            ));
+                        }
+
+                        // `builder()` is a method on the base struct, so it can only use one
+                        // partial struct.
+                        if options.iter().any(|options| options.base_builder) {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "`base_builder` may only be specified for one partial struct",
+                            ));
+                        }
+
+                        parsed.base_builder = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "by_ref") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.by_ref {
+                            return Err(Error::new_spanned(flag, "duplicate `by_ref` option"));
+                        }
+
+                        parsed.by_ref = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "mergeable") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.mergeable {
+                            return Err(Error::new_spanned(flag, "duplicate `mergeable` option"));
+                        }
+
+                        parsed.mergeable = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "ops") {
+                        let flag: Ident = input.parse()?;
+
+                        if parsed.ops {
+                            return Err(Error::new_spanned(flag, "duplicate `ops` option"));
+                        }
+
+                        parsed.ops = true;
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "report") || utils::peek_list(input, "report") {
+                        let key: Ident = input.parse()?;
+
+                        if parsed.report.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `report` option"));
+                        }
+
+                        // `merge_in_place_reporting()` is a method on the base struct, so it can
+                        // only accept one partial struct.
+                        if options.iter().any(|options| options.report.is_some()) {
+                            return Err(Error::new_spanned(
+                                key,
+                                "`report` may only be specified for one partial struct",
+                            ));
+                        }
+
+                        let mut mode = ReportMode::Set;
+
+                        if input.peek(token::Paren) {
+                            let content;
+                            syn::parenthesized!(content in input);
+
+                            let compare: Ident = content.parse()?;
+
+                            if compare != "compare" || !content.is_empty() {
+                                return Err(Error::new_spanned(
+                                    compare,
+                                    "expected `report(compare)`",
+                                ));
+                            }
+
+                            mode = ReportMode::Compare;
+                        }
+
+                        parsed.report = Some(mode);
+                        continue;
+                    }
+
+                    if utils::peek_list(input, "ref_partial") {
+                        let key: Ident = input.parse()?;
+                        let content;
+                        syn::parenthesized!(content in input);
+
+                        if parsed.ref_partial.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `ref_partial` option"));
+                        }
+
+                        parsed.ref_partial = Some(content.parse()?);
+                        continue;
+                    }
+
+                    if utils::peek_list(input, "track_source") {
+                        let key: Ident = input.parse()?;
+                        let content;
+                        syn::parenthesized!(content in input);
+
+                        if parsed.track_source.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `track_source` option"));
+                        }
+
+                        // `merge_in_place_from()` is a method on the base struct, and the sources
+                        // struct is named after the base struct, so only one can be generated.
+                        if options.iter().any(|options| options.track_source.is_some()) {
+                            return Err(Error::new_spanned(
+                                key,
+                                "`track_source` may only be specified for one partial struct",
+                            ));
+                        }
+
+                        parsed.track_source = Some(content.parse()?);
+                        continue;
+                    }
+
+                    if utils::peek_flag(input, "inherit_derives")
+                        || utils::peek_list(input, "inherit_derives")
+                    {
+                        let key: Ident = input.parse()?;
+
+                        if inherit_derives.is_some() {
+                            return Err(Error::new_spanned(
+                                key,
+                                "duplicate `inherit_derives` option",
+                            ));
+                        }
+
+                        let mut exclude = Vec::new();
+
+                        if input.peek(token::Paren) {
+                            let content;
+                            syn::parenthesized!(content in input);
+
+                            let meta: Meta = content.parse()?;
+                            let list = match meta {
+                                Meta::List(list) if list.path.is_ident("exclude") => list,
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        meta,
+                                        "expected `inherit_derives(exclude(...))`",
+                                    ));
+                                }
+                            };
+
+                            exclude = list
+                                .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?
+                                .into_iter()
+                                .collect();
+                        }
+
+                        inherit_derives = Some(exclude);
+                        continue;
+                    }
+
+                    if utils::peek_list(input, "impls") {
+                        let key: Ident = input.parse()?;
+                        let content;
+                        syn::parenthesized!(content in input);
+
+                        if !parsed.impls.is_empty() {
+                            return Err(Error::new_spanned(key, "duplicate `impls` option"));
+                        }
+
+                        for name in Punctuated::<Ident, Token![,]>::parse_terminated(&content)? {
+                            let partial_impl = match name.to_string().as_str() {
+                                "Clone" => PartialImpl::Clone,
+                                "Debug" => PartialImpl::Debug,
+                                "PartialEq" => PartialImpl::PartialEq,
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        name,
+                                        "expected `Clone`, `Debug`, or `PartialEq`",
+                                    ));
+                                }
+                            };
+
+                            if parsed.impls.contains(&partial_impl) {
+                                return Err(Error::new_spanned(name, "duplicate trait in `impls`"));
+                            }
+
+                            parsed.impls.push(partial_impl);
+                        }
+
+                        continue;
+                    }
+
+                    if utils::peek_key(input, "vis") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if parsed.vis.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `vis` option"));
+                        }
+
+                        parsed.vis = Some(utils::parse_vis(input)?);
+                        continue;
+                    }
+
+                    if utils::peek_key(input, "field_vis") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if parsed.field_vis.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `field_vis` option"));
+                        }
+
+                        parsed.field_vis = Some(utils::parse_vis(input)?);
+                        continue;
+                    }
+
+                    if utils::peek_key(input, "error") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if parsed.error.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `error` option"));
+                        }
+
+                        parsed.error = Some(input.parse()?);
+                        continue;
+                    }
+
+                    if input.peek(Token![crate]) && input.peek2(Token![=]) {
+                        let key: Token![crate] = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if parsed.krate.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `crate` option"));
+                        }
+
+                        parsed.krate = Some(input.parse::<LitStr>()?.parse()?);
+                        continue;
+                    }
+
+                    parsed.meta.push(input.parse()?);
+                }
+
+                if !input.is_empty() {
+                    return Err(input.error("expected `,`"));
+                }
+
+                if let PartialTarget::Existing(_) = parsed.target
+                    && (parsed.default
+                        || parsed.no_docs
+                        || parsed.serde_skip_none
+                        || parsed.clap
+                        || parsed.compact
+                        || parsed.diff
+                        || parsed.strip
+                        || !parsed.impls.is_empty()
+                        || parsed.builder
+                        || parsed.getters
+                        || parsed.from_env
+                        || parsed.base_builder
+                        || parsed.by_ref
+                        || parsed.mergeable
+                        || parsed.ops
+                        || parsed.report.is_some()
+                        || parsed.track_source.is_some()
+                        || parsed.error.is_some()
+                        || parsed.ref_partial.is_some()
+                        || inherit_derives.is_some()
+                        || parsed.vis.is_some()
+                        || parsed.field_vis.is_some()
+                        || !parsed.meta.is_empty())
+                {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "`#[partial(existing = ...)]` does not accept other options, since no partial struct is generated",
+                    ));
+                }
+
+                // Compact partial structs cannot expose their fields directly, so only the options that
+                // do not depend on them are supported.
+                if let Some(flag) = compact_flag {
+                    let unsupported = [
+                        ("serde_skip_none", parsed.serde_skip_none),
+                        ("clap", parsed.clap),
+                        ("diff", parsed.diff),
+                        ("strip", parsed.strip),
+                        ("impls", !parsed.impls.is_empty()),
+                        ("builder", parsed.builder),
+                        ("getters", parsed.getters),
+                        ("from_env", parsed.from_env),
+                        ("base_builder", parsed.base_builder),
+                        ("by_ref", parsed.by_ref),
+                        ("mergeable", parsed.mergeable),
+                        ("report", parsed.report.is_some()),
+                        ("track_source", parsed.track_source.is_some()),
+                        ("error", parsed.error.is_some()),
+                        ("ref_partial", parsed.ref_partial.is_some()),
+                        ("inherit_derives", inherit_derives.is_some()),
+                    ];
+
+                    if let Some((name, _)) = unsupported.iter().find(|(_, used)| *used) {
+                        return Err(Error::new_spanned(
+                            flag,
+                            format!("`{name}` cannot be used with `compact`"),
+                        ));
+                    }
+
+                    if let Some(derive) = parsed.meta.iter().find(|meta| meta.path().is_ident("derive")) {
+                        return Err(Error::new_spanned(
+                            derive,
+                            "`compact` partial structs cannot derive traits, since their fields may be uninitialized",
+                        ));
+                    }
+                }
+
+                if parsed.default
+                    && let Some(derive) = find_derive(&parsed.meta, "Default")?
+                {
+                    return Err(Error::new_spanned(
+                        derive,
+                        "`derive(Default)` conflicts with the `default` option, which already implements `Default` for the partial struct",
+                    ));
+                }
+
+                if let Some(exclude) = inherit_derives {
+                    parsed.inherited_derives =
+                        inherited_derives(derive_input, &parsed, &exclude)?;
+                }
+
+                for partial_impl in parsed.impls.iter() {
+                    if let Some(derive) = find_derive(&parsed.meta, partial_impl.name())? {
+                        return Err(Error::new_spanned(
+                            derive,
+                            format!(
+                                "`derive({0})` conflicts with `impls({0})`, which already implements `{0}` for the partial struct",
+                                partial_impl.name(),
+                            ),
+                        ));
+                    }
+                }
+
+                options.push(parsed);
+
+                Ok(())
+            })?;
+        }
+    }
+
+    if options.is_empty() {
+        // `#[partial(PartialConfig)]` on a field was most likely meant for the struct.
+        if let Some(attr) = misplaced_partial_name(input) {
+            return Err(Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` should annotate the struct itself, not a field",
+                    attr.to_token_stream(),
+                ),
+            ));
+        }
+
+        return Err(Error::new_spanned(
+            utils::DeriveInputWithoutData(input),
+            "expected `#[partial(...)]`",
+        ));
+    }
+
+    Ok(options)
+}
+
+/// A trait that can be implemented for the partial struct using `impls(...)`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PartialImpl {
+    Clone,
+    Debug,
+    PartialEq,
+}
+
+impl PartialImpl {
+    /// Returns the name of the trait.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Clone => "Clone",
+            Self::Debug => "Debug",
+            Self::PartialEq => "PartialEq",
+        }
+    }
+}
+
+/// What `merge_in_place_reporting()` should record, as specified by `report` or `report(compare)`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReportMode {
+    /// Only record which fields were set in the partial struct.
+    Set,
+
+    /// Also compare each field before and after merging to record whether it changed.
+    Compare,
+}
+
+/// Traits that are inherited by `inherit_derives`, since they are implemented for `Option<T>`
+/// whenever they are implemented for `T`.
+const INHERITABLE_DERIVES: &[&str] = &[
+    "Clone",
+    "Copy",
+    "Debug",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+];
+
+/// Returns the traits derived by the base struct that should also be derived by the partial
+/// struct.
+///
+/// Only traits in [`INHERITABLE_DERIVES`] are inherited. Traits in `exclude`, or that are already
+/// implemented for the partial struct with `derive(...)` or `impls(...)`, are skipped.
+fn inherited_derives(
+    input: &DeriveInput,
+    options: &PartialOptions,
+    exclude: &[Ident],
+) -> Result<Vec<Path>> {
+    let mut inherited = Vec::new();
+
+    for attr in input.attrs.iter() {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+
+        let derives = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+
+        for derive in derives {
+            let Some(name) = derive.segments.last().map(|segment| &segment.ident) else {
+                continue;
+            };
+
+            let inheritable = INHERITABLE_DERIVES
+                .iter()
+                .any(|trait_name| name == trait_name);
+            let excluded = exclude.iter().any(|excluded| excluded == name);
+            let implemented = options
+                .impls
+                .iter()
+                .any(|partial_impl| name == partial_impl.name())
+                || find_derive(&options.meta, &name.to_string())?.is_some();
+
+            if inheritable && !excluded && !implemented {
+                inherited.push(derive);
+            }
+        }
+    }
+
+    Ok(inherited)
+}
+
+/// Returns the names of the generated partial structs, which the fields' `#[partial(...)]`
+/// attributes may target.
+///
+/// If the struct's `#[partial(...)]` attributes could not be parsed, `partials` is `None` and the
+/// names are read from them without validating their options, so that mistakes in the fields'
+/// attributes can still be reported alongside them.
+pub fn partial_names(input: &DeriveInput, partials: Option<&[PartialOptions]>) -> Vec<Ident> {
+    if let Some(partials) = partials {
+        return partials
+            .iter()
+            .filter_map(|partial| match &partial.target {
+                PartialTarget::Generated(name) => Some(name.clone()),
+                PartialTarget::Existing(_) => None,
+            })
+            .collect();
+    }
+
+    input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("partial"))
+        .filter_map(|attr| {
+            attr.parse_args_with(|input: ParseStream<'_>| {
+                let name: Ident = input.parse()?;
+                input.parse::<TokenStream>()?;
+                Ok(name)
+            })
+            .ok()
+        })
+        .filter(|name| {
+            name.to_string()
+                .starts_with(|c: char| c.is_ascii_uppercase())
+        })
+        .collect()
+}
+
+/// Returns the path to the trait named `name` if it is derived by a `derive(...)` within `meta`.
+fn find_derive(meta: &Punctuated<Meta, Token![,]>, name: &str) -> Result<Option<Path>> {
+    for meta in meta {
+        if let Meta::List(list) = meta
+            && list.path.is_ident("derive")
+        {
+            let derives = list.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+
+            let derive = derives.into_iter().find(|path| {
+                path.segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == name)
+            });
+
+            if derive.is_some() {
+                return Ok(derive);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// The contents of all `#[partial(...)]` and `#[strategy(...)]` attributes on a field.
+pub struct FieldOptions {
+    /// How the field should be merged, as specified by `#[strategy(...)]`.
+    pub strategy: MergeStrategy,
+
+    /// Whether the field's `Option<T>` type is used as-is in the partial struct, rather than being
+    /// wrapped in another `Option`, as specified by `#[partial(flatten_option)]`.
+    pub flatten_option: bool,
+
+    /// Whether the field is left out of every partial struct, as specified by `#[partial(skip)]`.
+    pub skip: bool,
+
+    /// Whether the field is read from environment variables with its own prefix, rather than from
+    /// a single variable, as specified by `#[partial(env_nested)]`.
+    pub env_nested: bool,
+
+    /// The name of the field within every partial struct, if it should differ from the base
+    /// field's, as specified by `#[partial(rename = "...")]`.
+    pub rename: Option<Ident>,
+
+    /// The type of the field within every partial struct, if it should differ from
+    /// `Option<BaseTy>`, as specified by `#[partial(type = ...)]`.
+    pub ty: Option<Type>,
+
+    /// The function that converts the partial struct's value into the base field's type, as
+    /// specified by `#[partial(with = "...")]`.
+    pub with: Option<Path>,
+
+    /// Options that apply to the field in every generated partial struct.
+    pub common: FieldPartialOptions,
+
+    /// Options that only apply to the field in a specific partial struct, as specified by
+    /// `#[partial(PartialName, ...)]`.
+    pub targeted: Vec<(Ident, FieldPartialOptions)>,
+}
+
+impl FieldOptions {
+    /// Returns the attributes and visibility override of this field within the partial struct
+    /// named `partial_name`.
+    ///
+    /// Attributes from both untargeted and targeted `#[partial(...)]` attributes are applied in the
+    /// order they were written, alongside the index of the `#[partial(...)]` attribute they came
+    /// from. A targeted visibility takes precedence over an untargeted one.
+    pub fn for_partial(&self, partial_name: &Ident) -> (Vec<(usize, &Meta)>, Option<&Visibility>) {
+        let targeted = self
+            .targeted
+            .iter()
+            .find(|(name, _)| name == partial_name)
+            .map(|(_, options)| options);
+
+        let mut meta: Vec<(usize, &Meta)> = self
+            .common
+            .meta
+            .iter()
+            .map(|(index, meta)| (*index, meta))
+            .collect();
+        let mut vis = self.common.vis.as_ref();
+
+        if let Some(targeted) = targeted {
+            meta.extend(targeted.meta.iter().map(|(index, meta)| (*index, meta)));
+            vis = targeted.vis.as_ref().or(vis);
+        }
+
+        // This is a stable sort, so attributes from the same `#[partial(...)]` stay in order.
+        meta.sort_by_key(|(index, _)| *index);
+
+        (meta, vis)
+    }
+}
+
+/// The options for a field within one or more partial structs.
+#[derive(Default)]
+pub struct FieldPartialOptions {
+    /// Attributes that should be applied to the partial struct's field, alongside the index of the
+    /// `#[partial(...)]` attribute they were specified in.
+    pub meta: Vec<(usize, Meta)>,
+
+    /// The visibility of the partial struct's field, if it should differ from the base field's.
+    pub vis: Option<Visibility>,
+}
+
+/// How a field should be merged, as specified by `#[strategy(...)]`.
+#[derive(Default, Clone)]
+pub enum MergeStrategy {
+    /// The base's field is replaced by the partial's, if it exists.
+    #[default]
+    Overwrite,
+
+    /// The partial's field is merged into the base's using its `Merge` implementation.
+    Merge,
+
+    /// The partial's field is merged into the base's by calling the given function, which may
+    /// fail, as specified by `#[strategy(try_with = "...")]`.
+    TryWith(Path),
+}
+
+impl MergeStrategy {
+    /// Returns true if the field is merged with its `Merge` implementation.
+    pub fn is_merge(&self) -> bool {
+        matches!(self, Self::Merge)
+    }
+
+    /// Returns true if merging the field may fail.
+    pub fn is_fallible(&self) -> bool {
+        matches!(self, Self::TryWith(_))
+    }
+}
+
+/// Parses the value of `#[partial(rename = "...")]` into an identifier.
+///
+/// Keywords like `"type"` are converted into raw identifiers like `r#type`, so that the partial
+/// field can use names that are only valid in formats like serde's.
+fn parse_rename(lit: &LitStr) -> Result<Ident> {
+    let value = lit.value();
+
+    let mut ident = syn::parse_str::<Ident>(&value)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{value}")))
+        .map_err(|_| {
+            Error::new_spanned(
+                lit,
+                format!("`rename` must be a valid identifier, but `{value}` is not"),
+            )
+        })?;
+
+    ident.set_span(lit.span());
+
+    Ok(ident)
+}
+
+/// Parses the `#[partial(...)]` and `#[strategy(...)]` attributes on a field.
+///
+/// `partial_names` are the names of the generated partial structs, which a `#[partial(...)]`
+/// attribute may start with to only apply to that partial struct.
+pub fn field_options(field: &Field, partial_names: &[Ident]) -> Result<FieldOptions> {
+    let mut options = FieldOptions {
+        strategy: MergeStrategy::default(),
+        flatten_option: false,
+        skip: false,
+        env_nested: false,
+        rename: None,
+        ty: None,
+        with: None,
+        common: FieldPartialOptions::default(),
+        targeted: Vec::new(),
+    };
+
+    // The first attribute that changes how the field is included in the partial struct, which
+    // conflicts with `skip`.
+    let mut partial_attr = None;
+    let mut strategy_attr = None;
+
+    // Each attribute is parsed independently, so a mistake in one does not hide mistakes in others.
+    let mut errors = utils::Errors::default();
+
+    for (attr_index, attr) in field.attrs.iter().enumerate() {
+        if attr.path().is_ident("partial") {
+            let result = attr.parse_args_with(|input: ParseStream<'_>| {
+                if input.is_empty() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "expected at least one attribute or option, e.g. `#[partial(serde(rename = \"...\"))]`",
+                    ));
+                }
+
+                let target = partial_names
+                    .iter()
+                    .find(|name| utils::peek_flag(input, &name.to_string()));
+
+                let partial_options = match target {
+                    Some(name) => {
+                        input.parse::<Ident>()?;
+
+                        if !input.is_empty() {
+                            input.parse::<Token![,]>()?;
+                        }
+
+                        if input.is_empty() {
+                            return Err(Error::new_spanned(
+                                attr,
+                                format!(
+                                    "expected attributes or options to apply to `{name}` after its name, e.g. `#[partial({name}, serde(skip))]`"
+                                ),
+                            ));
+                        }
+
+                        match options.targeted.iter().position(|(other, _)| other == name) {
+                            Some(i) => &mut options.targeted[i].1,
+                            None => {
+                                options
+                                    .targeted
+                                    .push((name.clone(), FieldPartialOptions::default()));
+                                &mut options.targeted.last_mut().unwrap().1
+                            }
+                        }
+                    }
+                    None => &mut options.common,
+                };
+
+                loop {
+                    if input.is_empty() {
+                        break;
+                    }
+
+                    if utils::peek_flag(input, "flatten_option") {
+                        let flag: Ident = input.parse()?;
+
+                        // This changes how the field is merged, which must be the same for every
+                        // partial struct.
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "`flatten_option` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.flatten_option {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "duplicate `flatten_option` option",
+                            ));
+                        }
+
+                        if utils::option_inner(&field.ty).is_none() {
+                            return Err(Error::new_spanned(
+                                &field.ty,
+                                "`flatten_option` can only be used on fields of type `Option<T>`",
+                            ));
+                        }
+
+                        options.flatten_option = true;
+                        partial_attr.get_or_insert(attr);
+                    } else if utils::peek_flag(input, "skip") {
+                        let flag: Ident = input.parse()?;
+
+                        // A skipped field cannot be merged, which must be the same for every
+                        // partial struct.
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "`skip` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.skip {
+                            return Err(Error::new_spanned(flag, "duplicate `skip` option"));
+                        }
+
+                        options.skip = true;
+                    } else if utils::peek_flag(input, "env_nested") {
+                        let flag: Ident = input.parse()?;
+
+                        // `FromEnv` is implemented the same way for every partial struct.
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                flag,
+                                "`env_nested` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.env_nested {
+                            return Err(Error::new_spanned(flag, "duplicate `env_nested` option"));
+                        }
+
+                        options.env_nested = true;
+                        partial_attr.get_or_insert(attr);
+                    } else if utils::peek_key(input, "rename") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        // Every generated item refers to the field by its name, so the name must
+                        // be the same in every partial struct.
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                key,
+                                "`rename` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.rename.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `rename` option"));
+                        }
+
+                        options.rename = Some(parse_rename(&input.parse()?)?);
+                        partial_attr.get_or_insert(attr);
+                    } else if input.peek(Token![type]) && input.peek2(Token![=]) {
+                        let key: Token![type] = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        // Merging converts the value into the base field, which must be the same
+                        // for every partial struct.
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                key,
+                                "`type` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.ty.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `type` option"));
+                        }
+
+                        let ty: Type = input.parse()?;
+
+                        if utils::option_inner(&ty).is_none() {
+                            return Err(Error::new_spanned(
+                                ty,
+                                "`type` must be an `Option<T>`, where `None` leaves the base field unchanged",
+                            ));
+                        }
+
+                        options.ty = Some(ty);
+                        partial_attr.get_or_insert(attr);
+                    } else if utils::peek_key(input, "with") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if target.is_some() {
+                            return Err(Error::new_spanned(
+                                key,
+                                "`with` applies to every partial struct, so it cannot target a specific one",
+                            ));
+                        }
+
+                        if options.with.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `with` option"));
+                        }
+
+                        options.with = Some(input.parse::<LitStr>()?.parse()?);
+                        partial_attr.get_or_insert(attr);
+                    } else if utils::peek_key(input, "vis") {
+                        let key: Ident = input.parse()?;
+                        input.parse::<Token![=]>()?;
+
+                        if partial_options.vis.is_some() {
+                            return Err(Error::new_spanned(key, "duplicate `vis` option"));
+                        }
+
+                        partial_options.vis = Some(utils::parse_vis(input)?);
+                        partial_attr.get_or_insert(attr);
+                    } else {
+                        let meta: Meta = input.parse()?;
+
+                        // Without any generated partial structs, the attribute is already reported
+                        // as misplaced or as unusable with `existing`.
+                        if target.is_none()
+                            && input.is_empty()
+                            && !partial_names.is_empty()
+                            && names_partial_struct(&meta)
+                        {
+                            return Err(Error::new_spanned(
+                                attr,
+                                format!(
+                                    "`{}` is not a partial struct generated by this derive, add `{}` to the struct itself to generate it",
+                                    meta.to_token_stream(),
+                                    attr.to_token_stream(),
+                                ),
+                            ));
+                        }
+
+                        // Some attribute macros reject being applied twice, and would report the
+                        // error within the generated code.
+                        if partial_options
+                            .meta
+                            .iter()
+                            .any(|(_, other)| same_meta(other, &meta))
+                        {
+                            return Err(Error::new_spanned(
+                                meta,
+                                "duplicate attribute in `#[partial(...)]`",
+                            ));
+                        }
+
+                        partial_options.meta.push((attr_index, meta));
+                        partial_attr.get_or_insert(attr);
+                    }
+
+                    if input.is_empty() {
+                        break;
+                    }
+
+                    input.parse::<Token![,]>()?;
+                }
+
+                Ok(())
+            });
+
+            errors.record(result);
+        }
+
+        if attr.path().is_ident("strategy") {
+            // A later strategy would silently replace the earlier one, so both are reported.
+            if let Some(first) = strategy_attr {
+                errors.push(Error::new_spanned(
+                    quote!(#first #attr),
+                    "duplicate `#[strategy(...)]` attribute, since a field can only be merged one way",
+                ));
+                continue;
+            }
+
+            strategy_attr = Some(attr);
+
+            let mut parsed = false;
+
+            let result = attr.parse_nested_meta(|meta| {
+                if std::mem::replace(&mut parsed, true) {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "`#[strategy(...)]` expects only one strategy, since a field can only be merged one way",
+                    ));
+                }
+
+                if meta.path.is_ident("overwrite") {
+                    options.strategy = MergeStrategy::Overwrite;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("merge") {
+                    options.strategy = MergeStrategy::Merge;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("try_with") {
+                    let path = meta.value()?.parse::<LitStr>()?.parse()?;
+                    options.strategy = MergeStrategy::TryWith(path);
+                    return Ok(());
+                }
+
+                Err(Error::new(
+                    meta.path.span(),
+                    "expected `#[strategy(overwrite)]`, `#[strategy(merge)]`, or `#[strategy(try_with = \"...\")]`",
+                ))
+            });
+
+            errors.record(result);
+        }
+    }
+
+    errors.finish()?;
+
+    // Untargeted attributes are also applied to every targeted partial struct's field.
+    for (_, targeted) in &options.targeted {
+        let duplicate = targeted.meta.iter().find(|(_, meta)| {
+            options
+                .common
+                .meta
+                .iter()
+                .any(|(_, other)| same_meta(other, meta))
+        });
+
+        if let Some((_, meta)) = duplicate {
+            return Err(Error::new_spanned(
+                meta,
+                "duplicate attribute in `#[partial(...)]`, since it is already applied to every partial struct",
+            ));
+        }
+    }
+
+    match (&options.ty, &options.with) {
+        (Some(ty), None) => {
+            return Err(Error::new_spanned(
+                ty,
+                "`type` requires a `with = \"...\"` function that converts the value into the base field's type",
+            ));
+        }
+        (None, Some(with)) => {
+            return Err(Error::new_spanned(
+                with,
+                "`with` can only be used alongside `type = ...`",
+            ));
+        }
+        (Some(ty), Some(_)) if options.flatten_option => {
+            return Err(Error::new_spanned(
+                ty,
+                "`type` cannot be used with `flatten_option`",
+            ));
+        }
+        // The partial struct's values cannot be merged with each other, since they are not the
+        // base field's type.
+        (Some(ty), Some(_)) if options.strategy.is_merge() => {
+            return Err(Error::new_spanned(
+                ty,
+                "`type` cannot be used with `#[strategy(merge)]`, since the partial struct's values are not the base field's type",
+            ));
+        }
+        (Some(ty), Some(_)) if options.strategy.is_fallible() => {
+            return Err(Error::new_spanned(
+                ty,
+                "`type` cannot be used with `#[strategy(try_with = ...)]`, since the partial struct's values are not the base field's type",
+            ));
+        }
+        _ => {}
+    }
+
+    if options.skip {
+        if let Some(attr) = strategy_attr {
+            return Err(Error::new_spanned(
+                attr,
+                "`#[strategy(...)]` cannot be used with `#[partial(skip)]`, since skipped fields are never merged",
+            ));
+        }
+
+        if let Some(attr) = partial_attr {
+            return Err(Error::new_spanned(
+                attr,
+                "`#[partial(skip)]` cannot be combined with other `#[partial(...)]` options, since the field is not in the partial struct",
+            ));
+        }
+    }
+
+    Ok(options)
+}
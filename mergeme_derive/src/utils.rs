@@ -1,6 +1,11 @@
-use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::{Data, DeriveInput};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Error, GenericArgument, Generics, LitStr, Meta, Path,
+    PathArguments, Result, Token, Type, TypeParamBound, TypePath, Visibility, WherePredicate,
+    parse::ParseStream, punctuated::Punctuated, token,
+};
 
 /// A wrapper around [`DeriveInput`] whose [`ToTokens`] implementation does not emit the [`Data`]
 /// tokens.
@@ -29,3 +34,281 @@ impl ToTokens for DeriveInputWithoutData<'_> {
         input.generics.to_tokens(tokens);
     }
 }
+
+/// Collects errors so that they can all be reported at once, rather than stopping at the first.
+#[derive(Default)]
+pub struct Errors(Option<Error>);
+
+impl Errors {
+    /// Adds `error` to the errors that will be reported.
+    pub fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Returns the value of `result`, or adds its error to the errors that will be reported.
+    pub fn record<T>(&mut self, result: Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    /// Returns all collected errors combined into one, if there were any.
+    pub fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns true if the next token in `input` is the identifier `flag`, followed by either a comma
+/// or the end of the stream.
+///
+/// This is used to distinguish bare options like `default` from attributes that should be passed
+/// through to the partial struct.
+pub fn peek_flag(input: ParseStream<'_>, flag: &str) -> bool {
+    let fork = input.fork();
+
+    match fork.parse::<Ident>() {
+        Ok(ident) => ident == flag && (fork.is_empty() || fork.peek(Token![,])),
+        Err(_) => false,
+    }
+}
+
+/// Returns true if the next tokens in `input` are the identifier `key` followed by `=`.
+///
+/// This is used to distinguish options like `vis = pub` from attributes that should be passed
+/// through to the partial struct.
+pub fn peek_key(input: ParseStream<'_>, key: &str) -> bool {
+    let fork = input.fork();
+
+    match fork.parse::<Ident>() {
+        Ok(ident) => ident == key && fork.peek(Token![=]),
+        Err(_) => false,
+    }
+}
+
+/// Joins boolean expressions with `&&`, returning `true` if there are none.
+pub fn all(exprs: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+    let mut exprs = exprs.into_iter();
+
+    match exprs.next() {
+        Some(first) => quote!(#first #(&& #exprs)*),
+        None => quote!(true),
+    }
+}
+
+/// Merges `value` into `base`, both of which are `ty`, with `Merge::merge_in_place()`.
+///
+/// The call goes through `__private::merge_field()` and is spanned to `ty`, so that a field with
+/// `#[strategy(merge)]` whose type does not implement `Merge` is reported once at the field, rather
+/// than at the derive. Every merge of the field produces the same error, which rustc deduplicates.
+pub fn merge_value(krate: &Path, ty: &Type, base: TokenStream, value: TokenStream) -> TokenStream {
+    quote_spanned! {ty.span()=>
+        #krate::__private::merge_field::<#ty>(#base, #value);
+    }
+}
+
+/// Returns the attributes of the base struct or one of its fields that should be copied to the
+/// partial struct, alongside their index within `attrs`.
+///
+/// `#[deprecated]` is always copied, while doc comments are only copied if `docs` is true.
+pub fn forwarded_attrs(
+    attrs: &[Attribute],
+    docs: bool,
+) -> impl Iterator<Item = (usize, &Attribute)> {
+    attrs.iter().enumerate().filter(move |(_, attr)| {
+        let path = attr.path();
+        path.is_ident("deprecated") || (docs && path.is_ident("doc"))
+    })
+}
+
+/// Combines copied attributes and attributes from `#[partial(...)]`, ordering them by the index of
+/// the attribute they were written in.
+pub fn ordered_attrs<'a>(
+    forwarded: impl IntoIterator<Item = (usize, &'a Attribute)>,
+    meta: impl IntoIterator<Item = (usize, &'a Meta)>,
+) -> Vec<TokenStream> {
+    let mut attrs: Vec<(usize, TokenStream)> = forwarded
+        .into_iter()
+        .map(|(index, attr)| (index, quote!(#attr)))
+        .chain(
+            meta.into_iter()
+                .map(|(index, meta)| (index, quote!(#[#meta]))),
+        )
+        .collect();
+
+    // This is a stable sort, so attributes from the same `#[partial(...)]` stay in order.
+    attrs.sort_by_key(|(index, _)| *index);
+
+    attrs.into_iter().map(|(_, attr)| attr).collect()
+}
+
+/// Returns `T` if `ty` is syntactically an `Option<T>`, such as `Option<T>` or
+/// `core::option::Option<T>`.
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let segment = path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// Returns the type that `ty` dereferences to if it is syntactically a `String` or `Vec<T>`, being
+/// `str` or `[T]`.
+///
+/// This is used to make getters return `Option<&str>` rather than `Option<&String>`. Type aliases
+/// and other smart pointers are not detected.
+pub fn deref_target(ty: &Type) -> Option<Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let segment = path.segments.last()?;
+
+    match &segment.arguments {
+        PathArguments::None if segment.ident == "String" => Some(syn::parse_quote!(str)),
+        PathArguments::AngleBracketed(arguments)
+            if segment.ident == "Vec" && arguments.args.len() == 1 =>
+        {
+            match arguments.args.first() {
+                Some(GenericArgument::Type(inner)) => Some(syn::parse_quote!([#inner])),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the next tokens in `input` are the identifier `key` followed by parenthesis.
+///
+/// This is used to distinguish options like `impls(Clone)` from attributes that should be passed
+/// through to the partial struct.
+pub fn peek_list(input: ParseStream<'_>, key: &str) -> bool {
+    let fork = input.fork();
+
+    match fork.parse::<Ident>() {
+        Ok(ident) => ident == key && fork.peek(token::Paren),
+        Err(_) => false,
+    }
+}
+
+/// Parses the value of a `vis = ...` option, which may either be a visibility like `pub(crate)` or
+/// a string containing one like `"pub(crate)"`.
+pub fn parse_vis(input: ParseStream<'_>) -> Result<Visibility> {
+    if input.peek(LitStr) {
+        input.parse::<LitStr>()?.parse()
+    } else {
+        input.parse()
+    }
+}
+
+/// Returns true if an item with the visibility `vis` is known to be at least as visible as one with
+/// the visibility `other`.
+///
+/// Visibilities are only compared by how they are written, so this may return false for some that
+/// are equally visible, such as `pub(crate)` and `pub(in crate)`.
+pub fn at_least_as_visible(vis: &Visibility, other: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+        || matches!(other, Visibility::Inherited)
+        || vis.to_token_stream().to_string() == other.to_token_stream().to_string()
+}
+
+/// Returns true if `meta` is a `serde(...)` attribute that already controls whether a field is
+/// serialized, such as `serde(skip)` or `serde(skip_serializing_if = "...")`.
+pub fn skips_serializing(meta: &Meta) -> bool {
+    let Meta::List(list) = meta else {
+        return false;
+    };
+
+    list.path.is_ident("serde") && list.tokens.clone().into_iter().any(|token| {
+        matches!(
+            token,
+            TokenTree::Ident(ident)
+                if ident == "skip" || ident == "skip_serializing" || ident == "skip_serializing_if"
+        )
+    })
+}
+
+/// Returns `generics` without any `Merge` bounds, such as `T: Merge<T>` or
+/// `where T: mergeme::Merge<U>`.
+///
+/// The partial struct only stores its fields' values, so it does not need these bounds. Bounds are
+/// detected by the last segment of the trait's path, so renamed imports are not removed.
+pub fn without_merge_bounds(generics: &Generics) -> Generics {
+    fn retain_bounds(bounds: &mut Punctuated<TypeParamBound, Token![+]>) {
+        *bounds = std::mem::take(bounds)
+            .into_iter()
+            .filter(|bound| {
+                !matches!(
+                    bound,
+                    TypeParamBound::Trait(bound)
+                        if bound.path.segments.last().is_some_and(|segment| segment.ident == "Merge")
+                )
+            })
+            .collect();
+    }
+
+    let mut generics = generics.clone();
+
+    for param in generics.type_params_mut() {
+        retain_bounds(&mut param.bounds);
+
+        if param.bounds.is_empty() {
+            param.colon_token = None;
+        }
+    }
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter_map(|mut predicate| {
+                if let WherePredicate::Type(predicate) = &mut predicate {
+                    retain_bounds(&mut predicate.bounds);
+
+                    // Removing every bound would leave `T:`, which is valid but meaningless.
+                    if predicate.bounds.is_empty() {
+                        return None;
+                    }
+                }
+
+                Some(predicate)
+            })
+            .collect();
+    }
+
+    generics
+}
+
+/// Returns true if `ty` mentions any of the type parameters in `generics`, such as `T` in
+/// `Vec<T>`.
+///
+/// This compares identifiers, so a path segment that shares a name with a type parameter, like the
+/// `T` in `other::T`, is also counted.
+pub fn uses_type_params(ty: &Type, generics: &Generics) -> bool {
+    fn contains(tokens: TokenStream, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => params.contains(&&ident),
+            TokenTree::Group(group) => contains(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    let params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+
+    !params.is_empty() && contains(ty.to_token_stream(), &params)
+}
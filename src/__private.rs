@@ -0,0 +1,25 @@
+//! Items used by code generated by [`Merge`](derive@crate::Merge), which are not part of the public
+//! API.
+
+use crate::Merge;
+
+/// A type that can be merged with itself, used to check `#[strategy(merge)]` fields.
+///
+/// Unlike a `Merge<T>` bound, this is reported as a single error that names the field's type,
+/// rather than an error for every unmet bound of the implementation for [`Extend`], and without
+/// listing every other implementation of [`Merge`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be merged with `{Self}`",
+    label = "`{Self}` does not implement `Merge<{Self}>`",
+    note = "implement `Merge<{Self}>` for `{Self}`, or use `#[strategy(overwrite)]` instead of `#[strategy(merge)]`"
+)]
+pub trait MergeField: Merge<Self> {}
+
+#[diagnostic::do_not_recommend]
+impl<T: Merge<T>> MergeField for T {}
+
+/// Merges the value of a `#[strategy(merge)]` field into `base`.
+#[inline]
+pub fn merge_field<T: MergeField>(base: &mut T, value: T) {
+    base.merge_in_place(value);
+}
@@ -0,0 +1,197 @@
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+/// A trait for partial structs that can be read from environment variables.
+///
+/// [`Merge`](derive@crate::Merge) implements this for the partial struct if
+/// `#[partial(Name, from_env)]` is specified, where each field is read from the variable
+/// `<PREFIX>_<FIELD>`, such as `APP_PORT` for the field `port` with the prefix `APP`. Fields whose
+/// variable is not set are `None`. Please see [its documentation](derive@crate::Merge) for further
+/// details.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// # use mergeme::{FromEnv, Merge};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, from_env)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// // SAFETY: This example runs in its own process, with no other threads.
+/// unsafe { std::env::set_var("FROM_ENV_DOC_PORT", "8080") };
+///
+/// let partial = PartialConfig::from_env("FROM_ENV_DOC").unwrap();
+///
+/// assert_eq!(partial.name, None);
+/// assert_eq!(partial.port, Some(8080));
+/// ```
+pub trait FromEnv: Sized {
+    /// Reads `Self` from the environment variables that start with `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is set, but cannot be parsed.
+    fn from_env(prefix: &str) -> Result<Self, EnvError>;
+}
+
+/// An error returned when an environment variable is set, but cannot be parsed.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// # use mergeme::env_var;
+/// #
+/// // SAFETY: This example runs in its own process, with no other threads.
+/// unsafe { std::env::set_var("ENV_ERROR_DOC_PORT", "eighty") };
+///
+/// let error = env_var::<u16>("ENV_ERROR_DOC", "PORT").unwrap_err();
+///
+/// assert_eq!(error.var(), "ENV_ERROR_DOC_PORT");
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid value for environment variable `ENV_ERROR_DOC_PORT`: invalid digit found in string",
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EnvError {
+    var: String,
+    message: String,
+}
+
+impl EnvError {
+    /// Returns the name of the variable that could not be parsed.
+    pub fn var(&self) -> &str {
+        &self.var
+    }
+
+    /// Returns why the variable could not be parsed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for EnvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value for environment variable `{}`: {}",
+            self.var, self.message
+        )
+    }
+}
+
+impl core::error::Error for EnvError {}
+
+/// Reads and parses the environment variable `<PREFIX>_<NAME>` with [`FromStr`], returning `None`
+/// if it is not set.
+///
+/// If `prefix` is empty, the variable is just `NAME`. This is used by the [`FromEnv`]
+/// implementations generated by [`Merge`](derive@crate::Merge), for implementing [`FromEnv`] by
+/// hand.
+///
+/// # Errors
+///
+/// Returns an error if the variable is set, but is not valid unicode or cannot be parsed.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// # use mergeme::env_var;
+/// #
+/// // SAFETY: This example runs in its own process, with no other threads.
+/// unsafe { std::env::set_var("ENV_VAR_DOC_PORT", "8080") };
+///
+/// assert_eq!(env_var::<u16>("ENV_VAR_DOC", "PORT"), Ok(Some(8080)));
+/// assert_eq!(env_var::<u16>("ENV_VAR_DOC", "TIMEOUT"), Ok(None));
+/// ```
+pub fn env_var<T>(prefix: &str, name: &str) -> Result<Option<T>, EnvError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let var = env_key(prefix, name);
+
+    let Some(value) = std::env::var_os(&var) else {
+        return Ok(None);
+    };
+
+    let Some(value) = value.to_str() else {
+        return Err(EnvError {
+            var,
+            message: "value is not valid unicode".to_string(),
+        });
+    };
+
+    match value.parse() {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => Err(EnvError {
+            message: error.to_string(),
+            var,
+        }),
+    }
+}
+
+/// Reads `T` with [`FromEnv`] from the environment variables that start with `<PREFIX>_<NAME>__`,
+/// returning `None` if none of them are set.
+///
+/// If `prefix` is empty, the variables start with `NAME__`. This is used for fields with
+/// `#[partial(env_nested)]` by the [`FromEnv`] implementations generated by
+/// [`Merge`](derive@crate::Merge), for implementing [`FromEnv`] by hand.
+///
+/// # Errors
+///
+/// Returns an error if `T::from_env()` does.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// # use mergeme::{FromEnv, Merge, env_nested};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialServer, from_env)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// // SAFETY: This example runs in its own process, with no other threads.
+/// unsafe { std::env::set_var("ENV_NESTED_DOC_SERVER__PORT", "8080") };
+///
+/// let server = env_nested::<PartialServer>("ENV_NESTED_DOC", "SERVER").unwrap();
+///
+/// assert_eq!(server.unwrap().port, Some(8080));
+/// assert!(env_nested::<PartialServer>("ENV_NESTED_DOC", "PROXY").unwrap().is_none());
+/// ```
+pub fn env_nested<T: FromEnv>(prefix: &str, name: &str) -> Result<Option<T>, EnvError> {
+    let key = env_key(prefix, name);
+
+    // The nested type's variables are named after `<KEY>_`, which adds a second underscore.
+    let vars = format!("{key}__");
+    let set =
+        std::env::vars_os().any(|(var, _)| var.to_str().is_some_and(|var| var.starts_with(&vars)));
+
+    if set {
+        T::from_env(&format!("{key}_")).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the name of the variable `name` with `prefix`, separated by an underscore.
+fn env_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}_{name}")
+    }
+}
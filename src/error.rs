@@ -0,0 +1,147 @@
+use core::fmt::{self, Display, Formatter};
+
+/// An error returned when a partial struct is converted into its base struct, but some of its
+/// fields are `None`.
+///
+/// This type stores the names of all fields, alongside which of them are missing, so that it can
+/// report every missing field at once. Partial structs with up to 128 fields are tracked with a
+/// bitmask and never allocate. Larger ones require the `alloc` feature, which is enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, MissingFields};
+/// #
+/// #[derive(Merge, Debug)]
+/// #[partial(PartialConfig)]
+/// struct Config {
+///     name: String,
+///     version: u32,
+///     edition: u16,
+/// }
+///
+/// let partial = PartialConfig {
+///     name: Some("mergeme".to_string()),
+///     version: None,
+///     edition: None,
+/// };
+///
+/// let error: MissingFields = Config::try_from(partial).unwrap_err();
+///
+/// assert_eq!(error.iter().collect::<Vec<_>>(), ["version", "edition"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MissingFields {
+    fields: &'static [&'static str],
+    missing: Missing,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Missing {
+    Mask(u128),
+    #[cfg(feature = "alloc")]
+    Flags(alloc::boxed::Box<[bool]>),
+}
+
+impl MissingFields {
+    /// Creates a new [`MissingFields`] from the names of all fields and whether each of them is
+    /// missing.
+    ///
+    /// This is usually called by code generated by [`Merge`](derive@crate::Merge). Passing more
+    /// than 128 fields allocates, and fails to compile without the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::MissingFields;
+    /// #
+    /// let error = MissingFields::new(&["name", "version"], [false, true]);
+    ///
+    /// assert!(error.contains("version"));
+    /// assert!(!error.contains("name"));
+    /// ```
+    pub fn new<const N: usize>(fields: &'static [&'static str; N], missing: [bool; N]) -> Self {
+        #[cfg(feature = "alloc")]
+        if N > 128 {
+            return Self {
+                fields,
+                missing: Missing::Flags(missing.into()),
+            };
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        const {
+            assert!(
+                N <= 128,
+                "`MissingFields` supports at most 128 fields without the `alloc` feature",
+            )
+        };
+
+        let mask = missing
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (i, &missing)| mask | (u128::from(missing) << i));
+
+        Self {
+            fields,
+            missing: Missing::Mask(mask),
+        }
+    }
+
+    /// Returns true if the field at the given index is missing.
+    fn is_missing(&self, index: usize) -> bool {
+        match &self.missing {
+            Missing::Mask(mask) => mask & (1 << index) != 0,
+            #[cfg(feature = "alloc")]
+            Missing::Flags(flags) => flags[index],
+        }
+    }
+
+    /// Returns an iterator over the names of the missing fields, in the order they were declared.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &field)| self.is_missing(i).then_some(field))
+    }
+
+    /// Returns the number of missing fields.
+    pub fn len(&self) -> usize {
+        match &self.missing {
+            Missing::Mask(mask) => mask.count_ones() as usize,
+            #[cfg(feature = "alloc")]
+            Missing::Flags(flags) => flags.iter().filter(|&&missing| missing).count(),
+        }
+    }
+
+    /// Returns true if no fields are missing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the field with the given name is missing.
+    pub fn contains(&self, field: &str) -> bool {
+        self.iter().any(|missing| missing == field)
+    }
+}
+
+impl Display for MissingFields {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.len() {
+            1 => f.write_str("missing field ")?,
+            _ => f.write_str("missing fields ")?,
+        }
+
+        for (i, field) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "`{field}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for MissingFields {}
@@ -0,0 +1,111 @@
+use figment::{
+    Error, Metadata, Profile, Provider,
+    error::Kind,
+    value::{Dict, Empty, Map, Value},
+};
+use serde::Serialize;
+
+/// A [`figment::Provider`] that provides the fields of a partial struct that are set.
+///
+/// figment serializes `None` as an empty value that replaces the value from a source with lower
+/// precedence, so providing a partial struct with [`figment::providers::Serialized`] would unset
+/// every field that the partial struct leaves `None`. [`PartialProvider`] leaves these keys out
+/// instead, including within nested maps, so that figment's own precedence rules decide the
+/// result of merging partial structs with other sources.
+///
+/// The partial struct must serialize as a map, such as a struct deriving `Serialize`. Its data is
+/// provided for [`Profile::Default`], unless a different profile is chosen with
+/// [`PartialProvider::profile()`].
+///
+/// # Examples
+///
+/// ```
+/// # use figment::Figment;
+/// # use mergeme::{Merge, PartialProvider};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Merge, Deserialize, Debug)]
+/// #[partial(PartialConfig, default, derive(Serialize))]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// let defaults = PartialConfig {
+///     name: Some("server".to_string()),
+///     port: Some(80),
+/// };
+///
+/// let overrides = PartialConfig {
+///     port: Some(8080),
+///     ..Default::default()
+/// };
+///
+/// let config: Config = Figment::new()
+///     .merge(PartialProvider::new(defaults))
+///     .merge(PartialProvider::new(overrides))
+///     .extract()
+///     .unwrap();
+///
+/// assert_eq!(config.name, "server");
+/// assert_eq!(config.port, 8080);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PartialProvider<T> {
+    partial: T,
+    profile: Profile,
+}
+
+impl<T> PartialProvider<T> {
+    /// Creates a new [`PartialProvider`] that provides `partial` for [`Profile::Default`].
+    pub fn new(partial: T) -> Self {
+        Self {
+            partial,
+            profile: Profile::Default,
+        }
+    }
+
+    /// Changes the profile that the partial struct is provided for.
+    pub fn profile(mut self, profile: impl Into<Profile>) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Returns the partial struct.
+    pub fn into_inner(self) -> T {
+        self.partial
+    }
+}
+
+impl<T: Serialize> Provider for PartialProvider<T> {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(core::any::type_name::<T>())
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let value = Value::serialize(&self.partial)?;
+        let error = Kind::InvalidType(value.to_actual(), "map".into());
+        let dict = without_none(value).into_dict().ok_or(error)?;
+
+        Ok(self.profile.clone().collect(dict))
+    }
+}
+
+/// Removes the keys of maps whose values are `None`, recursively.
+///
+/// Items of arrays are kept, since removing them would shift the items after them.
+fn without_none(value: Value) -> Value {
+    match value {
+        Value::Dict(tag, dict) => Value::Dict(
+            tag,
+            dict.into_iter()
+                .filter(|(_, value)| !matches!(value, Value::Empty(_, Empty::None)))
+                .map(|(key, value)| (key, without_none(value)))
+                .collect(),
+        ),
+        Value::Array(tag, array) => {
+            Value::Array(tag, array.into_iter().map(without_none).collect())
+        }
+        value => value,
+    }
+}
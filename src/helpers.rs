@@ -0,0 +1,78 @@
+use crate::Merge;
+
+/// Overwrites `base` with the value of `partial` if it is `Some`, and leaves it unchanged
+/// otherwise.
+///
+/// This is the overwrite strategy used by [`Merge`](derive@crate::Merge), for implementing
+/// [`Merge`] by hand.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::overwrite_with;
+/// #
+/// let mut name = "Janette".to_string();
+///
+/// overwrite_with(&mut name, None);
+/// assert_eq!(name, "Janette");
+///
+/// overwrite_with(&mut name, Some("Lou".to_string()));
+/// assert_eq!(name, "Lou");
+/// ```
+pub fn overwrite_with<T>(base: &mut T, partial: Option<T>) {
+    if let Some(value) = partial {
+        *base = value;
+    }
+}
+
+/// Returns the value of `partial` if it is `Some`, and `base` otherwise.
+///
+/// This is a `const` version of [`overwrite_with()`] for [`Copy`] values, such as for building a
+/// configuration's defaults in a `const` or `static`.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::const_overwrite;
+/// #
+/// const DEFAULT_PORT: u16 = 8080;
+/// const PORT: u16 = const_overwrite(DEFAULT_PORT, Some(3000));
+/// const TIMEOUT: u32 = const_overwrite(30, None);
+///
+/// assert_eq!(PORT, 3000);
+/// assert_eq!(TIMEOUT, 30);
+/// ```
+pub const fn const_overwrite<T: Copy>(base: T, partial: Option<T>) -> T {
+    match partial {
+        Some(value) => value,
+        None => base,
+    }
+}
+
+/// Merges the value of `partial` into `base` if it is `Some`, and leaves it unchanged otherwise.
+///
+/// This is the merge strategy used by [`Merge`](derive@crate::Merge) for
+/// `#[strategy(merge)]` fields, for implementing [`Merge`] by hand.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+/// # use mergeme::merge_with;
+/// #
+/// let mut friends = vec!["Lou".to_string()];
+///
+/// merge_with(&mut friends, None::<Vec<String>>);
+/// assert_eq!(friends, ["Lou"]);
+///
+/// merge_with(&mut friends, Some(vec!["Kylie".to_string()]));
+/// assert_eq!(friends, ["Lou", "Kylie"]);
+/// ```
+pub fn merge_with<T, P>(base: &mut T, partial: Option<P>)
+where
+    T: Merge<P>,
+{
+    if let Some(value) = partial {
+        base.merge_in_place(value);
+    }
+}
@@ -0,0 +1,389 @@
+use core::fmt::{self, Display, Formatter};
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Applies `patch` to `target` as a JSON Merge Patch, as described by
+/// [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396).
+///
+/// If `patch` is an object, each of its keys is merged into `target` recursively, where a key set
+/// to `null` removes that key from `target`. If `target` is not an object, it is replaced with an
+/// empty object first. If `patch` is not an object, such as an array or a string, it replaces
+/// `target` entirely.
+///
+/// Since `null` removes keys, a merge patch cannot set a key's value to `null`, nor merge the
+/// items of arrays. Please see [`Merge`](crate::Merge) for merging typed values instead.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::json_merge_patch;
+/// # use serde_json::json;
+/// #
+/// let mut config = json!({
+///     "name": "mergeme",
+///     "server": {
+///         "host": "localhost",
+///         "port": 8080,
+///     },
+///     "plugins": ["auth"],
+/// });
+///
+/// json_merge_patch(
+///     &mut config,
+///     json!({
+///         "server": {
+///             "port": 3000,
+///             "host": null,
+///         },
+///         "plugins": ["metrics"],
+///     }),
+/// );
+///
+/// assert_eq!(
+///     config,
+///     json!({
+///         "name": "mergeme",
+///         "server": {
+///             "port": 3000,
+///         },
+///         "plugins": ["metrics"],
+///     }),
+/// );
+/// ```
+pub fn json_merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(&key);
+            } else {
+                json_merge_patch(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// An operation of a JSON Patch, as described by
+/// [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902).
+///
+/// Paths are JSON Pointers, as described by [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901),
+/// such as `/server/port`. This is (de)serialized as an object whose `op` key is the name of the
+/// operation, so a JSON Patch document may be deserialized as a `Vec<PatchOp>`.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::PatchOp;
+/// # use serde_json::json;
+/// #
+/// let ops: Vec<PatchOp> = serde_json::from_value(json!([
+///     { "op": "replace", "path": "/port", "value": 3000 },
+///     { "op": "remove", "path": "/host" },
+/// ]))
+/// .unwrap();
+///
+/// assert_eq!(
+///     ops[0],
+///     PatchOp::Replace {
+///         path: "/port".to_string(),
+///         value: json!(3000),
+///     },
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Adds `value` at `path`, replacing the value of an existing key or inserting into an array.
+    Add { path: String, value: Value },
+    /// Removes the value at `path`, which must exist.
+    Remove { path: String },
+    /// Replaces the value at `path`, which must exist, with `value`.
+    Replace { path: String, value: Value },
+    /// Removes the value at `from` and adds it at `path`.
+    Move { from: String, path: String },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy { from: String, path: String },
+    /// Checks that the value at `path` is equal to `value`.
+    Test { path: String, value: Value },
+}
+
+/// An error returned by [`apply_json_patch()`] when an operation cannot be applied.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{PatchErrorKind, PatchOp, apply_json_patch};
+/// # use serde_json::json;
+/// #
+/// let mut doc = json!({ "port": 8080 });
+///
+/// let error = apply_json_patch(
+///     &mut doc,
+///     &[PatchOp::Remove {
+///         path: "/host".to_string(),
+///     }],
+/// )
+/// .unwrap_err();
+///
+/// assert_eq!(error.index(), 0);
+/// assert_eq!(error.kind(), PatchErrorKind::NotFound);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PatchError {
+    index: usize,
+    kind: PatchErrorKind,
+}
+
+impl PatchError {
+    /// Returns the index of the operation that failed.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns why the operation failed.
+    pub fn kind(&self) -> PatchErrorKind {
+        self.kind
+    }
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "JSON Patch operation {} failed: {}",
+            self.index, self.kind
+        )
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+/// The reason that a [`PatchError`] was returned.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum PatchErrorKind {
+    /// A path is not a valid JSON Pointer, such as one that does not start with `/`.
+    InvalidPointer,
+    /// A path refers to a value that does not exist.
+    NotFound,
+    /// A path refers to an array with an index that is not a number, or is out of bounds.
+    InvalidIndex,
+    /// A `move` operation's `path` is inside of its `from`, so the value would be moved into
+    /// itself.
+    MoveIntoChild,
+    /// A `test` operation's value is not equal to the value at its path.
+    TestFailed,
+}
+
+impl Display for PatchErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidPointer => "path is not a valid JSON Pointer",
+            Self::NotFound => "path does not exist",
+            Self::InvalidIndex => "array index is invalid or out of bounds",
+            Self::MoveIntoChild => "cannot move a value into itself",
+            Self::TestFailed => "value is not equal to the tested value",
+        })
+    }
+}
+
+/// Applies the operations of a JSON Patch to `doc`, as described by
+/// [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902).
+///
+/// The operations are applied in order. If any of them fails, including a `test` operation whose
+/// value is not equal, `doc` is left unchanged and an error for the first failed operation is
+/// returned. Since the operations are applied to a copy of `doc` first, this clones `doc` once.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{PatchOp, apply_json_patch};
+/// # use serde_json::json;
+/// #
+/// let mut config = json!({
+///     "server": { "host": "localhost", "port": 8080 },
+///     "plugins": ["auth"],
+/// });
+///
+/// let ops: Vec<PatchOp> = serde_json::from_value(json!([
+///     { "op": "test", "path": "/server/port", "value": 8080 },
+///     { "op": "replace", "path": "/server/port", "value": 3000 },
+///     { "op": "add", "path": "/plugins/-", "value": "metrics" },
+///     { "op": "move", "from": "/server/host", "path": "/host" },
+/// ]))
+/// .unwrap();
+///
+/// apply_json_patch(&mut config, &ops).unwrap();
+///
+/// assert_eq!(
+///     config,
+///     json!({
+///         "server": { "port": 3000 },
+///         "plugins": ["auth", "metrics"],
+///         "host": "localhost",
+///     }),
+/// );
+/// ```
+pub fn apply_json_patch(doc: &mut Value, ops: &[PatchOp]) -> Result<(), PatchError> {
+    let mut patched = doc.clone();
+
+    for (index, op) in ops.iter().enumerate() {
+        apply_op(&mut patched, op).map_err(|kind| PatchError { index, kind })?;
+    }
+
+    *doc = patched;
+
+    Ok(())
+}
+
+fn apply_op(doc: &mut Value, op: &PatchOp) -> Result<(), PatchErrorKind> {
+    match op {
+        PatchOp::Add { path, value } => add(doc, path, value.clone()),
+        PatchOp::Remove { path } => remove(doc, path).map(drop),
+        PatchOp::Replace { path, value } => {
+            *pointer_mut(doc, &parse_pointer(path)?)? = value.clone();
+            Ok(())
+        }
+        PatchOp::Move { from, path } => {
+            if from == path {
+                // Still check that the value exists.
+                return pointer_mut(doc, &parse_pointer(from)?).map(drop);
+            }
+
+            if path
+                .strip_prefix(from.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+            {
+                return Err(PatchErrorKind::MoveIntoChild);
+            }
+
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        PatchOp::Copy { from, path } => {
+            let value = pointer_mut(doc, &parse_pointer(from)?)?.clone();
+            add(doc, path, value)
+        }
+        PatchOp::Test { path, value } => {
+            if pointer_mut(doc, &parse_pointer(path)?)? == value {
+                Ok(())
+            } else {
+                Err(PatchErrorKind::TestFailed)
+            }
+        }
+    }
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), PatchErrorKind> {
+    let mut tokens = parse_pointer(path)?;
+
+    let Some(last) = tokens.pop() else {
+        *doc = value;
+        return Ok(());
+    };
+
+    match pointer_mut(doc, &tokens)? {
+        Value::Object(object) => {
+            object.insert(last, value);
+        }
+        Value::Array(array) if last == "-" => array.push(value),
+        Value::Array(array) => {
+            let index = parse_index(&last).filter(|&index| index <= array.len());
+            array.insert(index.ok_or(PatchErrorKind::InvalidIndex)?, value);
+        }
+        _ => return Err(PatchErrorKind::NotFound),
+    }
+
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, PatchErrorKind> {
+    let mut tokens = parse_pointer(path)?;
+
+    let Some(last) = tokens.pop() else {
+        return Ok(core::mem::take(doc));
+    };
+
+    match pointer_mut(doc, &tokens)? {
+        Value::Object(object) => object.remove(&last).ok_or(PatchErrorKind::NotFound),
+        Value::Array(array) => {
+            let index = parse_index(&last).filter(|&index| index < array.len());
+            Ok(array.remove(index.ok_or(PatchErrorKind::InvalidIndex)?))
+        }
+        _ => Err(PatchErrorKind::NotFound),
+    }
+}
+
+/// Returns the value that `tokens` refers to.
+fn pointer_mut<'a>(
+    mut doc: &'a mut Value,
+    tokens: &[String],
+) -> Result<&'a mut Value, PatchErrorKind> {
+    for token in tokens {
+        doc = match doc {
+            Value::Object(object) => object.get_mut(token).ok_or(PatchErrorKind::NotFound)?,
+            Value::Array(array) => {
+                let index = parse_index(token).ok_or(PatchErrorKind::InvalidIndex)?;
+                array.get_mut(index).ok_or(PatchErrorKind::InvalidIndex)?
+            }
+            _ => return Err(PatchErrorKind::NotFound),
+        };
+    }
+
+    Ok(doc)
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchErrorKind> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let Some(pointer) = pointer.strip_prefix('/') else {
+        return Err(PatchErrorKind::InvalidPointer);
+    };
+
+    pointer
+        .split('/')
+        .map(|token| {
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '~' => match chars.next() {
+                        Some('0') => unescaped.push('~'),
+                        Some('1') => unescaped.push('/'),
+                        _ => return Err(PatchErrorKind::InvalidPointer),
+                    },
+                    c => unescaped.push(c),
+                }
+            }
+
+            Ok(unescaped)
+        })
+        .collect()
+}
+
+/// Parses an array index, which may not have leading zeros.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|b| b.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        return None;
+    }
+
+    token.parse().ok()
+}
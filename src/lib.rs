@@ -1,6 +1,60 @@
-#![doc = include_str!("../README.md")]
+// The README's example merges a `Vec`, which requires the `collections` feature.
+#![cfg_attr(feature = "collections", doc = include_str!("../README.md"))]
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "env")]
+mod env;
+mod error;
+#[cfg(feature = "figment")]
+mod figment;
+mod helpers;
+#[cfg(feature = "json")]
+mod json;
+mod macros;
+mod overridable;
+mod report;
+#[cfg(feature = "alloc")]
+mod stack;
+
+pub mod strategy;
+
+#[doc(hidden)]
+pub mod __private;
+
+use core::convert::Infallible;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    string::String,
+    vec::Vec,
+};
+
+pub use self::{
+    error::MissingFields,
+    helpers::{const_overwrite, merge_with, overwrite_with},
+    overridable::Overridable,
+    report::MergeReport,
+};
+
+#[cfg(feature = "alloc")]
+pub use self::stack::MergeStack;
+
+#[cfg(feature = "env")]
+pub use self::env::{EnvError, FromEnv, env_nested, env_var};
+
+#[cfg(feature = "figment")]
+pub use self::figment::PartialProvider;
+
+#[cfg(feature = "json")]
+pub use self::json::{PatchError, PatchErrorKind, PatchOp, apply_json_patch, json_merge_patch};
+
 #[cfg(feature = "derive")]
 pub use mergeme_derive::Merge;
 
@@ -14,7 +68,8 @@ pub use mergeme_derive::Merge;
 /// This is an example using the [`Merge`](derive@Merge) derive macro. Please see
 /// [its documentation](derive@Merge) for further details.
 ///
-/// ```
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
 /// # use mergeme::Merge;
 /// #
 /// #[derive(Merge)]
@@ -50,7 +105,8 @@ pub use mergeme_derive::Merge;
 ///
 /// This is an example implementing [`Merge`] from scratch. It is equivalent to the prior example.
 ///
-/// ```
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
 /// # use mergeme::Merge;
 /// #
 /// struct Person {
@@ -104,6 +160,168 @@ pub use mergeme_derive::Merge;
 /// assert_eq!(merged.age, 25);
 /// assert_eq!(merged.friends, ["Lou", "Kylie"]);
 /// ```
+///
+/// [`overwrite_with()`] and [`merge_with()`] shorten each field to a single line:
+///
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+/// # use mergeme::{Merge, merge_with, overwrite_with};
+/// #
+/// # struct Person {
+/// #     name: String,
+/// #     age: u16,
+/// #     friends: Vec<String>,
+/// # }
+/// #
+/// # struct PartialPerson {
+/// #     name: Option<String>,
+/// #     age: Option<u16>,
+/// #     friends: Option<Vec<String>>,
+/// # }
+/// #
+/// impl Merge<PartialPerson> for Person {
+///     fn merge_in_place(&mut self, other: PartialPerson) {
+///         overwrite_with(&mut self.name, other.name);
+///         overwrite_with(&mut self.age, other.age);
+///         merge_with(&mut self.friends, other.friends);
+///     }
+/// }
+/// #
+/// # let person = Person {
+/// #     name: "Janette".to_string(),
+/// #     age: 19,
+/// #     friends: vec!["Lou".to_string()],
+/// # };
+/// #
+/// # let partial = PartialPerson {
+/// #     name: None,
+/// #     age: Some(25),
+/// #     friends: Some(vec!["Kylie".to_string()]),
+/// # };
+/// #
+/// # let merged = person.merge(partial);
+/// #
+/// # assert_eq!(merged.name, "Janette");
+/// # assert_eq!(merged.age, 25);
+/// # assert_eq!(merged.friends, ["Lou", "Kylie"]);
+/// ```
+///
+/// # Indirection
+///
+/// [`Merge`] is not implemented for `Box<T>` or `&mut T`, since it would conflict with the
+/// implementation for types that implement [`Extend`]. Method calls dereference them
+/// automatically, so values behind a `Box` or a mutable reference can still be merged directly.
+/// Generic functions should accept `&mut T` where `T: Merge<Partial>`, which a boxed value can be
+/// passed to with `&mut *boxed`.
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// fn use_defaults<T: Merge<PartialConfig>>(config: &mut T) {
+///     config.merge_in_place(PartialConfig { port: Some(80) });
+/// }
+///
+/// let mut config = Box::new(Config { port: 8080 });
+///
+/// config.merge_in_place(PartialConfig { port: Some(3000) });
+/// assert_eq!(config.port, 3000);
+///
+/// use_defaults(&mut *config);
+/// assert_eq!(config.port, 80);
+/// ```
+///
+/// For the same reason, [`Merge`] is not implemented for `Rc<T>` or `Arc<T>`, which only allow
+/// shared access to their value. `Rc::make_mut()` and `Arc::make_mut()` can be used to merge into
+/// them instead. If other handles to the value exist, these clone it first and merge into the
+/// clone, so the other handles keep the old value while the merged handle sees the new one.
+///
+/// ```
+/// # use mergeme::Merge;
+/// # use std::sync::Arc;
+/// #
+/// #[derive(Merge, Clone)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let mut config = Arc::new(Config { port: 8080 });
+/// let shared = Arc::clone(&config);
+///
+/// Arc::make_mut(&mut config).merge_in_place(PartialConfig { port: Some(3000) });
+///
+/// assert_eq!(config.port, 3000);
+/// assert_eq!(shared.port, 8080);
+/// ```
+///
+/// Cells like [`Cell`](core::cell::Cell), [`RefCell`](core::cell::RefCell), and
+/// [`OnceCell`](core::cell::OnceCell) do not implement [`Merge`] either. Given mutable access to
+/// the cell, their `get_mut()` methods return a mutable reference to the value without any runtime
+/// checks, so merging through them never panics. An unset [`OnceCell`](core::cell::OnceCell) has
+/// no value to merge into, so it must be initialized first.
+///
+/// ```
+/// # use mergeme::Merge;
+/// # use std::cell::{OnceCell, RefCell};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let mut config = RefCell::new(Config { port: 8080 });
+///
+/// config.get_mut().merge_in_place(PartialConfig { port: Some(3000) });
+/// assert_eq!(config.borrow().port, 3000);
+///
+/// let mut lazy: OnceCell<Config> = OnceCell::new();
+///
+/// match lazy.get_mut() {
+///     Some(config) => config.merge_in_place(PartialConfig { port: Some(80) }),
+///     None => {
+///         let _ = lazy.set(Config { port: 80 });
+///     }
+/// }
+///
+/// assert_eq!(lazy.get().map(|config| config.port), Some(80));
+/// ```
+///
+/// Likewise, `Mutex::get_mut()` and `RwLock::get_mut()` return a mutable reference to a lock's
+/// value without locking it. They fail if the lock is poisoned, meaning a thread panicked while
+/// holding it. Merging only needs the value, so the poison can usually be ignored with
+/// `PoisonError::into_inner()`.
+///
+/// ```
+/// # use mergeme::Merge;
+/// # use std::sync::{PoisonError, RwLock};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let mut config = RwLock::new(Config { port: 8080 });
+///
+/// config
+///     .get_mut()
+///     .unwrap_or_else(PoisonError::into_inner)
+///     .merge_in_place(PartialConfig { port: Some(3000) });
+///
+/// assert_eq!(config.read().unwrap().port, 3000);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be merged with `{Partial}`",
+    label = "`{Self}` does not implement `Merge<{Partial}>`",
+    note = "implement `Merge<{Partial}>` for `{Self}`, or use `#[strategy(overwrite)]` if this is a field of a struct that derives `Merge`"
+)]
 pub trait Merge<Partial>: Sized {
     /// Merges `Self` and `Partial` together, mutating `Self` in place.
     ///
@@ -165,24 +383,255 @@ pub trait Merge<Partial>: Sized {
     /// assert_eq!(toast.name, "Toast");
     /// assert_eq!(toast.age, 4);
     /// ```
+    #[must_use = "this returns the merged value, use `merge_in_place()` to merge in place"]
     fn merge(mut self, other: Partial) -> Self {
         self.merge_in_place(other);
         self
     }
+
+    /// Merges `Self` and `Partial` together in place, returning `&mut Self` so that calls can be
+    /// chained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Merge;
+    /// #
+    /// #[derive(Merge)]
+    /// #[partial(PartialCat, default)]
+    /// struct Cat {
+    ///     name: String,
+    ///     age: u16,
+    /// }
+    ///
+    /// let mut whiskers = Cat {
+    ///     name: "Whiskers".to_string(),
+    ///     age: 4,
+    /// };
+    ///
+    /// whiskers
+    ///     .apply(PartialCat {
+    ///         name: Some("Toast".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .apply(PartialCat {
+    ///         age: Some(5),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// assert_eq!(whiskers.name, "Toast");
+    /// assert_eq!(whiskers.age, 5);
+    /// ```
+    fn apply(&mut self, other: Partial) -> &mut Self {
+        self.merge_in_place(other);
+        self
+    }
+
+    /// Merges `Self` and `Partial` together in place, returning true if `Self` changed.
+    ///
+    /// This clones `Self` before merging and compares it afterward. Structs that derive
+    /// [`Merge`](derive@Merge) can avoid the clone with `#[partial(Name, report(compare))]`, which
+    /// generates `merge_in_place_reporting()` that compares each field as it is merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Merge;
+    /// #
+    /// #[derive(Merge, Clone, PartialEq)]
+    /// #[partial(PartialCat, default)]
+    /// struct Cat {
+    ///     name: String,
+    ///     age: u16,
+    /// }
+    ///
+    /// let mut whiskers = Cat {
+    ///     name: "Whiskers".to_string(),
+    ///     age: 4,
+    /// };
+    ///
+    /// assert!(!whiskers.merge_changed(PartialCat::default()));
+    ///
+    /// // Overwriting a field with an equal value does not change it.
+    /// assert!(!whiskers.merge_changed(PartialCat {
+    ///     age: Some(4),
+    ///     ..Default::default()
+    /// }));
+    ///
+    /// assert!(whiskers.merge_changed(PartialCat {
+    ///     age: Some(5),
+    ///     ..Default::default()
+    /// }));
+    /// ```
+    fn merge_changed(&mut self, other: Partial) -> bool
+    where
+        Self: PartialEq + Clone,
+    {
+        let before = self.clone();
+        self.merge_in_place(other);
+        *self != before
+    }
+
+    /// Merges every `Partial` in `partials` into `Self` in order, mutating `Self` in place.
+    ///
+    /// This is equivalent to calling [`Merge::merge_in_place()`] for each partial, and does
+    /// nothing if `partials` is empty.
+    ///
+    /// # Examples
+    ///
+    /// Configuration is often layered from several sources, where each source overrides the ones
+    /// before it:
+    ///
+    /// ```
+    /// # use mergeme::Merge;
+    /// #
+    /// #[derive(Merge)]
+    /// #[partial(PartialConfig, default)]
+    /// struct Config {
+    ///     host: String,
+    ///     port: u16,
+    ///     verbose: bool,
+    /// }
+    ///
+    /// let mut config = Config {
+    ///     host: "localhost".to_string(),
+    ///     port: 80,
+    ///     verbose: false,
+    /// };
+    ///
+    /// let file = PartialConfig {
+    ///     host: Some("example.com".to_string()),
+    ///     port: Some(8080),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let env = PartialConfig {
+    ///     port: Some(9000),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let cli = PartialConfig {
+    ///     verbose: Some(true),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Default < file < environment < command line.
+    /// config.merge_all_in_place([file, env, cli]);
+    ///
+    /// assert_eq!(config.host, "example.com");
+    /// assert_eq!(config.port, 9000);
+    /// assert!(config.verbose);
+    /// ```
+    fn merge_all_in_place<I>(&mut self, partials: I)
+    where
+        I: IntoIterator<Item = Partial>,
+    {
+        for partial in partials {
+            self.merge_in_place(partial);
+        }
+    }
+
+    /// Merges every `Partial` in `partials` into `Self` in order, returning a new `Self`.
+    ///
+    /// Later partials are merged after earlier ones, so they take precedence for overwritten fields
+    /// while merged fields accumulate every value.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "collections", doc = "```")]
+    #[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+    /// # use mergeme::Merge;
+    /// #
+    /// #[derive(Merge)]
+    /// #[partial(PartialCat, default)]
+    /// struct Cat {
+    ///     name: String,
+    ///     #[strategy(merge)]
+    ///     toys: Vec<String>,
+    /// }
+    ///
+    /// let whiskers = Cat {
+    ///     name: "Whiskers".to_string(),
+    ///     toys: Vec::new(),
+    /// };
+    ///
+    /// let toast = whiskers.merge_all([
+    ///     PartialCat {
+    ///         name: Some("Toast".to_string()),
+    ///         toys: Some(vec!["yarn".to_string()]),
+    ///     },
+    ///     PartialCat {
+    ///         toys: Some(vec!["mouse".to_string()]),
+    ///         ..Default::default()
+    ///     },
+    /// ]);
+    ///
+    /// assert_eq!(toast.name, "Toast");
+    /// assert_eq!(toast.toys, ["yarn", "mouse"]);
+    /// ```
+    #[must_use = "this returns the merged value, use `merge_all_in_place()` to merge in place"]
+    fn merge_all<I>(mut self, partials: I) -> Self
+    where
+        I: IntoIterator<Item = Partial>,
+    {
+        self.merge_all_in_place(partials);
+        self
+    }
 }
 
 /// Implements [`Merge`] for any type that implements [`Extend`].
 ///
 /// This means that most standard library collection types can be merged with anything iterable over
-/// the same type. Some highlights include:
+/// the same type. This requires the `collections` feature, which is enabled by default. Some
+/// highlights include:
 ///
 /// - `Vec<T>` with `IntoIterator<Item = T>`
-/// - `String` with `IntoIterator<Item = &str>` or `IntoIterator<Item = char>`
+/// - `String` with `IntoIterator<Item = char>`, or over `&str`, `String`, or `Cow<str>`
 /// - `HashMap<K, V>` with `IntoIterator<Item = (K, V)>`
 /// - `HashSet<T>` with `IntoIterator<Item = T>`
 /// - `BTreeMap<K, V>` with `IntoIterator<Item = (K, V)>`
 /// - `BTreeSet<T>` with `IntoIterator<Item = T>`
 /// - `PathBuf` with `IntoIterator<Item = Path>`
+/// - `(A, B)` with `IntoIterator<Item = (T, U)>`, where `A: Extend<T>` and `B: Extend<U>`
+///
+/// Since this calls [`Extend::extend()`], collections reserve capacity for the partial's items
+/// however they do when extended directly, such as from [`Iterator::size_hint()`] for `Vec<T>`.
+///
+/// To replace a collection rather than extend it, such as a list of hosts that an override should
+/// replace entirely, wrap it in [`Replace`](strategy::Replace).
+///
+/// Because tuples implement [`Extend`], they cannot also implement [`Merge`] element-wise, such as
+/// merging `(PartialA, PartialB)` into `(A, B)`. The compiler rejects this since it would overlap
+/// with this implementation if tuples ever implemented [`IntoIterator`]. Instead, merge each
+/// element on its own, or group the values in a struct that derives [`Merge`](derive@Merge) with
+/// `#[strategy(merge)]` fields.
+///
+/// The same applies to arrays and [`Option`], which do not implement [`Extend`] but could in
+/// another crate, such as `Extend<Local>` for `[T; N]`. Arrays can be merged slot by slot instead,
+/// as shown in the last example below. `Option<T>` fields of a struct that derives
+/// [`Merge`](derive@Merge) can be deeply merged with `#[partial(flatten_option)]` and
+/// `#[strategy(merge)]`, which merges the inner values if both are `Some`, sets the base's value if
+/// it is `None`, and keeps it if the partial's is `None`.
+///
+/// `Cow<str>` and `Cow<[T]>` do not implement [`Merge`] for the same reason. Instead, merge into
+/// the owned value returned by `Cow::to_mut()`. This clones a borrowed value, so it can be skipped
+/// if the partial is empty to keep the value borrowed, as shown in the second to last example
+/// below.
+///
+/// For the same reason, `String` does not implement `Merge<&str>`, `Merge<String>`, or
+/// `Merge<Cow<str>>`, since none of them are iterable. Instead, `String` implements
+/// [`MergeRef<str>`](MergeRef) with the `alloc` feature, which appends a `&str`, `&String`, or
+/// `&Cow<str>` without allocating if it is empty. Strings can also be merged by value by wrapping
+/// them in an array or [`Option`], as shown in the second example below.
+///
+/// Without the `collections` feature, [`Merge`] can be implemented for other types that implement
+/// [`Extend`] with partials that implement [`IntoIterator`], such as your own collections, or a
+/// `HashMap<K, V>` with a patch type whose values are merged into the map's rather than replacing
+/// them. The compiler rejects these with this implementation enabled, since they would overlap
+/// with it.
+/// However, collections then cannot be merged, including by `#[strategy(merge)]` fields, unless
+/// they are wrapped in a type that implements [`Merge`], such as the wrappers in [`strategy`],
+/// which implement it either way.
 ///
 /// # Examples
 ///
@@ -196,12 +645,18 @@ pub trait Merge<Partial>: Sized {
 /// ```
 ///
 /// ```
-/// # use mergeme::Merge;
+/// # use mergeme::{Merge, MergeRef};
+/// # use std::borrow::Cow;
 /// #
-/// let c = String::from("Hello, ");
-/// let d = ['w', 'o', 'r', 'l', 'd', '!'];
+/// let c = String::from("config");
+/// let d = String::from(".toml");
 ///
-/// assert_eq!(c.merge(d), "Hello, world!");
+/// assert_eq!(c.merge_ref("-local").merge_ref(&d), "config-local.toml");
+///
+/// let e: Cow<str> = Cow::Borrowed("-backup");
+///
+/// // Wrapping a string in an array or `Option` merges it by value.
+/// assert_eq!(d.merge([e]).merge(Some(".bak")), ".toml-backup.bak");
 /// ```
 ///
 /// ```
@@ -237,6 +692,73 @@ pub trait Merge<Partial>: Sized {
 ///
 /// assert_eq!(g.merge(h), [2, 4, 8, 16, 32, 64]);
 /// ```
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// let i = (vec!["a"], String::from("x"));
+/// let j = [("b", 'y'), ("c", 'z')];
+///
+/// assert_eq!(i.merge(j), (vec!["a", "b", "c"], String::from("xyz")));
+/// ```
+///
+/// ```
+/// # use mergeme::Merge;
+/// # use std::borrow::Cow;
+/// #
+/// fn merge_cow<'a, T: Clone>(base: &mut Cow<'a, [T]>, partial: impl IntoIterator<Item = T>) {
+///     let mut partial = partial.into_iter().peekable();
+///
+///     // Only clone the borrowed slice if there is something to merge into it.
+///     if partial.peek().is_some() {
+///         base.to_mut().merge_in_place(partial);
+///     }
+/// }
+///
+/// let mut k = Cow::Borrowed(&[1, 2][..]);
+///
+/// merge_cow(&mut k, []);
+/// assert!(matches!(k, Cow::Borrowed(_)));
+///
+/// merge_cow(&mut k, [3]);
+/// assert_eq!(k, Cow::<[i32]>::Owned(vec![1, 2, 3]));
+/// ```
+///
+/// ```
+/// # use mergeme::Merge;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialChannel)]
+/// struct Channel {
+///     volume: u8,
+///     muted: bool,
+/// }
+///
+/// let mut channels = [(); 4].map(|()| Channel {
+///     volume: 50,
+///     muted: false,
+/// });
+///
+/// // Only override the second channel, leaving the rest as-is.
+/// let overrides = [
+///     None,
+///     Some(PartialChannel {
+///         volume: Some(80),
+///         muted: None,
+///     }),
+///     None,
+///     None,
+/// ];
+///
+/// for (channel, partial) in channels.iter_mut().zip(overrides) {
+///     if let Some(partial) = partial {
+///         channel.merge_in_place(partial);
+///     }
+/// }
+///
+/// assert_eq!(channels.map(|channel| channel.volume), [50, 80, 50, 50]);
+/// ```
+#[cfg(feature = "collections")]
 impl<Base, Partial, Item> Merge<Partial> for Base
 where
     Base: Extend<Item>,
@@ -246,3 +768,433 @@ where
         self.extend(other);
     }
 }
+
+/// A trait for merging `Self` into a `Base`, the reverse of [`Merge`].
+///
+/// This is implemented for every `Partial` where `Base` implements [`Merge<Partial>`], and is
+/// useful when the partial struct is the subject of an expression, such as at the end of a chain
+/// of builder methods. It should not be implemented manually; implement [`Merge`] instead.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, MergeInto};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialWindow, default, builder)]
+/// struct Window {
+///     title: String,
+///     width: u32,
+///     height: u32,
+/// }
+///
+/// let mut window = Window {
+///     title: "Untitled".to_string(),
+///     width: 800,
+///     height: 600,
+/// };
+///
+/// PartialWindow::default()
+///     .with_title("Editor".to_string())
+///     .with_width(1024)
+///     .merge_into(&mut window);
+///
+/// assert_eq!(window.title, "Editor");
+/// assert_eq!((window.width, window.height), (1024, 600));
+/// ```
+pub trait MergeInto<Base> {
+    /// Merges `self` into `base`, mutating `base` in place.
+    ///
+    /// This is equivalent to `base.merge_in_place(self)`.
+    fn merge_into(self, base: &mut Base);
+}
+
+impl<Base, Partial> MergeInto<Base> for Partial
+where
+    Base: Merge<Partial>,
+{
+    fn merge_into(self, base: &mut Base) {
+        base.merge_in_place(self);
+    }
+}
+
+/// An extension trait for merging every item of an iterator into a base value.
+///
+/// This is implemented for every [`Iterator`], and is the reverse of [`Merge::merge_all()`], for
+/// when the iterator is the subject of an expression, such as at the end of a chain of adapters.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+/// # use mergeme::{Merge, MergeIteratorExt};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     port: u16,
+///     #[strategy(merge)]
+///     plugins: Vec<String>,
+/// }
+///
+/// let base = Config {
+///     port: 80,
+///     plugins: Vec::new(),
+/// };
+///
+/// let config = ["auth", "metrics"]
+///     .into_iter()
+///     .map(|plugin| PartialConfig {
+///         plugins: Some(vec![plugin.to_string()]),
+///         ..Default::default()
+///     })
+///     .merge_fold(base);
+///
+/// assert_eq!(config.plugins, ["auth", "metrics"]);
+/// ```
+pub trait MergeIteratorExt: Iterator {
+    /// Merges every item of this iterator into `base` in order, returning the merged `base`.
+    ///
+    /// This is equivalent to `base.merge_all(self)`.
+    #[must_use = "this returns the merged value, use `merge_all_in_place()` to merge in place"]
+    fn merge_fold<Base>(self, base: Base) -> Base
+    where
+        Self: Sized,
+        Base: Merge<Self::Item>,
+    {
+        base.merge_all(self)
+    }
+}
+
+impl<I: Iterator> MergeIteratorExt for I {}
+
+/// A trait for types with a primary partial type, so that generic code can refer to it as
+/// `T::Partial` without naming it.
+///
+/// [`Merge`](derive@Merge) implements this for the base struct, where `Partial` is the first
+/// partial struct that it generates. It is not implemented if merging may fail, since then the
+/// base struct only implements [`TryMerge`], or if the partial struct is less visible than the base
+/// struct, since `Partial` would expose it.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, Mergeable};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// fn load<T: Mergeable>(base: T, layers: impl IntoIterator<Item = T::Partial>) -> T {
+///     base.merge_all(layers)
+/// }
+///
+/// let config = Config {
+///     name: "server".to_string(),
+///     port: 80,
+/// };
+///
+/// let config = load(config, [PartialConfig {
+///     port: Some(8080),
+///     ..Default::default()
+/// }]);
+///
+/// assert_eq!(config.port, 8080);
+/// ```
+pub trait Mergeable: Merge<<Self as Mergeable>::Partial> {
+    /// The partial type that is merged into `Self`.
+    type Partial;
+}
+
+/// A trait for two types that can be merged into one, where merging may fail.
+///
+/// This is the fallible version of [`Merge`]. Every type that implements [`Merge`] also implements
+/// [`TryMerge`] with an [`Infallible`] error, so functions that accept [`TryMerge`] work with
+/// both.
+///
+/// [`Merge`](derive@Merge) implements this instead of [`Merge`] if any field uses
+/// `#[strategy(try_with = "...")]`. Please see [its documentation](derive@Merge) for further
+/// details.
+///
+/// # Implementing `TryMerge`
+///
+/// Since every [`Merge`] type already implements this, [`TryMerge`] can only be implemented for
+/// types that do not implement [`Merge`] with the same `Partial`. Because [`Merge`] is implemented
+/// for every type that implements [`Extend`], `Partial` should usually be a type from your own
+/// crate that does not implement [`IntoIterator`], like a partial struct.
+///
+/// For the same reason, this crate does not implement [`TryMerge`] for standard library types
+/// itself. The partial types that would make sense for them, such as arrays and [`Option`],
+/// implement [`IntoIterator`], so the compiler cannot rule out that they overlap with the
+/// implementation for [`Merge`] types.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::TryMerge;
+/// #
+/// struct Inventory {
+///     items: Vec<String>,
+///     capacity: usize,
+/// }
+///
+/// struct Loot {
+///     items: Vec<String>,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Full;
+///
+/// impl TryMerge<Loot> for Inventory {
+///     type Error = Full;
+///
+///     fn try_merge_in_place(&mut self, other: Loot) -> Result<(), Self::Error> {
+///         if self.items.len() + other.items.len() > self.capacity {
+///             return Err(Full);
+///         }
+///
+///         self.items.extend(other.items);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let mut inventory = Inventory {
+///     items: vec!["sword".to_string()],
+///     capacity: 2,
+/// };
+///
+/// let shield = Loot {
+///     items: vec!["shield".to_string()],
+/// };
+///
+/// assert_eq!(inventory.try_merge_in_place(shield), Ok(()));
+///
+/// let potion = Loot {
+///     items: vec!["potion".to_string()],
+/// };
+///
+/// assert_eq!(inventory.try_merge_in_place(potion), Err(Full));
+/// ```
+///
+/// Merges that would break an invariant can be rejected before anything is changed:
+///
+/// ```
+/// # use mergeme::TryMerge;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// struct Range {
+///     min: u32,
+///     max: u32,
+/// }
+///
+/// struct PartialRange {
+///     min: Option<u32>,
+///     max: Option<u32>,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Inverted;
+///
+/// impl TryMerge<PartialRange> for Range {
+///     type Error = Inverted;
+///
+///     fn try_merge_in_place(&mut self, other: PartialRange) -> Result<(), Self::Error> {
+///         let min = other.min.unwrap_or(self.min);
+///         let max = other.max.unwrap_or(self.max);
+///
+///         if min > max {
+///             return Err(Inverted);
+///         }
+///
+///         *self = Range { min, max };
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let range = Range { min: 1, max: 10 };
+///
+/// let range = range
+///     .try_merge(PartialRange {
+///         min: Some(5),
+///         max: None,
+///     })
+///     .unwrap();
+///
+/// assert_eq!(range, Range { min: 5, max: 10 });
+///
+/// let mut range = range;
+///
+/// let result = range.try_merge_in_place(PartialRange {
+///     min: None,
+///     max: Some(2),
+/// });
+///
+/// assert_eq!(result, Err(Inverted));
+///
+/// // The range was not changed.
+/// assert_eq!(range, Range { min: 5, max: 10 });
+/// ```
+pub trait TryMerge<Partial>: Sized {
+    /// The error returned when merging fails.
+    type Error;
+
+    /// Tries to merge `Self` and `Partial` together, mutating `Self` in place.
+    ///
+    /// If this returns an error, `Self` may have been partially modified. Please see the
+    /// implementation's documentation for what it guarantees.
+    fn try_merge_in_place(&mut self, other: Partial) -> Result<(), Self::Error>;
+
+    /// Tries to merge `Self` and `Partial` together, returning a new `Self`.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "collections", doc = "```")]
+    #[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+    /// # use mergeme::TryMerge;
+    /// #
+    /// // Every `Merge` implementation is also a `TryMerge` implementation that never fails.
+    /// let merged = vec![1, 2].try_merge([3]);
+    ///
+    /// assert_eq!(merged, Ok(vec![1, 2, 3]));
+    /// ```
+    fn try_merge(mut self, other: Partial) -> Result<Self, Self::Error> {
+        self.try_merge_in_place(other)?;
+        Ok(self)
+    }
+}
+
+/// Implements [`TryMerge`] for any type that implements [`Merge`], which never fails.
+impl<Base, Partial> TryMerge<Partial> for Base
+where
+    Base: Merge<Partial>,
+{
+    type Error = Infallible;
+
+    fn try_merge_in_place(&mut self, other: Partial) -> Result<(), Self::Error> {
+        self.merge_in_place(other);
+        Ok(())
+    }
+}
+
+/// A trait for merging a borrowed `Partial` into `Self`, without consuming it.
+///
+/// This is useful when the same partial struct, such as a cached set of defaults, should be merged
+/// into many values. Implementations usually clone the parts of `Partial` that they merge.
+///
+/// This is separate from [`Merge`], which consumes `Partial`. `Merge<&Partial>` may also be
+/// implemented for borrowed partials, but that requires a sized `Partial` and is already
+/// implemented for collections whose items are [`Copy`] through [`Extend`]. [`MergeRef`] instead
+/// supports unsized partials like slices and [`str`], and clones any [`Clone`] items.
+///
+/// With the `alloc` feature, this is implemented for collections in the [`alloc`] crate:
+///
+/// - `Vec<T>` and `VecDeque<T>` with `[T]`
+/// - `String` with `str`
+/// - `BTreeMap<K, V>` with `BTreeMap<K, V>`
+/// - `BTreeSet<T>` with `BTreeSet<T>`
+///
+/// [`Merge`](derive@Merge) implements this for the base struct if `#[partial(Name, by_ref)]` is
+/// specified, alongside `Merge<&Partial>`. Please see [its documentation](derive@Merge) for further
+/// details.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, MergeRef};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, by_ref)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// let defaults = PartialConfig {
+///     name: None,
+///     port: Some(8080),
+/// };
+///
+/// let mut a = Config {
+///     name: "a".to_string(),
+///     port: 80,
+/// };
+///
+/// let mut b = Config {
+///     name: "b".to_string(),
+///     port: 443,
+/// };
+///
+/// a.merge_ref_in_place(&defaults);
+/// b.merge_ref_in_place(&defaults);
+///
+/// assert_eq!((a.name.as_str(), a.port), ("a", 8080));
+/// assert_eq!((b.name.as_str(), b.port), ("b", 8080));
+/// ```
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use mergeme::MergeRef;
+/// #
+/// let plugins = vec!["auth".to_string(), "metrics".to_string()];
+///
+/// let a = vec!["tracing".to_string()].merge_ref(&plugins[..]);
+/// let b = Vec::new().merge_ref(&plugins[..]);
+///
+/// assert_eq!(a, ["tracing", "auth", "metrics"]);
+/// assert_eq!(b, ["auth", "metrics"]);
+///
+/// let suffix = String::from("!");
+///
+/// assert_eq!(String::from("Hello").merge_ref(suffix.as_str()), "Hello!");
+/// ```
+pub trait MergeRef<Partial: ?Sized>: Sized {
+    /// Merges a borrowed `Partial` into `Self`, mutating `Self` in place.
+    fn merge_ref_in_place(&mut self, other: &Partial);
+
+    /// Merges a borrowed `Partial` into `Self`, returning a new `Self`.
+    #[must_use = "this returns the merged value, use `merge_ref_in_place()` to merge in place"]
+    fn merge_ref(mut self, other: &Partial) -> Self {
+        self.merge_ref_in_place(other);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> MergeRef<[T]> for Vec<T> {
+    fn merge_ref_in_place(&mut self, other: &[T]) {
+        self.extend_from_slice(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> MergeRef<[T]> for VecDeque<T> {
+    fn merge_ref_in_place(&mut self, other: &[T]) {
+        self.extend(other.iter().cloned());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MergeRef<str> for String {
+    fn merge_ref_in_place(&mut self, other: &str) {
+        self.push_str(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> MergeRef<BTreeMap<K, V>> for BTreeMap<K, V> {
+    fn merge_ref_in_place(&mut self, other: &BTreeMap<K, V>) {
+        self.extend(other.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + Clone> MergeRef<BTreeSet<T>> for BTreeSet<T> {
+    fn merge_ref_in_place(&mut self, other: &BTreeSet<T>) {
+        self.extend(other.iter().cloned());
+    }
+}
@@ -0,0 +1,41 @@
+/// Constructs a partial struct from only the fields that are set.
+///
+/// Each listed field is wrapped in `Some`, while every other field is filled in with
+/// [`Default::default()`], so the partial struct must implement [`Default`]. This is usually done
+/// with `#[partial(Name, default)]`, where the default of every field is `None`.
+///
+/// Since the fields are set directly, this cannot construct `compact` partial structs, whose fields
+/// are private.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, partial};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     name: String,
+///     version: u32,
+///     dependencies: Vec<String>,
+/// }
+///
+/// let partial = partial!(PartialConfig { version: 3 });
+///
+/// assert_eq!(partial.name, None);
+/// assert_eq!(partial.version, Some(3));
+/// assert_eq!(partial.dependencies, None);
+///
+/// // This is equivalent to:
+/// let mut partial = PartialConfig::default();
+/// partial.version = Some(3);
+/// ```
+#[macro_export]
+macro_rules! partial {
+    ($partial:ty { $($field:ident: $value:expr),* $(,)? }) => {{
+        #[allow(unused_mut)]
+        let mut partial: $partial = ::core::default::Default::default();
+        $(partial.$field = ::core::option::Option::Some($value);)*
+        partial
+    }};
+}
@@ -0,0 +1,231 @@
+use crate::Merge;
+
+/// A value that an override may leave unset, clear, or set.
+///
+/// This is a readable version of `Option<Option<T>>` for overriding an optional value, where an
+/// override needs to distinguish between not changing the value ([`Unset`](Self::Unset)), changing
+/// it to `None` ([`Clear`](Self::Clear)), and changing it to `Some` ([`Set`](Self::Set)).
+///
+/// `Option<T>` implements `Merge<Overridable<T>>`, so an [`Overridable`] can be the type of a field
+/// in a hand-written partial struct for an `Option<T>` field. Structs that derive
+/// [`Merge`](derive@crate::Merge) store these fields as `Option<Option<T>>`, which an
+/// [`Overridable`] converts into and from with [`From`].
+///
+/// With the `serde` feature, [`Set`](Self::Set) is (de)serialized as its value and
+/// [`Clear`](Self::Clear) as `null`. [`Unset`](Self::Unset) is the [`Default`], so a missing key
+/// deserializes to it if the field has `#[serde(default)]`, and it is skipped with
+/// `#[serde(skip_serializing_if = "Overridable::is_unset")]`. Otherwise it is serialized as `null`.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, Overridable};
+/// #
+/// struct Config {
+///     proxy: Option<String>,
+///     timeout: Option<u32>,
+/// }
+///
+/// #[derive(Default)]
+/// struct PartialConfig {
+///     proxy: Overridable<String>,
+///     timeout: Overridable<u32>,
+/// }
+///
+/// impl Merge<PartialConfig> for Config {
+///     fn merge_in_place(&mut self, other: PartialConfig) {
+///         self.proxy.merge_in_place(other.proxy);
+///         self.timeout.merge_in_place(other.timeout);
+///     }
+/// }
+///
+/// let config = Config {
+///     proxy: Some("localhost:8080".to_string()),
+///     timeout: Some(30),
+/// };
+///
+/// let config = config.merge(PartialConfig {
+///     proxy: Overridable::Clear,
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(config.proxy, None);
+/// assert_eq!(config.timeout, Some(30));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Overridable<T> {
+    /// The value is left unchanged.
+    #[default]
+    Unset,
+    /// The value is changed to `None`.
+    Clear,
+    /// The value is changed to `Some`.
+    Set(T),
+}
+
+impl<T> Overridable<T> {
+    /// Returns true if this is [`Unset`](Self::Unset).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// assert!(Overridable::<u32>::Unset.is_unset());
+    /// assert!(!Overridable::<u32>::Clear.is_unset());
+    /// ```
+    pub const fn is_unset(&self) -> bool {
+        matches!(self, Self::Unset)
+    }
+
+    /// Returns true if this is [`Clear`](Self::Clear).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// assert!(Overridable::<u32>::Clear.is_clear());
+    /// assert!(!Overridable::Set(1).is_clear());
+    /// ```
+    pub const fn is_clear(&self) -> bool {
+        matches!(self, Self::Clear)
+    }
+
+    /// Returns true if this is [`Set`](Self::Set).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// assert!(Overridable::Set(1).is_set());
+    /// assert!(!Overridable::<u32>::Unset.is_set());
+    /// ```
+    pub const fn is_set(&self) -> bool {
+        matches!(self, Self::Set(_))
+    }
+
+    /// Returns `self` if it is not [`Unset`](Self::Unset), and `other` otherwise.
+    ///
+    /// This is the same as merging `self` into `other`, so that `self` takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// assert_eq!(Overridable::Unset.or(Overridable::Set(1)), Overridable::Set(1));
+    /// assert_eq!(Overridable::Clear.or(Overridable::Set(1)), Overridable::Clear);
+    /// ```
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Unset => other,
+            _ => self,
+        }
+    }
+
+    /// Maps a [`Set`](Self::Set) value with `f`, leaving [`Unset`](Self::Unset) and
+    /// [`Clear`](Self::Clear) unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// assert_eq!(Overridable::Set("8080").map(str::len), Overridable::Set(4));
+    /// assert_eq!(Overridable::<&str>::Clear.map(str::len), Overridable::Clear);
+    /// ```
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Overridable<U> {
+        match self {
+            Self::Unset => Overridable::Unset,
+            Self::Clear => Overridable::Clear,
+            Self::Set(value) => Overridable::Set(f(value)),
+        }
+    }
+
+    /// Converts from `&Overridable<T>` to `Overridable<&T>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::Overridable;
+    /// #
+    /// let proxy = Overridable::Set("localhost:8080".to_string());
+    ///
+    /// assert_eq!(proxy.as_ref().map(String::len), Overridable::Set(14));
+    /// ```
+    pub const fn as_ref(&self) -> Overridable<&T> {
+        match self {
+            Self::Unset => Overridable::Unset,
+            Self::Clear => Overridable::Clear,
+            Self::Set(value) => Overridable::Set(value),
+        }
+    }
+}
+
+/// Overwrites `self` with the partial's value unless it is [`Unset`](Overridable::Unset).
+impl<T> Merge<Overridable<T>> for Option<T> {
+    fn merge_in_place(&mut self, other: Overridable<T>) {
+        match other {
+            Overridable::Unset => {}
+            Overridable::Clear => *self = None,
+            Overridable::Set(value) => *self = Some(value),
+        }
+    }
+}
+
+/// Overwrites `self` with the partial unless it is [`Unset`](Overridable::Unset), so that two
+/// overrides can be combined before being merged.
+impl<T> Merge<Overridable<T>> for Overridable<T> {
+    fn merge_in_place(&mut self, other: Overridable<T>) {
+        if !other.is_unset() {
+            *self = other;
+        }
+    }
+}
+
+/// Converts `None` into [`Unset`](Overridable::Unset), `Some(None)` into
+/// [`Clear`](Overridable::Clear), and `Some(Some(value))` into [`Set`](Overridable::Set).
+impl<T> From<Option<Option<T>>> for Overridable<T> {
+    fn from(value: Option<Option<T>>) -> Self {
+        match value {
+            None => Self::Unset,
+            Some(None) => Self::Clear,
+            Some(Some(value)) => Self::Set(value),
+        }
+    }
+}
+
+/// Converts [`Unset`](Overridable::Unset) into `None`, [`Clear`](Overridable::Clear) into
+/// `Some(None)`, and [`Set`](Overridable::Set) into `Some(Some(value))`.
+impl<T> From<Overridable<T>> for Option<Option<T>> {
+    fn from(value: Overridable<T>) -> Self {
+        match value {
+            Overridable::Unset => None,
+            Overridable::Clear => Some(None),
+            Overridable::Set(value) => Some(Some(value)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Overridable<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unset | Self::Clear => serializer.serialize_none(),
+            Self::Set(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Overridable<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A missing key never reaches this, so it is left to `#[serde(default)]`.
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            None => Self::Clear,
+            Some(value) => Self::Set(value),
+        })
+    }
+}
@@ -0,0 +1,138 @@
+/// A report of which fields were changed by a merge, returned by
+/// `merge_in_place_reporting()`.
+///
+/// `N` is the number of fields in the partial struct. This stores the names of all fields alongside
+/// which of them were set or changed, so that it does not need to allocate.
+///
+/// A field is _set_ if it was `Some` in the partial struct. If the report was generated with
+/// `#[partial(Name, report(compare))]`, a field is _changed_ if its value differs from before the
+/// merge. Otherwise, every set field is assumed to be changed.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, MergeReport};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default, report(compare))]
+/// struct Config {
+///     log_level: u8,
+///     workers: u16,
+///     name: String,
+/// }
+///
+/// let mut config = Config {
+///     log_level: 1,
+///     workers: 4,
+///     name: "server".to_string(),
+/// };
+///
+/// let report: MergeReport<3> = config.merge_in_place_reporting(PartialConfig {
+///     log_level: Some(2),
+///     workers: Some(4),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(report.set().collect::<Vec<_>>(), ["log_level", "workers"]);
+///
+/// // `workers` was overwritten with an equal value, so it did not change.
+/// assert_eq!(report.changed().collect::<Vec<_>>(), ["log_level"]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MergeReport<const N: usize> {
+    fields: &'static [&'static str; N],
+    set: [bool; N],
+    changed: Option<[bool; N]>,
+}
+
+impl<const N: usize> MergeReport<N> {
+    /// Creates a new [`MergeReport`] from the names of all fields, whether each of them was set,
+    /// and whether each of them changed, if that was compared.
+    ///
+    /// This is usually called by code generated by [`Merge`](derive@crate::Merge).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::MergeReport;
+    /// #
+    /// let report = MergeReport::new(&["name", "version"], [true, true], Some([false, true]));
+    ///
+    /// assert!(report.is_set("name"));
+    /// assert!(!report.is_changed("name"));
+    /// assert!(report.is_changed("version"));
+    /// ```
+    pub const fn new(
+        fields: &'static [&'static str; N],
+        set: [bool; N],
+        changed: Option<[bool; N]>,
+    ) -> Self {
+        Self {
+            fields,
+            set,
+            changed,
+        }
+    }
+
+    /// Returns an iterator over the names of the fields that were set, in the order they were
+    /// declared.
+    pub fn set(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .zip(self.set.iter())
+            .filter_map(|(&field, &set)| set.then_some(field))
+    }
+
+    /// Returns an iterator over the names of the fields that changed, in the order they were
+    /// declared.
+    ///
+    /// If values were not compared, this is the same as [`MergeReport::set()`].
+    pub fn changed(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let changed = self.changed.as_ref().unwrap_or(&self.set);
+
+        self.fields
+            .iter()
+            .zip(changed.iter())
+            .filter_map(|(&field, &changed)| changed.then_some(field))
+    }
+
+    /// Returns true if the field with the given name was set.
+    pub fn is_set(&self, field: &str) -> bool {
+        self.set().any(|set| set == field)
+    }
+
+    /// Returns true if the field with the given name changed.
+    ///
+    /// If values were not compared, this is the same as [`MergeReport::is_set()`].
+    pub fn is_changed(&self, field: &str) -> bool {
+        self.changed().any(|changed| changed == field)
+    }
+
+    /// Returns true if values were compared to determine whether they changed.
+    pub fn is_compared(&self) -> bool {
+        self.changed.is_some()
+    }
+
+    /// Returns true if any field changed.
+    ///
+    /// If values were not compared, this is true if any field was set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::MergeReport;
+    /// #
+    /// let report = MergeReport::new(&["name", "version"], [true, false], Some([false, false]));
+    ///
+    /// assert!(!report.is_empty());
+    /// assert!(!report.has_changed());
+    /// ```
+    pub fn has_changed(&self) -> bool {
+        self.changed().next().is_some()
+    }
+
+    /// Returns true if no fields were set, meaning that the merge did nothing.
+    pub fn is_empty(&self) -> bool {
+        !self.set.contains(&true)
+    }
+}
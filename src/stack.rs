@@ -0,0 +1,210 @@
+use core::{fmt, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+use crate::Merge;
+
+/// An ordered list of partials that are merged onto a base, such as layers of configuration.
+///
+/// The layers are stored from the lowest to the highest precedence: [`resolve()`] merges them onto
+/// the base in order, so a later layer overrides an earlier one. [`push()`] adds a layer with the
+/// highest precedence so far, while [`insert()`] adds one at a given precedence.
+///
+/// [`resolve()`]: MergeStack::resolve
+/// [`push()`]: MergeStack::push
+/// [`insert()`]: MergeStack::insert
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, MergeStack};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig, default)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// let mut stack = MergeStack::new();
+///
+/// // The config file.
+/// stack.push(PartialConfig {
+///     name: Some("server".to_string()),
+///     port: Some(8080),
+/// });
+///
+/// // Command-line arguments.
+/// stack.push(PartialConfig {
+///     port: Some(3000),
+///     ..Default::default()
+/// });
+///
+/// // Environment variables, which take precedence over the config file but not the arguments.
+/// stack.insert(1, PartialConfig {
+///     port: Some(80),
+///     ..Default::default()
+/// });
+///
+/// let defaults = Config {
+///     name: "mergeme".to_string(),
+///     port: 0,
+/// };
+///
+/// let config = stack.resolve(defaults);
+///
+/// assert_eq!(config.name, "server");
+/// assert_eq!(config.port, 3000);
+/// ```
+pub struct MergeStack<B, P> {
+    layers: Vec<P>,
+    base: PhantomData<fn(B) -> B>,
+}
+
+impl<B, P> MergeStack<B, P> {
+    /// Creates a new [`MergeStack`] with no layers.
+    pub const fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            base: PhantomData,
+        }
+    }
+
+    /// Adds a layer that takes precedence over every existing layer.
+    pub fn push(&mut self, partial: P) {
+        self.layers.push(partial);
+    }
+
+    /// Adds a layer at `index`, which takes precedence over the layers before it but not the layers
+    /// after it.
+    ///
+    /// An `index` of 0 adds a layer with the lowest precedence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::MergeStack;
+    /// #
+    /// let mut stack: MergeStack<Vec<u8>, Vec<u8>> = MergeStack::new();
+    ///
+    /// stack.push(vec![1]);
+    /// stack.push(vec![3]);
+    /// stack.insert(1, vec![2]);
+    ///
+    /// assert_eq!(stack.layers(), [vec![1], vec![2], vec![3]]);
+    /// ```
+    pub fn insert(&mut self, index: usize, partial: P) {
+        self.layers.insert(index, partial);
+    }
+
+    /// Removes and returns the layer at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> P {
+        self.layers.remove(index)
+    }
+
+    /// Returns the layers, from the lowest to the highest precedence.
+    pub fn layers(&self) -> &[P] {
+        &self.layers
+    }
+
+    /// Returns the layers mutably, from the lowest to the highest precedence.
+    pub fn layers_mut(&mut self) -> &mut [P] {
+        &mut self.layers
+    }
+
+    /// Returns the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns true if there are no layers.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the layers, from the lowest to the highest precedence.
+    pub fn into_layers(self) -> Vec<P> {
+        self.layers
+    }
+
+    /// Merges every layer onto `base` in order, from the lowest to the highest precedence.
+    ///
+    /// To resolve the same layers more than once, clone the [`MergeStack`] first.
+    ///
+    /// This is equivalent to [`Merge::merge_all()`] with the layers.
+    #[must_use = "this returns the merged value, use `resolve_in_place()` to merge in place"]
+    pub fn resolve(self, base: B) -> B
+    where
+        B: Merge<P>,
+    {
+        base.merge_all(self.layers)
+    }
+
+    /// Merges every layer onto `base` in place, from the lowest to the highest precedence.
+    pub fn resolve_in_place(self, base: &mut B)
+    where
+        B: Merge<P>,
+    {
+        base.merge_all_in_place(self.layers);
+    }
+}
+
+impl<B, P: Clone> Clone for MergeStack<B, P> {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self.layers.clone(),
+            base: PhantomData,
+        }
+    }
+}
+
+impl<B, P: fmt::Debug> fmt::Debug for MergeStack<B, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeStack")
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
+
+impl<B, P: PartialEq> PartialEq for MergeStack<B, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.layers == other.layers
+    }
+}
+
+impl<B, P: Eq> Eq for MergeStack<B, P> {}
+
+impl<B, P> Default for MergeStack<B, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B, P> From<Vec<P>> for MergeStack<B, P> {
+    fn from(layers: Vec<P>) -> Self {
+        Self {
+            layers,
+            base: PhantomData,
+        }
+    }
+}
+
+impl<B, P> FromIterator<P> for MergeStack<B, P> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl<B, P> Extend<P> for MergeStack<B, P> {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        self.layers.extend(iter);
+    }
+}
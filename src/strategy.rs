@@ -0,0 +1,1840 @@
+//! Wrapper types that change how a value is merged.
+//!
+//! These are useful as the types of fields that are merged by hand or with
+//! `#[strategy(merge)]`, where the wrapped type's own [`Merge`] implementation would do the wrong
+//! thing.
+//!
+//! # Partials
+//!
+//! Most wrappers implement [`Extend`] instead of [`Merge`]. With the `collections` feature, the
+//! implementation of [`Merge`] for every type that implements [`Extend`] covers them, so they can
+//! be merged with anything iterable over the values they extend with, such as another wrapper or an
+//! `Option<T>`.
+//!
+//! That implementation also prevents them from implementing `Merge<T>` for the bare value they
+//! wrap, such as `Max<u32>: Merge<u32>`. The compiler rejects it, since the two would overlap for
+//! any `T` that implements [`IntoIterator`] over itself, which it cannot rule out. [`Replace`] and
+//! [`Intersect`] only implement [`Merge`] with themselves for a similar reason: another crate could
+//! implement [`Extend`] for a wrapper of its own type.
+//!
+//! Without the `collections` feature, wrappers implement [`Merge`] with themselves and with
+//! `Option<T>` directly. Either way, a bare value can be merged by wrapping it in `Some`, and a
+//! hand-written partial struct can store `Option<T>` rather than an `Option` of the wrapper:
+//!
+//! ```
+//! # use mergeme::{Merge, strategy::{Max, Min}};
+//! # use std::time::Duration;
+//! #
+//! struct Limits {
+//!     timeout: Min<Duration>,
+//!     workers: Max<u32>,
+//! }
+//!
+//! struct PartialLimits {
+//!     timeout: Option<Duration>,
+//!     workers: Option<u32>,
+//! }
+//!
+//! impl Merge<PartialLimits> for Limits {
+//!     fn merge_in_place(&mut self, other: PartialLimits) {
+//!         self.timeout.merge_in_place(other.timeout);
+//!         self.workers.merge_in_place(other.workers);
+//!     }
+//! }
+//!
+//! let limits = Limits {
+//!     timeout: Min(Duration::from_secs(30)),
+//!     workers: Max(4),
+//! };
+//!
+//! let limits = limits.merge(PartialLimits {
+//!     timeout: Some(Duration::from_secs(10)),
+//!     workers: Some(2),
+//! });
+//!
+//! assert_eq!(*limits.timeout, Duration::from_secs(10));
+//! assert_eq!(*limits.workers.merge(Some(8)), 8);
+//! ```
+//!
+//! Partial structs generated by [`Merge`](derive@crate::Merge) store an `Option` of the wrapper for
+//! `#[strategy(merge)]` fields, which can be created from the bare value with [`From`], such as
+//! `Some(8.into())`.
+
+use core::{
+    iter,
+    ops::{AddAssign, Deref, DerefMut, Not},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque, btree_map},
+    string::String,
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet, hash_map};
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+use serde::{Deserialize, Deserializer};
+
+use crate::Merge;
+
+/// A wrapper that merges by replacing the base's value with the partial's.
+///
+/// Collections such as `Vec` implement [`Merge`] by appending the partial's items, but sometimes
+/// a list in an override should replace the base's list entirely. Wrapping the collection in
+/// [`Replace`] opts it out of that, while [`Deref`], [`DerefMut`], and [`From`] keep it transparent
+/// to use otherwise.
+///
+/// This only implements `Merge<Replace<T>>`, so the partial must be wrapped as well, as explained
+/// under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the value it wraps.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+/// # use mergeme::{Merge, strategy::Replace};
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialConfig)]
+/// struct Config {
+///     #[strategy(merge)]
+///     plugins: Vec<String>,
+///     #[strategy(merge)]
+///     hosts: Replace<Vec<String>>,
+/// }
+///
+/// let config = Config {
+///     plugins: vec!["auth".to_string()],
+///     hosts: Replace(vec!["localhost".to_string()]),
+/// };
+///
+/// let partial = PartialConfig {
+///     plugins: Some(vec!["metrics".to_string()]),
+///     hosts: Some(vec!["example.com".to_string()].into()),
+/// };
+///
+/// let config = config.merge(partial);
+///
+/// // `plugins` is appended to, while `hosts` is replaced.
+/// assert_eq!(config.plugins, ["auth", "metrics"]);
+/// assert_eq!(*config.hosts, ["example.com"]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Replace<T>(pub T);
+
+impl<T> Replace<T> {
+    /// Returns the wrapped value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Replace;
+    /// #
+    /// let hosts = Replace(vec!["localhost"]);
+    ///
+    /// assert_eq!(hosts.into_inner(), ["localhost"]);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Merge<Replace<T>> for Replace<T> {
+    fn merge_in_place(&mut self, other: Replace<T>) {
+        *self = other;
+    }
+}
+
+impl<T> From<T> for Replace<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Replace<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Replace<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper that keeps a collection sorted as it is merged with other items.
+///
+/// Collections such as `Vec` implement [`Merge`] by appending the partial's items, which breaks
+/// the order of a sorted list that is binary searched. [`Sorted`] implements [`Extend`] by
+/// extending the collection and then sorting it with the stable [`slice::sort()`], so items that
+/// compare equal keep their order, with the base's items before the partial's. Duplicates are
+/// preserved. Since the collection was already sorted, this is close to linear when only a few
+/// items are merged.
+///
+/// This is implemented for collections that are [`IntoIterator`] and [`AsMut<[Item]>`](AsMut)
+/// whose items are [`Ord`], such as `Vec<T>`, and requires the `alloc` feature. With the
+/// `collections` feature or without it, it implements [`Merge`] through [`Extend`], so it can be
+/// merged with anything iterable over its items, including another [`Sorted`].
+///
+/// [`Sorted`] only derefs to the collection immutably, so that it cannot be unsorted. Use
+/// [`Sorted::into_inner()`] to modify it, then [`Sorted::new()`] to sort it again.
+///
+/// With the `serde` feature, this is (de)serialized as the collection it wraps, and is sorted
+/// after being deserialized.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Sorted};
+/// #
+/// let ports = Sorted::new(vec![443, 80, 8080]);
+///
+/// assert_eq!(*ports, [80, 443, 8080]);
+///
+/// let ports = ports.merge([3000, 80]);
+///
+/// assert_eq!(*ports, [80, 80, 443, 3000, 8080]);
+/// assert!(ports.binary_search(&3000).is_ok());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Sorted<C>(C);
+
+#[cfg(feature = "alloc")]
+impl<C> Sorted<C>
+where
+    C: IntoIterator + AsMut<[C::Item]>,
+    C::Item: Ord,
+{
+    /// Sorts `inner` and wraps it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Sorted;
+    /// #
+    /// let names = Sorted::new(vec!["lou", "janette", "mike"]);
+    ///
+    /// assert_eq!(*names, ["janette", "lou", "mike"]);
+    /// ```
+    pub fn new(mut inner: C) -> Self {
+        inner.as_mut().sort();
+        Self(inner)
+    }
+
+    /// Wraps `inner` without sorting it.
+    ///
+    /// It is a logic error for `inner` not to be sorted, which results in merged items being
+    /// sorted incorrectly. This is checked in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Sorted;
+    /// #
+    /// let ids = Sorted::from_sorted_unchecked(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(*ids, [1, 2, 3]);
+    /// ```
+    pub fn from_sorted_unchecked(mut inner: C) -> Self {
+        debug_assert!(inner.as_mut().is_sorted(), "`inner` is not sorted");
+        Self(inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Sorted<C> {
+    /// Returns the wrapped collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Sorted;
+    /// #
+    /// let mut ids = Sorted::new(vec![2, 1]).into_inner();
+    ///
+    /// ids.push(0);
+    ///
+    /// assert_eq!(ids, [1, 2, 0]);
+    /// ```
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Extend<C::Item> for Sorted<C>
+where
+    C: IntoIterator + Extend<C::Item> + AsMut<[C::Item]>,
+    C::Item: Ord,
+{
+    fn extend<I: IntoIterator<Item = C::Item>>(&mut self, iter: I) {
+        self.0.extend(iter);
+        self.0.as_mut().sort();
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "collections")))]
+impl<C, P> Merge<P> for Sorted<C>
+where
+    Self: Extend<P::Item>,
+    P: IntoIterator,
+{
+    fn merge_in_place(&mut self, other: P) {
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: IntoIterator> IntoIterator for Sorted<C> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> From<C> for Sorted<C>
+where
+    C: IntoIterator + AsMut<[C::Item]>,
+    C::Item: Ord,
+{
+    fn from(inner: C) -> Self {
+        Self::new(inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Deref for Sorted<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl<'de, C> Deserialize<'de> for Sorted<C>
+where
+    C: Deserialize<'de> + IntoIterator + AsMut<[C::Item]>,
+    C::Item: Ord,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// A wrapper that keeps only the `N` most recently merged items of a collection.
+///
+/// Collections such as `Vec` implement [`Merge`] by appending the partial's items, so lists that
+/// are merged into repeatedly, such as a history of recently opened files, grow without bound.
+/// [`Capped`] implements [`Extend`] by extending the collection and then removing items from its
+/// front until it has at most `N` items. The newest items are kept, so if a single partial has more
+/// than `N` items, only its last `N` items are kept.
+///
+/// This is implemented for `Vec<T>` and `VecDeque<T>`, and requires the `alloc` feature. With the
+/// `collections` feature or without it, it implements [`Merge`] through [`Extend`], so it can be
+/// merged with anything iterable over its items, including another [`Capped`].
+///
+/// [`Capped`] only derefs to the collection immutably, so that it cannot grow past `N` items. Use
+/// [`Capped::into_inner()`] to modify it, then [`Capped::new()`] to cap it again.
+///
+/// With the `serde` feature, this is (de)serialized as the collection it wraps, and is capped
+/// after being deserialized.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Capped};
+/// #
+/// let recent: Capped<Vec<&str>, 3> = Capped::new(vec!["a.txt", "b.txt"]);
+///
+/// let recent = recent.merge(["c.txt", "d.txt"]);
+///
+/// // `a.txt` was the oldest, so it was removed.
+/// assert_eq!(*recent, ["b.txt", "c.txt", "d.txt"]);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Capped<C, const N: usize>(C);
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> Capped<C, N> {
+    /// Returns the wrapped collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Capped;
+    /// #
+    /// let mut history = Capped::<_, 2>::new(vec![1, 2, 3]).into_inner();
+    ///
+    /// history.push(4);
+    ///
+    /// assert_eq!(history, [2, 3, 4]);
+    /// ```
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> Capped<C, N>
+where
+    C: IntoIterator,
+    Self: Extend<C::Item>,
+{
+    /// Removes the oldest items from the front of `inner` until it has at most `N` items, then
+    /// wraps it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Capped;
+    /// #
+    /// let history: Capped<_, 2> = Capped::new(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(*history, [2, 3]);
+    /// ```
+    pub fn new(inner: C) -> Self {
+        let mut capped = Self(inner);
+
+        // Extending removes the oldest items, even if there are no new ones.
+        capped.extend(core::iter::empty());
+        capped
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> Extend<T> for Capped<Vec<T>, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+
+        let excess = self.0.len().saturating_sub(N);
+        self.0.drain(..excess);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> Extend<T> for Capped<VecDeque<T>, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+
+        let excess = self.0.len().saturating_sub(N);
+        self.0.drain(..excess);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> From<C> for Capped<C, N>
+where
+    C: IntoIterator,
+    Self: Extend<C::Item>,
+{
+    fn from(inner: C) -> Self {
+        Self::new(inner)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl<'de, C, const N: usize> Deserialize<'de> for Capped<C, N>
+where
+    C: Deserialize<'de> + IntoIterator,
+    Self: Extend<C::Item>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "collections")))]
+impl<C, const N: usize, P> Merge<P> for Capped<C, N>
+where
+    Self: Extend<P::Item>,
+    P: IntoIterator,
+{
+    fn merge_in_place(&mut self, other: P) {
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: IntoIterator, const N: usize> IntoIterator for Capped<C, N> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, const N: usize> Deref for Capped<C, N> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A wrapper that merges maps key by key, merging the values of keys that both maps contain.
+///
+/// Maps such as `HashMap` implement [`Merge`] by inserting the partial's entries, which replaces
+/// the base's value for every key that both maps contain. [`MergeMap`] implements [`Extend`] by
+/// merging the partial's value into the base's with [`Merge::merge_in_place()`] if the key exists,
+/// and inserting it otherwise. This is useful for maps of nested configuration, such as a map of
+/// named profiles that each layer only partially overrides.
+///
+/// This is implemented for `BTreeMap<K, V>` with the `alloc` feature, and `HashMap<K, V, S>` with
+/// the `std` feature. The partial's values may be any `PV` where `V: Merge<PV> + From<PV>`, which
+/// includes `V` itself if `V: Merge<V>`, such as for a map of partial structs. With the
+/// `collections` feature or without it, it implements [`Merge`] through [`Extend`], so it can be
+/// merged with anything iterable over `(K, PV)`, including another [`MergeMap`].
+///
+/// With the `serde` feature, this is (de)serialized as the map it wraps.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "collections", doc = "```")]
+#[cfg_attr(not(feature = "collections"), doc = "```ignore")]
+/// # use mergeme::{Merge, strategy::MergeMap};
+/// # use std::collections::HashMap;
+/// #
+/// #[derive(Merge)]
+/// #[partial(PartialProfile, default)]
+/// struct Profile {
+///     opt_level: u8,
+///     debug: bool,
+///     #[strategy(merge)]
+///     features: Vec<String>,
+/// }
+///
+/// #[derive(Merge)]
+/// #[partial(PartialConfig)]
+/// struct Config {
+///     // Profiles are stored as partial structs, so that each layer may only set some fields.
+///     #[strategy(merge)]
+///     profiles: MergeMap<HashMap<String, PartialProfile>>,
+/// }
+///
+/// let config = Config {
+///     profiles: MergeMap(HashMap::from([(
+///         "release".to_string(),
+///         PartialProfile {
+///             opt_level: Some(3),
+///             debug: Some(false),
+///             features: Some(vec!["lto".to_string()]),
+///         },
+///     )])),
+/// };
+///
+/// let partial = PartialConfig {
+///     profiles: Some(MergeMap(HashMap::from([
+///         (
+///             "release".to_string(),
+///             PartialProfile {
+///                 debug: Some(true),
+///                 features: Some(vec!["simd".to_string()]),
+///                 ..Default::default()
+///             },
+///         ),
+///         (
+///             "dev".to_string(),
+///             PartialProfile {
+///                 opt_level: Some(0),
+///                 ..Default::default()
+///             },
+///         ),
+///     ]))),
+/// };
+///
+/// let config = config.merge(partial);
+///
+/// // `release` was merged rather than replaced, and `dev` was inserted.
+/// let release = &config.profiles["release"];
+///
+/// assert_eq!(release.opt_level, Some(3));
+/// assert_eq!(release.debug, Some(true));
+/// assert_eq!(release.features.as_deref(), Some(&["lto".to_string(), "simd".to_string()][..]));
+/// assert_eq!(config.profiles["dev"].opt_level, Some(0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct MergeMap<M>(pub M);
+
+impl<M> MergeMap<M> {
+    /// Returns the wrapped map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::MergeMap;
+    /// # use std::collections::BTreeMap;
+    /// #
+    /// let limits = MergeMap(BTreeMap::from([("requests", 100)]));
+    ///
+    /// assert_eq!(limits.into_inner()["requests"], 100);
+    /// ```
+    pub fn into_inner(self) -> M {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, PV> Extend<(K, PV)> for MergeMap<BTreeMap<K, V>>
+where
+    K: Ord,
+    V: Merge<PV> + From<PV>,
+{
+    fn extend<I: IntoIterator<Item = (K, PV)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            match self.0.entry(key) {
+                btree_map::Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(value),
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(V::from(value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, PV, S> Extend<(K, PV)> for MergeMap<HashMap<K, V, S>>
+where
+    K: Eq + Hash,
+    V: Merge<PV> + From<PV>,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, PV)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            match self.0.entry(key) {
+                hash_map::Entry::Occupied(mut entry) => entry.get_mut().merge_in_place(value),
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(V::from(value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<M, P> Merge<P> for MergeMap<M>
+where
+    Self: Extend<P::Item>,
+    P: IntoIterator,
+{
+    fn merge_in_place(&mut self, other: P) {
+        self.extend(other);
+    }
+}
+
+impl<M: IntoIterator> IntoIterator for MergeMap<M> {
+    type Item = M::Item;
+    type IntoIter = M::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<M> From<M> for MergeMap<M> {
+    fn from(map: M) -> Self {
+        Self(map)
+    }
+}
+
+impl<M> Deref for MergeMap<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M> DerefMut for MergeMap<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper for a map of lists that merges by appending to the list of each key.
+///
+/// Maps such as `HashMap<K, Vec<V>>` implement [`Merge`] by inserting the partial's entries, which
+/// replaces the base's list for every key that both maps contain. [`MultiMap`] implements
+/// [`Extend`] by appending the partial's values to the list of their key, inserting an empty list
+/// first if the key is new. The partial may be iterable over single values, `(K, V)`, or over
+/// lists of values, `(K, Vec<V>)`, such as another map or [`MultiMap`].
+///
+/// Values keep the order in which they were merged within each list, while the order of the keys
+/// follows the underlying map.
+///
+/// This is implemented for `BTreeMap<K, Vec<V>>` with the `alloc` feature, and
+/// `HashMap<K, Vec<V>, S>` with the `std` feature. With the `collections` feature or without it, it
+/// implements [`Merge`] through [`Extend`].
+///
+/// With the `serde` feature, this is (de)serialized as the map it wraps.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use mergeme::{Merge, strategy::MultiMap};
+/// # use std::collections::BTreeMap;
+/// #
+/// let routes = MultiMap(BTreeMap::from([("/api", vec!["api-1"])]));
+///
+/// let routes = routes
+///     .merge([("/api", vec!["api-2", "api-3"]), ("/", vec!["web-1"])])
+///     .merge([("/", "web-2")]);
+///
+/// assert_eq!(routes["/api"], ["api-1", "api-2", "api-3"]);
+/// assert_eq!(routes["/"], ["web-1", "web-2"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct MultiMap<M>(pub M);
+
+impl<M> MultiMap<M> {
+    /// Returns the wrapped map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::MultiMap;
+    /// # use std::collections::BTreeMap;
+    /// #
+    /// let routes = MultiMap(BTreeMap::from([("/", vec!["web-1"])]));
+    ///
+    /// assert_eq!(routes.into_inner()["/"], ["web-1"]);
+    /// ```
+    pub fn into_inner(self) -> M {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> Extend<(K, V)> for MultiMap<BTreeMap<K, Vec<V>>> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.0.entry(key).or_default().push(value);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> Extend<(K, Vec<V>)> for MultiMap<BTreeMap<K, Vec<V>>> {
+    fn extend<I: IntoIterator<Item = (K, Vec<V>)>>(&mut self, iter: I) {
+        for (key, values) in iter {
+            self.0.entry(key).or_default().extend(values);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Extend<(K, V)> for MultiMap<HashMap<K, Vec<V>, S>>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.0.entry(key).or_default().push(value);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Extend<(K, Vec<V>)> for MultiMap<HashMap<K, Vec<V>, S>>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, Vec<V>)>>(&mut self, iter: I) {
+        for (key, values) in iter {
+            self.0.entry(key).or_default().extend(values);
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<M, P> Merge<P> for MultiMap<M>
+where
+    Self: Extend<P::Item>,
+    P: IntoIterator,
+{
+    fn merge_in_place(&mut self, other: P) {
+        self.extend(other);
+    }
+}
+
+impl<M: IntoIterator> IntoIterator for MultiMap<M> {
+    type Item = M::Item;
+    type IntoIter = M::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<M> From<M> for MultiMap<M> {
+    fn from(map: M) -> Self {
+        Self(map)
+    }
+}
+
+impl<M> Deref for MultiMap<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M> DerefMut for MultiMap<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper for a set that merges by narrowing it to the items that are also in the partial.
+///
+/// Sets such as `HashSet` implement [`Merge`] by inserting the partial's items, so the result is
+/// the union of every layer. [`Intersect`] instead keeps only the items that are in both sets, so
+/// the result is the intersection of every layer, such as for a set of permissions that each layer
+/// may only restrict.
+///
+/// An empty partial set restricts the base to nothing, clearing it. To leave the base as-is,
+/// do not merge a partial at all, such as by leaving the partial struct's field `None`.
+///
+/// This is implemented for `BTreeSet<T>` with the `alloc` feature, and `HashSet<T, S>` with the
+/// `std` feature. This only implements `Merge<Intersect<S>>`, so the partial must be wrapped as
+/// well, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the set it wraps.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use mergeme::{Merge, strategy::Intersect};
+/// # use std::collections::BTreeSet;
+/// #
+/// let permissions = Intersect(BTreeSet::from(["read", "write", "delete"]));
+///
+/// let permissions = permissions
+///     .merge(Intersect(BTreeSet::from(["read", "write"])))
+///     .merge(Intersect(BTreeSet::from(["read", "execute"])));
+///
+/// assert_eq!(*permissions, BTreeSet::from(["read"]));
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Intersect<S>(pub S);
+
+impl<S> Intersect<S> {
+    /// Returns the wrapped set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Intersect;
+    /// # use std::collections::BTreeSet;
+    /// #
+    /// let permissions = Intersect(BTreeSet::from(["read"]));
+    ///
+    /// assert_eq!(permissions.into_inner(), BTreeSet::from(["read"]));
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Merge<Intersect<BTreeSet<T>>> for Intersect<BTreeSet<T>> {
+    fn merge_in_place(&mut self, other: Intersect<BTreeSet<T>>) {
+        self.0.retain(|item| other.0.contains(item));
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Merge<Intersect<HashSet<T, S>>> for Intersect<HashSet<T, S>>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn merge_in_place(&mut self, other: Intersect<HashSet<T, S>>) {
+        self.0.retain(|item| other.0.contains(item));
+    }
+}
+
+impl<S> From<S> for Intersect<S> {
+    fn from(set: S) -> Self {
+        Self(set)
+    }
+}
+
+impl<S> Deref for Intersect<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for Intersect<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper for a collection that merges by removing the partial's items from it.
+///
+/// Collections such as `Vec` implement [`Merge`] by adding the partial's items, so a layer can
+/// never take away an item that an earlier layer added. [`Exclude`] implements [`Extend`] by
+/// removing each of the partial's items instead, such as for a list of default plugins that an
+/// override may disable. Items that are not in the base are ignored.
+///
+/// - `Vec<T>` keeps only the items that are not equal to any of the partial's items. If the base
+///   contains an item more than once, every occurrence is removed.
+/// - `BTreeSet<T>` and `HashSet<T, S>` remove each of the partial's items.
+/// - `BTreeMap<K, V>` and `HashMap<K, V, S>` remove the entry of each of the partial's keys.
+///
+/// The partial may be anything that is iterable over the items or keys to remove, such as an
+/// array, a `Vec`, or another [`Exclude`]. This is implemented for `Vec<T>`, `BTreeSet<T>`, and
+/// `BTreeMap<K, V>` with the `alloc` feature, and `HashSet<T, S>` and `HashMap<K, V, S>` with the
+/// `std` feature. With the `collections` feature or without it, it implements [`Merge`] through
+/// [`Extend`].
+///
+/// With the `serde` feature, this is (de)serialized as the collection it wraps.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use mergeme::{Merge, strategy::Exclude};
+/// #
+/// let plugins = Exclude(vec!["auth", "metrics", "tracing", "metrics"]);
+///
+/// let plugins = plugins.merge(["metrics", "cache"]).merge(vec!["tracing"]);
+///
+/// assert_eq!(*plugins, ["auth"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Exclude<C>(pub C);
+
+impl<C> Exclude<C> {
+    /// Returns the wrapped collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Exclude;
+    /// #
+    /// let plugins = Exclude(vec!["auth"]);
+    ///
+    /// assert_eq!(plugins.into_inner(), ["auth"]);
+    /// ```
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PartialEq> Extend<T> for Exclude<Vec<T>> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for excluded in iter {
+            self.0.retain(|item| *item != excluded);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Extend<T> for Exclude<BTreeSet<T>> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.0.remove(&item);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Extend<T> for Exclude<HashSet<T, S>>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.0.remove(&item);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> Extend<K> for Exclude<BTreeMap<K, V>> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.0.remove(&key);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Extend<K> for Exclude<HashMap<K, V, S>>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.0.remove(&key);
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<C, P> Merge<P> for Exclude<C>
+where
+    Self: Extend<P::Item>,
+    P: IntoIterator,
+{
+    fn merge_in_place(&mut self, other: P) {
+        self.extend(other);
+    }
+}
+
+impl<C: IntoIterator> IntoIterator for Exclude<C> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<C> From<C> for Exclude<C> {
+    fn from(collection: C) -> Self {
+        Self(collection)
+    }
+}
+
+impl<C> Deref for Exclude<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C> DerefMut for Exclude<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper that merges by keeping the smaller of the base's and the partial's values.
+///
+/// This is useful for limits that each layer may only tighten, such as a timeout. If the values are
+/// equal, the base's value is kept.
+///
+/// The partial may be another [`Min`], or anything else that is iterable over the wrapped type,
+/// such as an `Option<T>` in a hand-written partial struct. Without the `collections` feature, it
+/// merges with `T`, `Option<T>`, and [`Min`] instead, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the value it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Min};
+/// # use std::time::Duration;
+/// #
+/// let timeout = Min(Duration::from_secs(30));
+///
+/// let timeout = timeout
+///     .merge(Min(Duration::from_secs(10)))
+///     .merge(Some(Duration::from_secs(20)))
+///     .merge(None);
+///
+/// assert_eq!(*timeout, Duration::from_secs(10));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Min<T>(pub T);
+
+impl<T> Min<T> {
+    /// Returns the wrapped value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Min;
+    /// #
+    /// let timeout = Min(30);
+    ///
+    /// assert_eq!(timeout.into_inner(), 30);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Ord> Extend<T> for Min<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            if value < self.0 {
+                self.0 = value;
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: Ord> Merge<Min<T>> for Min<T> {
+    fn merge_in_place(&mut self, other: Min<T>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: Ord> Merge<Option<T>> for Min<T> {
+    fn merge_in_place(&mut self, other: Option<T>) {
+        self.extend(other);
+    }
+}
+
+impl<T> IntoIterator for Min<T> {
+    type Item = T;
+    type IntoIter = iter::Once<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+impl<T> From<T> for Min<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Min<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Min<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper that merges by keeping the larger of the base's and the partial's values.
+///
+/// This is useful for values that each layer may only raise, such as a minimum supported version.
+/// If the values are equal, the base's value is kept.
+///
+/// The partial may be another [`Max`], or anything else that is iterable over the wrapped type,
+/// such as an `Option<T>` in a hand-written partial struct. Without the `collections` feature, it
+/// merges with `T`, `Option<T>`, and [`Max`] instead, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the value it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Max};
+/// #
+/// let limit = Max(100u32);
+///
+/// let limit = limit.merge(Max(250)).merge(Some(50)).merge(None);
+///
+/// assert_eq!(*limit, 250);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Max<T>(pub T);
+
+impl<T> Max<T> {
+    /// Returns the wrapped value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Max;
+    /// #
+    /// let limit = Max(100);
+    ///
+    /// assert_eq!(limit.into_inner(), 100);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Ord> Extend<T> for Max<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            if value > self.0 {
+                self.0 = value;
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: Ord> Merge<Max<T>> for Max<T> {
+    fn merge_in_place(&mut self, other: Max<T>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: Ord> Merge<Option<T>> for Max<T> {
+    fn merge_in_place(&mut self, other: Option<T>) {
+        self.extend(other);
+    }
+}
+
+impl<T> IntoIterator for Max<T> {
+    type Item = T;
+    type IntoIter = iter::Once<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+impl<T> From<T> for Max<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Max<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Max<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper that merges by adding the partial's value to the base's.
+///
+/// This is useful for values that accumulate across layers, such as a counter. It requires the
+/// wrapped type to implement [`AddAssign`], so integer overflow is handled as it is for `+=`.
+///
+/// The partial may be another [`Sum`], or anything else that is iterable over the wrapped type,
+/// such as an `Option<T>` in a hand-written partial struct. Without the `collections` feature, it
+/// merges with `T`, `Option<T>`, and [`Sum`] instead, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the value it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Sum};
+/// #
+/// let retries = Sum(1u32);
+///
+/// let retries = retries.merge(Sum(2)).merge(Some(3)).merge(None);
+///
+/// assert_eq!(*retries, 6);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Sum<T>(pub T);
+
+impl<T> Sum<T> {
+    /// Returns the wrapped value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Sum;
+    /// #
+    /// let retries = Sum(3);
+    ///
+    /// assert_eq!(retries.into_inner(), 3);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: AddAssign> Extend<T> for Sum<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.0 += value;
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: AddAssign> Merge<Sum<T>> for Sum<T> {
+    fn merge_in_place(&mut self, other: Sum<T>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl<T: AddAssign> Merge<Option<T>> for Sum<T> {
+    fn merge_in_place(&mut self, other: Option<T>) {
+        self.extend(other);
+    }
+}
+
+impl<T> IntoIterator for Sum<T> {
+    type Item = T;
+    type IntoIter = iter::Once<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+impl<T> From<T> for Sum<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Sum<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Sum<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper for a `bool` that merges with OR, so that it is `true` if any layer sets it.
+///
+/// This is useful for flags that any layer may enable but none may disable again, such as
+/// verbose logging.
+///
+/// The partial may be another [`Any`], or anything else that is iterable over `bool`, such as an
+/// `Option<bool>` in a hand-written partial struct. Without the `collections` feature, it merges
+/// with `bool`, `Option<bool>`, and [`Any`] instead, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the `bool` it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Any};
+/// #
+/// let verbose = Any(false);
+///
+/// let verbose = verbose.merge(Any(true)).merge(Some(false)).merge(None);
+///
+/// assert!(*verbose);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Any(pub bool);
+
+impl Any {
+    /// Returns the wrapped `bool`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Any;
+    /// #
+    /// assert!(Any(true).into_inner());
+    /// ```
+    pub fn into_inner(self) -> bool {
+        self.0
+    }
+}
+
+impl Extend<bool> for Any {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for value in iter {
+            self.0 |= value;
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl Merge<Any> for Any {
+    fn merge_in_place(&mut self, other: Any) {
+        self.extend(other);
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl Merge<Option<bool>> for Any {
+    fn merge_in_place(&mut self, other: Option<bool>) {
+        self.extend(other);
+    }
+}
+
+impl IntoIterator for Any {
+    type Item = bool;
+    type IntoIter = iter::Once<bool>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+impl From<bool> for Any {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for Any {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Any {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Not for Any {
+    type Output = bool;
+
+    fn not(self) -> Self::Output {
+        !self.0
+    }
+}
+
+/// A wrapper for a `bool` that merges with AND, so that it is only `true` if every layer allows it.
+///
+/// This is useful for permissions that any layer may revoke but none may grant again, such as
+/// whether network access is allowed.
+///
+/// The partial may be another [`All`], or anything else that is iterable over `bool`, such as an
+/// `Option<bool>` in a hand-written partial struct. Without the `collections` feature, it merges
+/// with `bool`, `Option<bool>`, and [`All`] instead, as explained under [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the `bool` it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::All};
+/// #
+/// let allow_network = All(true);
+///
+/// let allow_network = allow_network.merge(All(false)).merge(Some(true)).merge(None);
+///
+/// assert!(!allow_network);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct All(pub bool);
+
+impl All {
+    /// Returns the wrapped `bool`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::All;
+    /// #
+    /// assert!(All(true).into_inner());
+    /// ```
+    pub fn into_inner(self) -> bool {
+        self.0
+    }
+}
+
+impl Extend<bool> for All {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for value in iter {
+            self.0 &= value;
+        }
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl Merge<All> for All {
+    fn merge_in_place(&mut self, other: All) {
+        self.extend(other);
+    }
+}
+
+#[cfg(not(feature = "collections"))]
+impl Merge<Option<bool>> for All {
+    fn merge_in_place(&mut self, other: Option<bool>) {
+        self.extend(other);
+    }
+}
+
+impl IntoIterator for All {
+    type Item = bool;
+    type IntoIter = iter::Once<bool>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+impl From<bool> for All {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for All {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for All {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Not for All {
+    type Output = bool;
+
+    fn not(self) -> Self::Output {
+        !self.0
+    }
+}
+
+/// A wrapper that merges by keeping the value with the newest version, such as a counter or a
+/// timestamp.
+///
+/// Merging usually keeps whichever value was merged last, but partials that arrive out of order,
+/// such as from other machines, should not replace a newer value with an older one. [`Latest`]
+/// only replaces its value if the partial's version is strictly greater, so the result is the
+/// value with the greatest version no matter the order they are merged in. If the versions are
+/// equal, the base's value is kept, so merging the same partial twice does nothing.
+///
+/// This implements `Merge<Latest<T, V>>`, where `V` must implement [`Ord`].
+///
+/// With the `serde` feature, this is (de)serialized as a struct with `value` and `version` fields.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Latest};
+/// #
+/// let host = Latest::new("localhost", 2);
+///
+/// let host = host
+///     .merge(Latest::new("example.com", 5))
+///     .merge(Latest::new("old.example.com", 3));
+///
+/// assert_eq!(*host, "example.com");
+/// assert_eq!(host.version(), &5);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Latest<T, V = u64> {
+    value: T,
+    version: V,
+}
+
+impl<T, V> Latest<T, V> {
+    /// Creates a new [`Latest`] from a value and its version.
+    pub const fn new(value: T, version: V) -> Self {
+        Self { value, version }
+    }
+
+    /// Returns a reference to the value.
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a reference to the value's version.
+    pub const fn version(&self) -> &V {
+        &self.version
+    }
+
+    /// Returns the value, discarding its version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Latest;
+    /// #
+    /// let host = Latest::new("localhost", 2);
+    ///
+    /// assert_eq!(host.into_inner(), "localhost");
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the value and its version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Latest;
+    /// #
+    /// let host = Latest::new("localhost", 2);
+    ///
+    /// assert_eq!(host.into_parts(), ("localhost", 2));
+    /// ```
+    pub fn into_parts(self) -> (T, V) {
+        (self.value, self.version)
+    }
+}
+
+impl<T, V: Ord> Merge<Latest<T, V>> for Latest<T, V> {
+    fn merge_in_place(&mut self, other: Latest<T, V>) {
+        if other.version > self.version {
+            *self = other;
+        }
+    }
+}
+
+impl<T, V> Deref for Latest<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// A wrapper that merges strings by joining them with the separator `SEP`.
+///
+/// Merging a [`String`] on its own appends the partial with nothing in between, which mangles
+/// lists such as space-separated flags or comma-separated tags. [`Joined`] inserts `SEP` between
+/// the base and the partial instead, unless either of them is empty, so the separator never
+/// appears at either end or twice in a row.
+///
+/// The partial may be another [`Joined`], or anything else that is iterable over [`String`]s or
+/// `&str`s, such as an `Option<String>` in a hand-written partial struct. Since a [`Joined`] with
+/// any separator may be the partial, its separator may need to be written out, as in `Joined::<'
+/// '>::from("...")`. Without the `collections` feature, it merges with `String`, `&str`, an
+/// `Option` of either, and a [`Joined`] with the same separator instead, as explained under
+/// [Partials](self#partials).
+///
+/// With the `serde` feature, this is (de)serialized as the string it wraps.
+///
+/// # Examples
+///
+/// ```
+/// # use mergeme::{Merge, strategy::Joined};
+/// #
+/// let flags: Joined<' '> = Joined("-C opt-level=3".to_string());
+///
+/// let flags = flags
+///     .merge(Joined::<' '>::from("-C lto"))
+///     .merge(Some("-C debuginfo=0"))
+///     .merge(Some(""));
+///
+/// assert_eq!(*flags, "-C opt-level=3 -C lto -C debuginfo=0");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct Joined<const SEP: char>(pub String);
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> Joined<SEP> {
+    /// Returns the wrapped string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mergeme::strategy::Joined;
+    /// #
+    /// let tags: Joined<','> = Joined("config".to_string());
+    ///
+    /// assert_eq!(tags.into_inner(), "config");
+    /// ```
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn push(&mut self, string: &str) {
+        if string.is_empty() {
+            return;
+        }
+
+        if !self.0.is_empty() {
+            self.0.push(SEP);
+        }
+
+        self.0.push_str(string);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> Extend<String> for Joined<SEP> {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for string in iter {
+            if self.0.is_empty() {
+                // Reuse the partial's allocation.
+                self.0 = string;
+            } else {
+                self.push(&string);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, const SEP: char> Extend<&'a str> for Joined<SEP> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for string in iter {
+            self.push(string);
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "collections")))]
+impl<const SEP: char> Merge<Joined<SEP>> for Joined<SEP> {
+    fn merge_in_place(&mut self, other: Joined<SEP>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "collections")))]
+impl<const SEP: char> Merge<Option<String>> for Joined<SEP> {
+    fn merge_in_place(&mut self, other: Option<String>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "collections")))]
+impl<'a, const SEP: char> Merge<Option<&'a str>> for Joined<SEP> {
+    fn merge_in_place(&mut self, other: Option<&'a str>) {
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> IntoIterator for Joined<SEP> {
+    type Item = String;
+    type IntoIter = iter::Once<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> From<String> for Joined<SEP> {
+    fn from(string: String) -> Self {
+        Self(string)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> From<&str> for Joined<SEP> {
+    fn from(string: &str) -> Self {
+        Self(String::from(string))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> Deref for Joined<SEP> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const SEP: char> DerefMut for Joined<SEP> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
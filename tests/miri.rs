@@ -0,0 +1,36 @@
+//! Runs the UI tests that exercise the `unsafe` code generated for `#[partial(Name, compact)]` as
+//! regular tests, since trybuild cannot run under Miri. Check them with:
+//!
+//! ```sh
+//! cargo +nightly miri test --test miri
+//! ```
+
+// Like the UI tests in `tests/ui/pass`, these merge collections.
+#![cfg(feature = "collections")]
+
+mod compact {
+    include!("ui/pass/compact.rs");
+
+    #[test]
+    fn compact() {
+        main();
+    }
+}
+
+mod compact_scope {
+    include!("ui/pass/compact_scope.rs");
+
+    #[test]
+    fn compact_scope() {
+        main();
+    }
+}
+
+mod drop_order {
+    include!("ui/pass/drop_order.rs");
+
+    #[test]
+    fn drop_order() {
+        main();
+    }
+}
@@ -4,6 +4,32 @@ use trybuild::TestCases;
 fn ui() {
     let t = TestCases::new();
 
-    t.pass("tests/ui/pass/*.rs");
-    t.compile_fail("tests/ui/fail/*/*.rs");
+    // Most tests merge collections, which requires the blanket implementation for `Extend`. Run
+    // the others with `cargo test --no-default-features --features derive,alloc`.
+    if cfg!(feature = "collections") {
+        t.pass("tests/ui/pass/*.rs");
+        t.compile_fail("tests/ui/fail/*/*.rs");
+    } else if cfg!(feature = "alloc") {
+        t.pass("tests/ui/no_collections/*.rs");
+    }
+
+    // Run with `cargo test --features serde`.
+    if cfg!(feature = "serde") {
+        t.pass("tests/ui/serde/*.rs");
+    }
+
+    // Run with `cargo test --features env`.
+    if cfg!(feature = "env") {
+        t.pass("tests/ui/env/*.rs");
+    }
+
+    // Run with `cargo test --features figment`.
+    if cfg!(feature = "figment") {
+        t.pass("tests/ui/figment/*.rs");
+    }
+
+    // Run with `cargo test --features json`.
+    if cfg!(feature = "json") {
+        t.pass("tests/ui/json/*.rs");
+    }
 }
@@ -0,0 +1,80 @@
+use mergeme::{EnvError, FromEnv, Merge};
+
+#[derive(Merge)]
+#[partial(PartialServer, default, impls(Debug, PartialEq), from_env)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, impls(Debug, PartialEq), from_env)]
+struct Config<T> {
+    name: String,
+    #[partial(flatten_option)]
+    license: Option<String>,
+    #[partial(rename = "type")]
+    kind: T,
+    #[strategy(merge)]
+    #[partial(env_nested)]
+    server: PartialServer,
+    #[strategy(merge)]
+    #[partial(env_nested)]
+    proxy: PartialServer,
+}
+
+fn main() {
+    // SAFETY: This test runs in its own process, and does not spawn other threads.
+    unsafe {
+        std::env::set_var("APP_NAME", "mergeme");
+        std::env::set_var("APP_TYPE", "3");
+        std::env::set_var("APP_SERVER__PORT", "8080");
+    }
+
+    let partial = PartialConfig::<u8>::from_env("APP").unwrap();
+
+    // Only the variables that are set are `Some`, including within nested partial structs.
+    assert_eq!(
+        partial,
+        PartialConfig {
+            name: Some("mergeme".to_string()),
+            r#type: Some(3),
+            server: Some(PartialServer {
+                port: Some(8080),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+
+    let config = Config {
+        name: "server".to_string(),
+        license: None,
+        kind: 1,
+        server: PartialServer {
+            host: Some("localhost".to_string()),
+            port: Some(80),
+        },
+        proxy: PartialServer::default(),
+    };
+
+    let config = config.merge(partial);
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.kind, 3);
+    assert_eq!(config.server.host.as_deref(), Some("localhost"));
+    assert_eq!(config.server.port, Some(8080));
+
+    // An empty prefix reads the names without one.
+    unsafe { std::env::set_var("PORT", "3000") };
+
+    assert_eq!(PartialServer::from_env("").unwrap().port, Some(3000));
+
+    // A value that cannot be parsed reports the variable's name.
+    unsafe { std::env::set_var("APP_SERVER__PORT", "eighty") };
+
+    let error: EnvError = PartialConfig::<u8>::from_env("APP").unwrap_err();
+
+    assert_eq!(error.var(), "APP_SERVER__PORT");
+    assert_eq!(error.message(), "invalid digit found in string");
+}
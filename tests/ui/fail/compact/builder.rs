@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// The builder's methods would conflict with the compact partial struct's accessors.
+#[partial(CompactConfig, compact, builder)]
+struct Config {
+    name: String,
+}
+
+fn main() {}
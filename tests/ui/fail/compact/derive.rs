@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// Derived traits would read fields that may be uninitialized.
+#[partial(CompactConfig, compact, derive(Clone))]
+struct Config {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, PartialEq)]
+#[partial(PartialConfig, diff)]
+struct Config {
+    name: String,
+    // A difference of `Some(1)` to `None` could not be represented.
+    #[partial(flatten_option)]
+    version: Option<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, PartialEq)]
+#[partial(PartialConfig, diff)]
+struct Config {
+    name: String,
+    // Merging the difference would append to the old dependencies instead of replacing them.
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialBackend)]
+enum Backend {
+    Sqlite {
+        path: String,
+        // A skipped field could not be filled in when replacing a different variant.
+        #[partial(skip)]
+        cache: Vec<String>,
+    },
+    Memory,
+}
+
+fn main() {}
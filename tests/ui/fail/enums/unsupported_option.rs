@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// There is no variant to default to.
+#[partial(PartialBackend, default)]
+enum Backend {
+    Sqlite { path: String },
+    Memory,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use mergeme::Merge;
+
+struct ConfigPatch {
+    name: Option<String>,
+}
+
+#[derive(Merge)]
+// `ConfigPatch` is not generated, so it cannot have options.
+#[partial(existing = ConfigPatch, default)]
+struct Config {
+    name: String,
+}
+
+fn main() {}
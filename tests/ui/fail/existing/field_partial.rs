@@ -0,0 +1,15 @@
+use mergeme::Merge;
+
+struct ConfigPatch {
+    name: Option<String>,
+}
+
+#[derive(Merge)]
+#[partial(existing = ConfigPatch)]
+struct Config {
+    // There is no generated partial struct to apply this attribute to.
+    #[partial(serde(alias = "title"))]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use mergeme::Merge;
+
+struct ConfigPatch {
+    name: Option<String>,
+}
+
+#[derive(Merge)]
+#[partial(existing = ConfigPatch)]
+struct Config {
+    name: String,
+    // `ConfigPatch` does not have a `version` field.
+    version: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // Only `Option<T>` fields can be flattened.
+    #[partial(flatten_option)]
+    version: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfigFile)]
+#[partial(PartialConfigCli)]
+struct Config {
+    // Flattening changes how the field is merged, so it must apply to every partial struct.
+    #[partial(PartialConfigCli, flatten_option)]
+    version: Option<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialServer)]
+struct Server {
+    port: u16,
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    #[strategy(merge)]
+    #[partial(env_nested)]
+    server: PartialServer,
+}
+
+fn main() {}
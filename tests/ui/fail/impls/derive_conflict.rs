@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// Both of these implement `Clone` for `PartialConfig`.
+#[partial(PartialConfig, impls(Clone, Debug), derive(Clone))]
+struct Config {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// Only `Clone`, `Debug`, and `PartialEq` are supported.
+#[partial(PartialConfig, impls(Clone, Hash))]
+struct Config {
+    name: String,
+}
+
+fn main() {}
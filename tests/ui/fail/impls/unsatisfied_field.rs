@@ -0,0 +1,13 @@
+use mergeme::Merge;
+
+struct NotClone;
+
+#[derive(Merge)]
+#[partial(PartialConfig, impls(Clone))]
+struct Config {
+    name: String,
+    // This field's type does not implement `Clone`.
+    secret: NotClone,
+}
+
+fn main() {}
@@ -1,10 +0,0 @@
-use mergeme::Merge;
-
-#[derive(Merge)]
-#[partial(PartialChoice)]
-enum Choice {
-    A,
-    B,
-}
-
-fn main() {}
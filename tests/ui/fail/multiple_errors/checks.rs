@@ -0,0 +1,13 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// `diff` cannot be used with merged or flattened fields, and both are reported.
+#[partial(PartialConfig, diff)]
+struct Config {
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    #[partial(flatten_option)]
+    proxy: Option<String>,
+}
+
+fn main() {}
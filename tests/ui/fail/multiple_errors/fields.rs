@@ -0,0 +1,17 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // Every mistake below is reported in the same compilation.
+    #[strategy(add)]
+    name: String,
+    #[strategy(sum)]
+    version: u32,
+    #[strategy(max)]
+    edition: u16,
+    #[partial()]
+    authors: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// `by_ref` is specified twice.
+#[partial(PartialConfig, by_ref, by_ref)]
+struct Config {
+    // Field attributes may still target `PartialConfig`, and mistakes in them are reported too.
+    #[partial(PartialConfig, derive(Debug))]
+    #[strategy(add)]
+    name: String,
+    #[partial(PartialConfig)]
+    version: u32,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+#![deny(unused_must_use)]
+
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, diff, strip)]
+struct Config {
+    name: String,
+    port: u16,
+}
+
+fn main() {
+    let config = Config {
+        name: "server".to_string(),
+        port: 80,
+    };
+
+    // `merge()` returns the merged struct, so ignoring it discards the merge.
+    config.merge(PartialConfig::default());
+
+    let old = Config {
+        name: "server".to_string(),
+        port: 80,
+    };
+
+    let new = Config {
+        name: "proxy".to_string(),
+        port: 80,
+    };
+
+    PartialConfig::diff(&old, &new);
+    new.strip(&PartialConfig::default());
+}
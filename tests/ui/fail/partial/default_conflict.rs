@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// Both of these implement `Default` for `PartialConfig`.
+#[partial(PartialConfig, default, derive(Clone, Default))]
+struct Config {
+    name: String,
+}
+
+fn main() {}
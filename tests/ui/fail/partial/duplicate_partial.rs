@@ -1,8 +1,8 @@
 use mergeme::Merge;
 
 #[derive(Merge)]
-#[partial(PartialConfig1)]
-#[partial(PartialConfig2)]
+#[partial(PartialConfig)]
+#[partial(PartialConfig, default)]
 struct Config {
     name: String,
     dependencies: Vec<String>,
@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone)]
+#[partial(PartialConfig, strip)]
+// Both partial structs would generate a `Config::strip()` method.
+#[partial(PartialConfigCli, strip)]
+struct Config {
+    name: String,
+}
+
+fn main() {}
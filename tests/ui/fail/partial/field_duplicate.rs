@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    #[partial(doc(hidden))]
+    #[partial(doc(hidden))]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+#[partial(PartialConfigFile)]
+struct Config {
+    // Untargeted attributes already apply to `PartialConfigFile`.
+    #[partial(doc(hidden))]
+    #[partial(PartialConfigFile, doc(hidden))]
+    name: String,
+}
+
+fn main() {}
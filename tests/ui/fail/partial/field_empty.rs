@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // At least one attribute or option is required.
+    #[partial()]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+#[partial(PartialConfigFile)]
+struct Config {
+    // The attributes to apply to `PartialConfigFile` are missing.
+    #[partial(PartialConfigFile)]
+    name: String,
+}
+
+fn main() {}
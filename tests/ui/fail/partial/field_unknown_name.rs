@@ -0,0 +1,11 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // `PartialConfigFile` is not generated, so this would be treated as an attribute.
+    #[partial(PartialConfigFile)]
+    name: String,
+}
+
+fn main() {}
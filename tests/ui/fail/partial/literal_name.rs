@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// The name should be an identifier, not a string.
+#[partial("PartialConfig")]
+struct Config {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// The partial struct's name is missing before `derive(...)`.
+#[partial(derive(Default))]
+struct Config {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// The name should be in parenthesis, like `#[partial(PartialConfig)]`.
+#[partial = "PartialConfig"]
+struct Config {
+    name: String,
+}
+
+fn main() {}
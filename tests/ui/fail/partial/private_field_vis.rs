@@ -0,0 +1,14 @@
+mod config {
+    use mergeme::Merge;
+
+    #[derive(Merge)]
+    #[partial(PartialConfig, field_vis = "pub(self)")]
+    pub struct Config {
+        pub name: String,
+    }
+}
+
+fn main() {
+    // `field_vis` overrides the base field's visibility, so this field is private.
+    let _ = config::PartialConfig { name: None };
+}
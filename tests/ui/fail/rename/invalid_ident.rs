@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    #[partial(rename = "log-level")]
+    log_level: u8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, base_builder)]
+struct Config {
+    name: String,
+    // The builder cannot create the base struct without every field.
+    #[partial(skip)]
+    cache: Vec<String>,
+}
+
+fn main() {}
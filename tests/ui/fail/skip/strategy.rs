@@ -0,0 +1,13 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    name: String,
+    // Skipped fields are never merged, so a strategy would have no effect.
+    #[partial(skip)]
+    #[strategy(merge)]
+    cache: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // Only one strategy may be used, rather than the last one winning.
+    #[strategy(overwrite)]
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    // This also applies to strategies within the same attribute.
+    #[strategy(overwrite, merge)]
+    hosts: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use mergeme::Merge;
+
+struct Port(u16);
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    name: String,
+    #[strategy(merge)]
+    port: Port,
+}
+
+#[derive(Merge)]
+#[partial(PartialListener)]
+enum Listener {
+    Tcp {
+        #[strategy(merge)]
+        port: Port,
+    },
+}
+
+fn main() {}
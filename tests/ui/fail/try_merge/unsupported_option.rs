@@ -0,0 +1,16 @@
+use mergeme::Merge;
+
+fn add(count: &mut u8, more: u8) -> Result<(), ()> {
+    *count = count.checked_add(more).ok_or(())?;
+    Ok(())
+}
+
+#[derive(Merge)]
+// `by_ref` generates an infallible `Merge` implementation.
+#[partial(PartialCounter, error = (), by_ref)]
+struct Counter {
+    #[strategy(try_with = "add")]
+    count: u8,
+}
+
+fn main() {}
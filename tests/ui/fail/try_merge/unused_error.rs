@@ -0,0 +1,10 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// `error` is only used when a field has `#[strategy(try_with = ...)]`.
+#[partial(PartialCounter, error = ())]
+struct Counter {
+    count: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mergeme::Merge;
+use std::path::PathBuf;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    #[partial(type = Option<String>)]
+    cache_dir: PathBuf,
+}
+
+fn main() {}
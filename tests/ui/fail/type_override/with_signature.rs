@@ -0,0 +1,16 @@
+use mergeme::Merge;
+use std::path::PathBuf;
+
+fn parse_path(path: &str) -> PathBuf {
+    PathBuf::from(path)
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    // The converter must accept the partial's `String` by value.
+    #[partial(type = Option<String>, with = "parse_path")]
+    cache_dir: PathBuf,
+}
+
+fn main() {}
@@ -0,0 +1,95 @@
+use figment::{
+    Figment, Profile,
+    providers::{Format, Toml},
+};
+use mergeme::{Merge, PartialProvider};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge, Deserialize, Debug, PartialEq)]
+#[partial(PartialServer, default, derive(Deserialize, Serialize), impls(Clone, Debug, PartialEq))]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Merge, Deserialize, Debug, PartialEq)]
+#[partial(PartialConfig, default, derive(Serialize), impls(Clone))]
+struct Config {
+    name: String,
+    #[partial(flatten_option)]
+    license: Option<String>,
+    #[strategy(merge)]
+    server: PartialServer,
+}
+
+fn main() {
+    let defaults = PartialConfig {
+        name: Some("mergeme".to_string()),
+        license: Some("MIT".to_string()),
+        server: Some(PartialServer {
+            host: Some("localhost".to_string()),
+            port: Some(80),
+        }),
+    };
+
+    let overrides = PartialConfig {
+        license: Some("Apache-2.0".to_string()),
+        server: Some(PartialServer {
+            port: Some(8080),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    // Fields that are `None` in the overrides, including nested ones, must not unset the
+    // defaults.
+    let config: Config = Figment::new()
+        .merge(PartialProvider::new(defaults.clone()))
+        .merge(PartialProvider::new(overrides.clone()))
+        .extract()
+        .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "mergeme".to_string(),
+            license: Some("Apache-2.0".to_string()),
+            server: PartialServer {
+                host: Some("localhost".to_string()),
+                port: Some(8080),
+            },
+        },
+    );
+
+    // The result is the same as merging the partial structs directly.
+    let merged = defaults.clone().merge(overrides.clone());
+    let merged: Config = Figment::new()
+        .merge(PartialProvider::new(merged))
+        .extract()
+        .unwrap();
+
+    assert_eq!(merged, config);
+
+    // Partial structs compose with figment's other providers.
+    let config: Config = Figment::new()
+        .merge(PartialProvider::new(defaults.clone()))
+        .merge(Toml::string("name = \"figment\"\n[server]\nhost = \"example.com\""))
+        .merge(PartialProvider::new(overrides.clone()))
+        .extract()
+        .unwrap();
+
+    assert_eq!(config.name, "figment");
+    assert_eq!(config.server.host.as_deref(), Some("example.com"));
+    assert_eq!(config.server.port, Some(8080));
+
+    // Partial structs provided for another profile are only used when that profile is selected.
+    let figment = Figment::new()
+        .merge(PartialProvider::new(defaults))
+        .merge(PartialProvider::new(overrides).profile("release"));
+
+    let config: Config = figment.extract().unwrap();
+    assert_eq!(config.license.as_deref(), Some("MIT"));
+
+    let config: Config = figment.select(Profile::new("release")).extract().unwrap();
+    assert_eq!(config.license.as_deref(), Some("Apache-2.0"));
+}
@@ -0,0 +1,77 @@
+use mergeme::json_merge_patch;
+use serde_json::{Value, json};
+
+fn patched(mut target: Value, patch: Value) -> Value {
+    json_merge_patch(&mut target, patch);
+    target
+}
+
+fn main() {
+    // The example from section 3 of RFC 7396.
+    let document = json!({
+        "title": "Goodbye!",
+        "author": {
+            "givenName": "John",
+            "familyName": "Doe",
+        },
+        "tags": ["example", "sample"],
+        "content": "This will be unchanged",
+    });
+
+    let patch = json!({
+        "title": "Hello!",
+        "phoneNumber": "+01-123-456-7890",
+        "author": {
+            "familyName": null,
+        },
+        "tags": ["example"],
+    });
+
+    assert_eq!(
+        patched(document, patch),
+        json!({
+            "title": "Hello!",
+            "author": {
+                "givenName": "John",
+            },
+            "tags": ["example"],
+            "content": "This will be unchanged",
+            "phoneNumber": "+01-123-456-7890",
+        }),
+    );
+
+    // The test cases from appendix A of RFC 7396, as `(target, patch, result)`.
+    let cases = [
+        (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+        (json!({"a": "b"}), json!({"b": "c"}), json!({"a": "b", "b": "c"})),
+        (json!({"a": "b"}), json!({"a": null}), json!({})),
+        (json!({"a": "b", "b": "c"}), json!({"a": null}), json!({"b": "c"})),
+        (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+        (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+        (
+            json!({"a": {"b": "c"}}),
+            json!({"a": {"b": "d", "c": null}}),
+            json!({"a": {"b": "d"}}),
+        ),
+        (
+            json!({"a": [{"b": "c"}]}),
+            json!({"a": [1]}),
+            json!({"a": [1]}),
+        ),
+        (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+        (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+        (json!({"a": "foo"}), json!(null), json!(null)),
+        (json!({"a": "foo"}), json!("bar"), json!("bar")),
+        (json!({"e": null}), json!({"a": 1}), json!({"e": null, "a": 1})),
+        (json!([1, 2]), json!({"a": "b", "c": null}), json!({"a": "b"})),
+        (
+            json!({}),
+            json!({"a": {"bb": {"ccc": null}}}),
+            json!({"a": {"bb": {}}}),
+        ),
+    ];
+
+    for (target, patch, result) in cases {
+        assert_eq!(patched(target, patch), result);
+    }
+}
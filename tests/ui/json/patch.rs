@@ -0,0 +1,174 @@
+use mergeme::{PatchErrorKind, PatchOp, apply_json_patch};
+use serde_json::{Value, json};
+
+fn patched(mut doc: Value, ops: Value) -> Result<Value, (usize, PatchErrorKind)> {
+    let ops: Vec<PatchOp> = serde_json::from_value(ops).unwrap();
+
+    apply_json_patch(&mut doc, &ops)
+        .map(|()| doc)
+        .map_err(|error| (error.index(), error.kind()))
+}
+
+fn main() {
+    // `add`, from appendices A.1 to A.3 and A.16 of RFC 6902.
+    assert_eq!(
+        patched(
+            json!({"foo": "bar"}),
+            json!([{"op": "add", "path": "/baz", "value": "qux"}]),
+        ),
+        Ok(json!({"baz": "qux", "foo": "bar"})),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["bar", "baz"]}),
+            json!([{"op": "add", "path": "/foo/1", "value": "qux"}]),
+        ),
+        Ok(json!({"foo": ["bar", "qux", "baz"]})),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["bar"]}),
+            json!([{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]),
+        ),
+        Ok(json!({"foo": ["bar", ["abc", "def"]]})),
+    );
+
+    // `remove`, from appendices A.3 and A.4.
+    assert_eq!(
+        patched(
+            json!({"baz": "qux", "foo": "bar"}),
+            json!([{"op": "remove", "path": "/baz"}]),
+        ),
+        Ok(json!({"foo": "bar"})),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["bar", "qux", "baz"]}),
+            json!([{"op": "remove", "path": "/foo/1"}]),
+        ),
+        Ok(json!({"foo": ["bar", "baz"]})),
+    );
+
+    // `replace`, from appendix A.5.
+    assert_eq!(
+        patched(
+            json!({"baz": "qux", "foo": "bar"}),
+            json!([{"op": "replace", "path": "/baz", "value": "boo"}]),
+        ),
+        Ok(json!({"baz": "boo", "foo": "bar"})),
+    );
+
+    // `move`, from appendices A.6 and A.7.
+    assert_eq!(
+        patched(
+            json!({
+                "foo": {"bar": "baz", "waldo": "fred"},
+                "qux": {"corge": "grault"},
+            }),
+            json!([{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]),
+        ),
+        Ok(json!({
+            "foo": {"bar": "baz"},
+            "qux": {"corge": "grault", "thud": "fred"},
+        })),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["all", "grass", "cows", "eat"]}),
+            json!([{"op": "move", "from": "/foo/1", "path": "/foo/3"}]),
+        ),
+        Ok(json!({"foo": ["all", "cows", "eat", "grass"]})),
+    );
+
+    // `copy`.
+    assert_eq!(
+        patched(
+            json!({"foo": {"bar": 1}}),
+            json!([{"op": "copy", "from": "/foo", "path": "/baz"}]),
+        ),
+        Ok(json!({"foo": {"bar": 1}, "baz": {"bar": 1}})),
+    );
+
+    // `test`, from appendices A.8 and A.14, with escaped pointers.
+    assert_eq!(
+        patched(
+            json!({"baz": "qux", "foo": ["a", 2, "c"]}),
+            json!([
+                {"op": "test", "path": "/baz", "value": "qux"},
+                {"op": "test", "path": "/foo/1", "value": 2},
+            ]),
+        ),
+        Ok(json!({"baz": "qux", "foo": ["a", 2, "c"]})),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"/": 9, "~1": 10}),
+            json!([
+                {"op": "test", "path": "/~01", "value": 10},
+                {"op": "test", "path": "/~1", "value": 9},
+            ]),
+        ),
+        Ok(json!({"/": 9, "~1": 10})),
+    );
+
+    // A failed `test` leaves the document unchanged, even if earlier operations succeeded.
+    let mut doc = json!({"baz": "qux", "foo": "bar"});
+
+    let ops: Vec<PatchOp> = serde_json::from_value(json!([
+        {"op": "remove", "path": "/foo"},
+        {"op": "test", "path": "/baz", "value": "bar"},
+    ]))
+    .unwrap();
+
+    let error = apply_json_patch(&mut doc, &ops).unwrap_err();
+
+    assert_eq!(error.index(), 1);
+    assert_eq!(error.kind(), PatchErrorKind::TestFailed);
+    assert_eq!(doc, json!({"baz": "qux", "foo": "bar"}));
+
+    // Other errors, from appendices A.9, A.12, and A.13.
+    assert_eq!(
+        patched(
+            json!({"foo": "bar"}),
+            json!([{"op": "add", "path": "/baz/bat", "value": "qux"}]),
+        ),
+        Err((0, PatchErrorKind::NotFound)),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["bar", "baz"]}),
+            json!([{"op": "add", "path": "/foo/3", "value": "qux"}]),
+        ),
+        Err((0, PatchErrorKind::InvalidIndex)),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": ["bar"]}),
+            json!([{"op": "remove", "path": "/foo/01"}]),
+        ),
+        Err((0, PatchErrorKind::InvalidIndex)),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": {"bar": 1}}),
+            json!([{"op": "move", "from": "/foo", "path": "/foo/bar"}]),
+        ),
+        Err((0, PatchErrorKind::MoveIntoChild)),
+    );
+
+    assert_eq!(
+        patched(
+            json!({"foo": 1}),
+            json!([{"op": "replace", "path": "foo", "value": 2}]),
+        ),
+        Err((0, PatchErrorKind::InvalidPointer)),
+    );
+}
@@ -0,0 +1,76 @@
+use mergeme::Merge;
+
+/// A set of tags that keeps the order they were added in, ignoring duplicates.
+#[derive(Default, Debug, PartialEq)]
+struct Tags(Vec<String>);
+
+impl Extend<String> for Tags {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for tag in iter {
+            if !self.0.contains(&tag) {
+                self.0.push(tag);
+            }
+        }
+    }
+}
+
+// Merging a list of tags removes those prefixed with `-`, rather than adding them like `Extend`.
+// This would overlap with the blanket implementation for `Extend` if it were enabled.
+impl Merge<Vec<String>> for Tags {
+    fn merge_in_place(&mut self, other: Vec<String>) {
+        for tag in other {
+            match tag.strip_prefix('-') {
+                Some(removed) => self.0.retain(|tag| tag != removed),
+                None => self.extend([tag]),
+            }
+        }
+    }
+}
+
+// Without the blanket implementation, merged fields need `Merge<Tags>` to be implemented by hand.
+impl Merge<Tags> for Tags {
+    fn merge_in_place(&mut self, other: Tags) {
+        self.extend(other.0);
+    }
+}
+
+#[derive(Merge)]
+#[partial(PartialPost, default)]
+struct Post {
+    title: String,
+    #[strategy(merge)]
+    tags: Tags,
+}
+
+impl From<Vec<String>> for Tags {
+    fn from(tags: Vec<String>) -> Self {
+        let mut result = Tags::default();
+        result.extend(tags);
+        result
+    }
+}
+
+fn strings(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|tag| tag.to_string()).collect()
+}
+
+fn main() {
+    let mut tags = Tags::from(strings(&["rust", "config"]));
+
+    tags.merge_in_place(strings(&["-config", "derive", "rust"]));
+
+    assert_eq!(tags, Tags(strings(&["rust", "derive"])));
+
+    let post = Post {
+        title: "Hello".to_string(),
+        tags,
+    };
+
+    let post = post.merge(PartialPost {
+        title: Some("Goodbye".to_string()),
+        tags: Some(Tags::from(strings(&["derive", "serde"]))),
+    });
+
+    assert_eq!(post.title, "Goodbye");
+    assert_eq!(post.tags, Tags(strings(&["rust", "derive", "serde"])));
+}
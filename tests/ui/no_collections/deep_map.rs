@@ -0,0 +1,85 @@
+use std::collections::{HashMap, hash_map};
+
+use mergeme::Merge;
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialServer, default)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+/// A patch for a map of servers, where each server is merged rather than replaced.
+#[derive(Default)]
+struct ServersPatch(HashMap<String, PartialServer>);
+
+impl IntoIterator for ServersPatch {
+    type Item = (String, PartialServer);
+    type IntoIter = hash_map::IntoIter<String, PartialServer>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+// Since the patch is iterable, this would overlap with the blanket implementation for `Extend` if
+// it were enabled.
+impl Merge<ServersPatch> for HashMap<String, Server> {
+    fn merge_in_place(&mut self, other: ServersPatch) {
+        for (name, partial) in other {
+            match self.get_mut(&name) {
+                Some(server) => server.merge_in_place(partial),
+                None => {
+                    if let Ok(server) = Server::try_from(partial) {
+                        self.insert(name, server);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn server(host: &str, port: u16) -> Server {
+    Server {
+        host: host.to_string(),
+        port,
+    }
+}
+
+fn main() {
+    let mut servers = HashMap::from([("api".to_string(), server("api.internal", 80))]);
+
+    servers.merge_in_place(ServersPatch(HashMap::from([
+        (
+            "api".to_string(),
+            PartialServer {
+                port: Some(8080),
+                ..Default::default()
+            },
+        ),
+        (
+            "admin".to_string(),
+            PartialServer {
+                host: Some("admin.internal".to_string()),
+                port: Some(443),
+            },
+        ),
+        // Incomplete servers that are not in the map are ignored.
+        (
+            "metrics".to_string(),
+            PartialServer {
+                port: Some(9090),
+                ..Default::default()
+            },
+        ),
+    ])));
+
+    // The existing server keeps its host, since only its port was patched.
+    assert_eq!(servers["api"], server("api.internal", 8080));
+    assert_eq!(servers["admin"], server("admin.internal", 443));
+    assert!(!servers.contains_key("metrics"));
+
+    servers.merge_in_place(ServersPatch::default());
+
+    assert_eq!(servers.len(), 2);
+}
@@ -0,0 +1,73 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use mergeme::{
+    Merge,
+    strategy::{All, Any, Capped, Exclude, Joined, Max, MergeMap, Min, MultiMap, Sorted, Sum},
+};
+
+#[derive(Merge)]
+#[partial(PartialLimits, default)]
+struct Limits {
+    #[strategy(merge)]
+    timeout: Min<Duration>,
+    #[strategy(merge)]
+    workers: Max<u32>,
+    #[strategy(merge)]
+    retries: Sum<u32>,
+    #[strategy(merge)]
+    hosts: Sorted<Vec<String>>,
+}
+
+fn main() {
+    // Without the blanket implementation, wrappers of a single value still merge with an `Option`
+    // of the bare value and with another wrapper.
+    assert_eq!(*Min(30).merge(Some(10)).merge(Some(20)).merge(None), 10);
+    assert_eq!(*Max(1u32).merge(Some(4u32)).merge(Max(2)), 4);
+    assert_eq!(*Sum(1).merge(Some(2)).merge(Some(3)).merge(Sum(4)), 10);
+    assert!(*Any(false).merge(Some(true)).merge(None));
+    assert!(!*All(true).merge(Some(false)).merge(All(true)));
+
+    let flags: Joined<' '> = Joined::from("-C opt-level=3");
+    let flags = flags
+        .merge(Some("-C lto"))
+        .merge(Some(String::from("-C debuginfo=0")))
+        .merge(None::<&str>)
+        .merge(Joined::<' '>::from(""));
+
+    assert_eq!(*flags, "-C opt-level=3 -C lto -C debuginfo=0");
+
+    // Wrappers of collections still merge with anything iterable over their items.
+    let hosts = Sorted::new(vec![3, 1]).merge(vec![2]).merge(Some(0));
+    assert_eq!(*hosts, [0, 1, 2, 3]);
+
+    let history: Capped<Vec<u8>, 2> = Capped::new(vec![1, 2]).merge([3]);
+    assert_eq!(*history, [2, 3]);
+
+    let ports = Exclude(vec![80, 443, 8080]).merge([443]);
+    assert_eq!(*ports, [80, 8080]);
+
+    let workers = MergeMap(BTreeMap::from([("dev", Max(1))])).merge([("dev", Max(2))]);
+    assert_eq!(*workers["dev"], 2);
+
+    let tags = MultiMap(BTreeMap::<_, Vec<_>>::new()).merge([("lang", "rust")]);
+    assert_eq!(tags["lang"], ["rust"]);
+
+    let limits = Limits {
+        timeout: Min(Duration::from_secs(30)),
+        workers: Max(1),
+        retries: Sum(0),
+        hosts: Sorted::new(vec!["b".to_string()]),
+    };
+
+    let limits = limits.merge(PartialLimits {
+        timeout: Some(Min(Duration::from_secs(10))),
+        workers: Some(Max(8)),
+        retries: Some(Sum(2)),
+        hosts: Some(Sorted::new(vec!["a".to_string()])),
+    });
+
+    assert_eq!(*limits.timeout, Duration::from_secs(10));
+    assert_eq!(*limits.workers, 8);
+    assert_eq!(*limits.retries, 2);
+    assert_eq!(*limits.hosts, ["a", "b"]);
+}
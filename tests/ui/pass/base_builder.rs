@@ -0,0 +1,38 @@
+use mergeme::Merge;
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialConfig, base_builder)]
+struct Config<T> {
+    name: String,
+    data: T,
+
+    // Flattened fields are optional, since `None` is a valid value.
+    #[partial(flatten_option)]
+    description: Option<String>,
+}
+
+fn main() {
+    let config = Config::builder().name("mergeme").data(1u8).build().unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "mergeme".to_string(),
+            data: 1,
+            description: None,
+        },
+    );
+
+    let config = Config::<u8>::builder()
+        .description(Some("A derivable trait".to_string()))
+        .name("mergeme")
+        .data(2u8)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.description.as_deref(), Some("A derivable trait"));
+
+    let error = Config::<u8>::builder().build().unwrap_err();
+
+    assert_eq!(error.iter().collect::<Vec<_>>(), ["name", "data"]);
+}
@@ -0,0 +1,52 @@
+use mergeme::{
+    Merge,
+    strategy::{All, Any},
+};
+
+#[derive(Merge)]
+#[partial(PartialFlags, default)]
+struct Flags {
+    #[strategy(merge)]
+    verbose: Any,
+    #[strategy(merge)]
+    allow_network: All,
+}
+
+fn main() {
+    // Every combination of base and partial for both wrappers.
+    for (base, partial, or, and) in [
+        (false, false, false, false),
+        (false, true, true, false),
+        (true, false, true, false),
+        (true, true, true, true),
+    ] {
+        assert_eq!(*Any(base).merge(Any(partial)), or);
+        assert_eq!(*All(base).merge(All(partial)), and);
+
+        // An `Option<bool>` works as the partial as well.
+        assert_eq!(Any(base).merge(Some(partial)), Any(or));
+        assert_eq!(All(base).merge(Some(partial)), All(and));
+    }
+
+    let flags = Flags {
+        verbose: Any(false),
+        allow_network: All(true),
+    };
+
+    let flags = flags.merge(PartialFlags {
+        verbose: Some(true.into()),
+        allow_network: Some(All(false)),
+    });
+
+    // Neither flag can be changed back by a later layer.
+    let flags = flags.merge(PartialFlags {
+        verbose: Some(Any(false)),
+        allow_network: Some(All(true)),
+    });
+
+    let flags = flags.merge(PartialFlags::default());
+
+    assert!(*flags.verbose);
+    assert!(!flags.allow_network);
+    assert!(!flags.allow_network.into_inner());
+}
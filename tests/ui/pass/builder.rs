@@ -0,0 +1,26 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialPackage, default, builder)]
+struct Package {
+    // Raw identifiers are stripped from setter names.
+    r#type: String,
+
+    // Flattened fields accept the type within the `Option<T>`.
+    #[partial(flatten_option)]
+    version: Option<u32>,
+}
+
+fn main() {
+    let partial = PartialPackage::default()
+        .with_type("library".to_string())
+        .with_version(2);
+
+    assert_eq!(partial.r#type.as_deref(), Some("library"));
+    assert_eq!(partial.version, Some(2));
+
+    let mut partial = PartialPackage::default();
+    partial.set_version(3);
+
+    assert_eq!(partial.version, Some(3));
+}
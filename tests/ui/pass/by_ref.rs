@@ -0,0 +1,58 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, by_ref)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    #[partial(flatten_option)]
+    proxy: Option<String>,
+    // Skipped fields do not need to implement `Clone`, since they are not in the partial struct.
+    #[partial(skip)]
+    state: State,
+}
+
+/// Per-tenant state that cannot be cloned.
+struct State;
+
+fn tenant(name: &str) -> Config {
+    Config {
+        name: name.to_string(),
+        port: 80,
+        plugins: vec!["auth".to_string()],
+        proxy: None,
+        state: State,
+    }
+}
+
+fn main() {
+    let template = PartialConfig {
+        port: Some(8080),
+        plugins: Some(vec!["metrics".to_string()]),
+        proxy: Some("proxy.internal".to_string()),
+        ..Default::default()
+    };
+
+    let mut tenants = [tenant("alpha"), tenant("beta"), tenant("gamma")];
+
+    // The same partial struct is applied to every tenant without being consumed.
+    for config in tenants.iter_mut() {
+        config.merge_in_place(&template);
+    }
+
+    for (config, name) in tenants.iter().zip(["alpha", "beta", "gamma"]) {
+        assert_eq!(config.name, name);
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.plugins, ["auth", "metrics"]);
+        assert_eq!(config.proxy.as_deref(), Some("proxy.internal"));
+    }
+
+    // The partial struct is still usable, and can be merged by value afterward.
+    assert_eq!(template.count_set(), 3);
+
+    let config = tenant("delta").merge(template);
+
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+}
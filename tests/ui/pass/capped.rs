@@ -0,0 +1,55 @@
+use std::collections::VecDeque;
+
+use mergeme::{Merge, strategy::Capped};
+
+#[derive(Merge)]
+#[partial(PartialHistory, default)]
+struct History {
+    #[strategy(merge)]
+    recent_files: Capped<Vec<String>, 3>,
+}
+
+fn main() {
+    // Constructing a capped collection keeps its newest items.
+    let ids: Capped<Vec<u32>, 4> = Capped::new(vec![1, 2, 3, 4, 5, 6]);
+
+    assert_eq!(*ids, [3, 4, 5, 6]);
+
+    // Merging removes the oldest items from the front.
+    let ids = ids.merge([7, 8]);
+
+    assert_eq!(*ids, [5, 6, 7, 8]);
+
+    // A partial larger than the cap only keeps its own last items.
+    let ids = ids.merge(10..20);
+
+    assert_eq!(*ids, [16, 17, 18, 19]);
+
+    // Merging fewer items than the cap does not remove any.
+    let short: Capped<VecDeque<u32>, 4> = Capped::new(VecDeque::from([1]));
+    let short = short.merge([2, 3]);
+
+    assert_eq!(*short, [1, 2, 3]);
+
+    // Capped collections can be merged with each other.
+    let short = short.merge(Capped::<Vec<u32>, 2>::new(vec![4, 5, 6]));
+
+    assert_eq!(short.into_inner(), [2, 3, 5, 6]);
+
+    // A cap of zero keeps nothing.
+    let empty: Capped<Vec<u32>, 0> = Capped::new(vec![1]).merge([2]);
+
+    assert!(empty.is_empty());
+
+    let mut history = History {
+        recent_files: Capped::new(vec!["a.txt".to_string()]),
+    };
+
+    for file in ["b.txt", "c.txt", "d.txt"] {
+        history.merge_in_place(PartialHistory {
+            recent_files: Some(vec![file.to_string()].into()),
+        });
+    }
+
+    assert_eq!(*history.recent_files, ["b.txt", "c.txt", "d.txt"]);
+}
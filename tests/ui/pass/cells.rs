@@ -0,0 +1,77 @@
+use std::cell::{Cell, OnceCell, RefCell};
+
+use mergeme::Merge;
+
+#[derive(Merge, Default)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+/// Merges `partial` into the cell's value, or initializes the cell with `base` merged with
+/// `partial` if it is unset.
+fn merge_once(cell: &mut OnceCell<Config>, base: Config, partial: PartialConfig) {
+    match cell.get_mut() {
+        Some(config) => config.merge_in_place(partial),
+        None => {
+            let result = cell.set(base.merge(partial));
+            assert!(result.is_ok());
+        }
+    }
+}
+
+fn main() {
+    let mut config = RefCell::new(Config::default());
+
+    // `get_mut()` does not borrow at runtime, so this cannot panic.
+    config.get_mut().merge_in_place(PartialConfig {
+        port: Some(8080),
+        ..Default::default()
+    });
+
+    assert_eq!(config.borrow().port, 8080);
+
+    let mut config = Cell::new(Config::default());
+
+    config.get_mut().merge_in_place(PartialConfig {
+        name: Some("server".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(config.take().name, "server");
+
+    // An unset cell is initialized.
+    let mut lazy = OnceCell::new();
+
+    merge_once(
+        &mut lazy,
+        Config::default(),
+        PartialConfig {
+            plugins: Some(vec!["auth".to_string()]),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(lazy.get().unwrap().plugins, ["auth"]);
+
+    // A set cell is merged into in place, ignoring the base.
+    merge_once(
+        &mut lazy,
+        Config {
+            port: 1,
+            ..Default::default()
+        },
+        PartialConfig {
+            plugins: Some(vec!["metrics".to_string()]),
+            ..Default::default()
+        },
+    );
+
+    let config = lazy.get().unwrap();
+
+    assert_eq!(config.port, 0);
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+}
@@ -0,0 +1,64 @@
+use mergeme::Merge;
+
+#[derive(Clone)]
+struct TlsConfig {
+    port: u16,
+}
+
+// `cfg(all())` is always enabled, while `cfg(any())` is always disabled. These stand in for
+// feature flags that are on and off.
+#[derive(Merge)]
+#[partial(PartialConfig, default, builder, impls(Clone))]
+struct Config {
+    name: String,
+
+    #[cfg(all())]
+    tls: TlsConfig,
+
+    #[cfg(any())]
+    quic: QuicConfig,
+
+    #[cfg_attr(all(), strategy(merge))]
+    #[cfg_attr(all(), partial(vis = pub(crate)))]
+    dependencies: Vec<String>,
+
+    #[cfg_attr(any(), strategy(merge))]
+    features: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        tls: TlsConfig { port: 443 },
+        dependencies: vec!["syn".to_string()],
+        features: vec!["derive".to_string()],
+    };
+
+    let partial = PartialConfig {
+        tls: Some(TlsConfig { port: 8443 }),
+        dependencies: Some(vec!["quote".to_string()]),
+        features: Some(vec!["std".to_string()]),
+        ..Default::default()
+    };
+
+    let config = config.merge(partial);
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.tls.port, 8443);
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+    assert_eq!(config.features, ["std"]);
+
+    // Disabled fields are not counted as missing.
+    let error = Config::try_from(PartialConfig::default().with_name("mergeme".to_string()))
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.iter().collect::<Vec<_>>(),
+        ["tls", "dependencies", "features"],
+    );
+
+    let partial = PartialConfig::default().with_tls(TlsConfig { port: 80 });
+
+    assert_eq!(partial.clone().count_set(), 1);
+}
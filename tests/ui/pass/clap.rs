@@ -0,0 +1,70 @@
+use clap::Parser;
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(
+    PartialArgs,
+    clap,
+    derive(Parser, Debug, PartialEq),
+    command(name = "server")
+)]
+struct Config {
+    /// The address to listen on.
+    #[partial(arg(long))]
+    host: String,
+    /// The port to listen on.
+    #[partial(arg(short, long))]
+    port: u16,
+    /// Logs every request.
+    #[partial(arg(long, num_args = 0, default_missing_value = "true"))]
+    verbose: bool,
+    /// Plugins to load, in addition to those in the configuration file.
+    #[strategy(merge)]
+    #[partial(arg(long = "plugin"))]
+    plugins: Vec<String>,
+}
+
+fn main() {
+    let args = PartialArgs::try_parse_from(["server", "-p", "8080", "--plugin", "auth"]).unwrap();
+
+    // Only the flags that were passed are `Some`.
+    assert_eq!(
+        args,
+        PartialArgs {
+            host: None,
+            port: Some(8080),
+            verbose: None,
+            plugins: Some(vec!["auth".to_string()]),
+        },
+    );
+
+    let config = Config {
+        host: "localhost".to_string(),
+        port: 80,
+        verbose: false,
+        plugins: vec!["metrics".to_string()],
+    };
+
+    let config = config.merge(args);
+
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.plugins, ["metrics", "auth"]);
+
+    // Boolean flags are `Some(true)` when passed, and `None` otherwise.
+    let args = PartialArgs::try_parse_from(["server", "--verbose"]).unwrap();
+
+    assert_eq!(args.verbose, Some(true));
+    assert_eq!(args.port, None);
+
+    let config = config.merge(args);
+
+    assert!(config.verbose);
+
+    // Doc comments are copied to the partial struct, so they become each flag's help.
+    let help = PartialArgs::try_parse_from(["server", "--help"])
+        .unwrap_err()
+        .to_string();
+
+    assert!(help.contains("The port to listen on"));
+}
@@ -0,0 +1,158 @@
+use mergeme::Merge;
+use std::mem::size_of;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+#[partial(CompactConfig, compact)]
+struct Config {
+    name: String,
+    port: u16,
+    workers: u64,
+    verbose: bool,
+    // Field types do not need to implement `Default`.
+    key: [u8; 64],
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+#[derive(Merge)]
+#[partial(CompactFlags, compact)]
+struct Flags {
+    // More than 8 fields need more than one byte of the bitset.
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+    i: u8,
+    r#type: u8,
+}
+
+/// Counts how many values are dropped, to verify that each one is dropped exactly once.
+struct DropCounter<'a>(Option<&'a std::cell::Cell<usize>>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        if let Some(count) = self.0 {
+            count.set(count.get() + 1);
+        }
+    }
+}
+
+#[derive(Merge)]
+#[partial(CompactTracked, compact)]
+struct Tracked<'a> {
+    value: DropCounter<'a>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "mergeme".to_string(),
+        port: 80,
+        workers: 1,
+        verbose: false,
+        key: [0; 64],
+        dependencies: vec!["syn".to_string()],
+    }
+}
+
+fn main() {
+    // Fields without a niche are stored without `Option`'s extra space.
+    assert!(size_of::<CompactConfig>() < size_of::<PartialConfig>());
+
+    let partial = CompactConfig::default();
+
+    assert!(partial.is_empty());
+    assert!(!partial.is_complete());
+    assert_eq!(partial.count_set(), 0);
+    assert_eq!(partial.port(), None);
+
+    let mut partial = partial
+        .with_port(8080)
+        .with_dependencies(vec!["quote".to_string()]);
+
+    partial.set_verbose(true);
+    *partial.port_mut().unwrap() += 1;
+
+    assert_eq!(partial.port(), Some(&8081));
+    assert_eq!(partial.count_set(), 3);
+
+    // Merging behaves the same as with a regular partial struct.
+    let merged = config().merge(partial);
+
+    assert_eq!(merged.name, "mergeme");
+    assert_eq!(merged.port, 8081);
+    assert!(merged.verbose);
+    assert_eq!(merged.dependencies, ["syn", "quote"]);
+
+    // Compact partial structs merge with each other.
+    let combined = CompactConfig::default()
+        .with_workers(4)
+        .with_dependencies(vec!["syn".to_string()])
+        .merge(
+            CompactConfig::default()
+                .with_workers(8)
+                .with_dependencies(vec!["quote".to_string()]),
+        );
+
+    assert_eq!(combined.workers(), Some(&8));
+    assert_eq!(
+        combined.dependencies().unwrap(),
+        &["syn".to_string(), "quote".to_string()],
+    );
+
+    // `take_*()` unsets the field.
+    let mut partial = CompactConfig::from(config());
+
+    assert!(partial.is_complete());
+    assert_eq!(partial.take_name().as_deref(), Some("mergeme"));
+    assert_eq!(partial.take_name(), None);
+
+    let error = Config::try_from(partial).err().unwrap();
+
+    assert_eq!(error.to_string(), "missing field `name`");
+
+    let config = Config::try_from(CompactConfig::from(config())).unwrap();
+
+    assert_eq!(config.name, "mergeme");
+
+    // Bits beyond the first byte are tracked separately.
+    let flags = CompactFlags::default().with_a(1).with_type(2);
+
+    assert_eq!(flags.count_set(), 2);
+    assert_eq!(flags.a(), Some(&1));
+    assert_eq!(flags.i(), None);
+    assert_eq!(flags.r#type(), Some(&2));
+
+    // Values are dropped once when overwritten, taken, merged, or dropped with the partial.
+    let count = std::cell::Cell::new(0);
+
+    let mut tracked = CompactTracked::default();
+    tracked.set_value(DropCounter(Some(&count)));
+    tracked.set_value(DropCounter(Some(&count)));
+
+    assert_eq!(count.get(), 1);
+
+    drop(tracked.take_value());
+
+    assert_eq!(count.get(), 2);
+
+    let mut base = Tracked {
+        value: DropCounter(Some(&count)),
+    };
+
+    base.merge_in_place(CompactTracked::default().with_value(DropCounter(Some(&count))));
+
+    assert_eq!(count.get(), 3);
+
+    drop(CompactTracked::default().with_value(DropCounter(Some(&count))));
+
+    assert_eq!(count.get(), 4);
+
+    drop(base);
+
+    assert_eq!(count.get(), 5);
+}
@@ -0,0 +1,57 @@
+use mergeme::Merge;
+
+mod types {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Port(pub u16);
+}
+
+mod server {
+    // Field types only need to resolve where the base struct is declared.
+    use super::types::Port;
+
+    #[derive(mergeme::Merge)]
+    #[partial(CompactServer, compact)]
+    pub struct Server {
+        pub port: Port,
+        pub workers: u32,
+    }
+}
+
+fn main() {
+    use server::{CompactServer, Server};
+    use types::Port;
+
+    // Field types may be declared within a function body.
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Level(u8);
+
+    #[derive(Merge)]
+    #[partial(CompactConfig, compact)]
+    struct Config<T> {
+        level: Level,
+        port: Port,
+        extra: T,
+    }
+
+    let config = Config {
+        level: Level(1),
+        port: Port(80),
+        extra: "base",
+    };
+
+    let config = config.merge(CompactConfig::default().with_level(Level(3)).with_extra("partial"));
+
+    assert_eq!(config.level, Level(3));
+    assert_eq!(config.port, Port(80));
+    assert_eq!(config.extra, "partial");
+
+    let server = Server {
+        port: Port(80),
+        workers: 1,
+    };
+
+    let server = server.merge(CompactServer::default().with_port(Port(8080)));
+
+    assert_eq!(server.port, Port(8080));
+    assert_eq!(server.workers, 1);
+}
@@ -0,0 +1,73 @@
+use mergeme::{Merge, MergeRef};
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialBuffer, default, impls(Clone, Debug, PartialEq), builder, diff, strip, by_ref)]
+struct Buffer<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+#[derive(Merge)]
+#[partial(PartialChannels, default)]
+struct Channels<T, const N: usize = 2> {
+    #[strategy(merge)]
+    names: Vec<T>,
+    volumes: [u8; N],
+}
+
+#[derive(Merge)]
+#[partial(CompactBuffer, compact)]
+struct Compact<const N: usize> {
+    data: [u8; N],
+}
+
+fn main() {
+    let buffer = Buffer {
+        data: [0; 4],
+        len: 0,
+    };
+
+    let partial = PartialBuffer::<4>::default().with_data([1, 2, 3, 0]).with_len(3);
+    let buffer = buffer.merge(partial.clone());
+
+    assert_eq!(buffer.data, [1, 2, 3, 0]);
+    assert_eq!(buffer.len, 3);
+
+    let old = Buffer {
+        data: [0; 4],
+        len: 0,
+    };
+
+    let diff = PartialBuffer::diff(&old, &buffer);
+
+    assert_eq!(diff, partial);
+    assert!(buffer.strip(&partial).is_empty());
+
+    let merged = old.merge_ref(&partial);
+
+    assert_eq!(merged, buffer);
+
+    let full = Buffer::try_from(PartialBuffer::from(Buffer { data: [9], len: 1 })).unwrap();
+
+    assert_eq!(full.data, [9]);
+
+    // Default const arguments apply to the partial struct as well.
+    let channels: Channels<&str> = Channels {
+        names: vec!["left"],
+        volumes: [50, 50],
+    };
+
+    let partial: PartialChannels<&str> = PartialChannels {
+        names: Some(vec!["right"]),
+        volumes: Some([80, 20]),
+    };
+
+    let channels = channels.merge(partial);
+
+    assert_eq!(channels.names, ["left", "right"]);
+    assert_eq!(channels.volumes, [80, 20]);
+
+    let compact = Compact { data: [0u8; 3] }.merge(CompactBuffer::default().with_data([1, 2, 3]));
+
+    assert_eq!(compact.data, [1, 2, 3]);
+}
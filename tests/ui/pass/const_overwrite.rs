@@ -0,0 +1,56 @@
+use mergeme::const_overwrite;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Config {
+    port: u16,
+    retries: u8,
+    verbose: bool,
+}
+
+#[derive(Clone, Copy)]
+struct PartialConfig {
+    port: Option<u16>,
+    retries: Option<u8>,
+    verbose: Option<bool>,
+}
+
+const DEFAULTS: Config = Config {
+    port: 8080,
+    retries: 3,
+    verbose: false,
+};
+
+const BOARD: PartialConfig = PartialConfig {
+    port: Some(80),
+    retries: None,
+    verbose: Some(true),
+};
+
+/// Merges `partial` into `base` field by field, in a `const` context.
+const fn merge(base: Config, partial: PartialConfig) -> Config {
+    Config {
+        port: const_overwrite(base.port, partial.port),
+        retries: const_overwrite(base.retries, partial.retries),
+        verbose: const_overwrite(base.verbose, partial.verbose),
+    }
+}
+
+static CONFIG: Config = merge(DEFAULTS, BOARD);
+
+fn main() {
+    let config = const { merge(DEFAULTS, BOARD) };
+
+    assert_eq!(
+        config,
+        Config {
+            port: 80,
+            retries: 3,
+            verbose: true,
+        },
+    );
+    assert_eq!(CONFIG, config);
+
+    // It may also be used outside of `const` contexts.
+    assert_eq!(const_overwrite(1, None), 1);
+    assert_eq!(const_overwrite(1, Some(2)), 2);
+}
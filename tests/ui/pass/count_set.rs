@@ -0,0 +1,42 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    version: u32,
+    dependencies: Vec<String>,
+}
+
+#[derive(Merge)]
+#[partial(PartialUnit, default)]
+struct Unit;
+
+fn main() {
+    assert_eq!(PartialConfig::default().count_set(), 0);
+
+    let partial = PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    };
+
+    assert_eq!(partial.count_set(), 1);
+
+    let partial = PartialConfig {
+        name: Some("mergeme".to_string()),
+        dependencies: Some(Vec::new()),
+        ..Default::default()
+    };
+
+    assert_eq!(partial.count_set(), 2);
+
+    let partial = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(2),
+        dependencies: Some(Vec::new()),
+    };
+
+    assert_eq!(partial.count_set(), 3);
+
+    assert_eq!(PartialUnit::default().count_set(), 0);
+}
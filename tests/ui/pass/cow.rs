@@ -0,0 +1,42 @@
+use std::borrow::Cow;
+
+use mergeme::Merge;
+
+/// Merges `partial` into `base`, without cloning a borrowed value if `partial` is empty.
+fn merge_cow<B, P>(base: &mut Cow<'_, B>, partial: P)
+where
+    B: ToOwned + ?Sized,
+    B::Owned: Merge<std::iter::Peekable<P::IntoIter>>,
+    P: IntoIterator,
+{
+    let mut partial = partial.into_iter().peekable();
+
+    if partial.peek().is_some() {
+        base.to_mut().merge_in_place(partial);
+    }
+}
+
+fn main() {
+    let mut name = Cow::Borrowed("merge");
+
+    merge_cow(&mut name, "".chars());
+
+    assert!(matches!(name, Cow::Borrowed("merge")));
+
+    merge_cow(&mut name, "me".chars());
+
+    assert!(matches!(name, Cow::Owned(_)));
+    assert_eq!(name, "mergeme");
+
+    let plugins = ["auth".to_string()];
+    let mut merged = Cow::Borrowed(&plugins[..]);
+
+    merge_cow(&mut merged, []);
+
+    assert!(matches!(merged, Cow::Borrowed(_)));
+
+    merge_cow(&mut merged, ["metrics".to_string()]);
+
+    assert!(matches!(merged, Cow::Owned(_)));
+    assert_eq!(merged.as_ref(), ["auth", "metrics"]);
+}
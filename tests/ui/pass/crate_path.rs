@@ -0,0 +1,51 @@
+// `mergeme` is re-exported from another module, such as an internal `utils` crate.
+mod utils {
+    pub mod mergeme {
+        pub use ::mergeme::*;
+    }
+}
+
+use utils::mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, crate = "crate::utils::mergeme")]
+struct Config {
+    name: String,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+mod patch {
+    pub struct ConfigPatch {
+        pub name: Option<String>,
+    }
+}
+
+#[derive(Merge)]
+#[partial(existing = patch::ConfigPatch, crate = "crate::utils::mergeme")]
+struct PatchedConfig {
+    name: String,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        dependencies: vec!["syn".to_string()],
+    }
+    .merge(PartialConfig {
+        name: None,
+        dependencies: Some(vec!["quote".to_string()]),
+    });
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+
+    let config = PatchedConfig {
+        name: "mergeme".to_string(),
+    }
+    .merge(patch::ConfigPatch {
+        name: Some("mergeme_derive".to_string()),
+    });
+
+    assert_eq!(config.name, "mergeme_derive");
+}
@@ -0,0 +1,42 @@
+use mergeme::Merge;
+
+// Note that this does not implement `Default`.
+struct Token(u64);
+
+#[derive(Merge)]
+#[partial(PartialSession, default)]
+struct Session {
+    user: String,
+    token: Token,
+}
+
+#[derive(Merge)]
+#[partial(PartialNamedData, default)]
+struct NamedData<T> {
+    name: String,
+    data: T,
+}
+
+fn main() {
+    // `Default` is implemented without requiring `T: Default`.
+    let partial = PartialNamedData::<Token>::default();
+
+    assert!(partial.data.is_none());
+
+    let partial = PartialSession::default();
+
+    assert!(partial.user.is_none());
+    assert!(partial.token.is_none());
+
+    let session = Session {
+        user: "root".to_string(),
+        token: Token(0),
+    }
+    .merge(PartialSession {
+        token: Some(Token(1)),
+        ..Default::default()
+    });
+
+    assert_eq!(session.user, "root");
+    assert_eq!(session.token.0, 1);
+}
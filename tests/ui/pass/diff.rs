@@ -0,0 +1,69 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialConfig, diff)]
+struct Config {
+    name: String,
+    version: u32,
+    dependencies: Vec<String>,
+}
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialPackage, diff)]
+struct Package {
+    description: Option<String>,
+}
+
+#[derive(Merge, Clone, PartialEq)]
+#[partial(PartialNamedData, diff)]
+struct NamedData<T> {
+    name: String,
+    data: T,
+}
+
+fn main() {
+    let old = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    let new = Config {
+        version: 2,
+        ..old.clone()
+    };
+
+    let diff = PartialConfig::diff(&old, &new);
+
+    assert_eq!(diff.name, None);
+    assert_eq!(diff.version, Some(2));
+    assert_eq!(diff.dependencies, None);
+    assert_eq!(diff.count_set(), 1);
+
+    // Merging the difference into the old struct produces the new one.
+    assert_eq!(old.clone().merge(diff), new);
+
+    assert!(PartialConfig::diff(&old, &old).is_empty());
+
+    // Optional fields can be changed back to `None`.
+    let old = Package {
+        description: Some("A derivable trait".to_string()),
+    };
+    let new = Package { description: None };
+
+    assert_eq!(PartialPackage::diff(&old, &new).description, Some(None));
+    assert_eq!(old.clone().merge(PartialPackage::diff(&old, &new)), new);
+
+    // Generic fields only need to implement `Clone` and `PartialEq` when `diff()` is called.
+    let old = NamedData {
+        name: "data".to_string(),
+        data: 1u8,
+    };
+
+    let new = NamedData {
+        name: "data".to_string(),
+        data: 2u8,
+    };
+
+    assert_eq!(PartialNamedData::diff(&old, &new).data, Some(2));
+}
@@ -0,0 +1,44 @@
+//! The partial struct generated by `#[derive(Merge)]` should be documented.
+
+#![deny(missing_docs)]
+
+/// Configuration types.
+pub mod config {
+    use mergeme::Merge;
+
+    /// The configuration for a package.
+    #[derive(Merge)]
+    #[partial(PartialConfig, default)]
+    pub struct Config {
+        /// The name of the package.
+        pub name: String,
+
+        /// The version of the package.
+        #[partial(doc = "")]
+        #[partial(doc = "If unset, the version is not changed.")]
+        pub version: u32,
+
+        /// The old name of the package.
+        #[deprecated = "use `name` instead"]
+        pub old_name: String,
+    }
+}
+
+fn main() {
+    use config::{Config, PartialConfig};
+    use mergeme::Merge;
+
+    #[allow(deprecated)]
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        old_name: "mergme".to_string(),
+    };
+
+    let config = config.merge(PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    });
+
+    assert_eq!(config.version, 2);
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+
+use mergeme::Merge;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: String) {
+    EVENTS.with_borrow_mut(|events| events.push(event));
+}
+
+fn take_events() -> Vec<String> {
+    EVENTS.with_borrow_mut(std::mem::take)
+}
+
+struct Tracked(&'static str);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        record(format!("drop {}", self.0));
+    }
+}
+
+impl Merge<Tracked> for Tracked {
+    fn merge_in_place(&mut self, other: Tracked) {
+        record(format!("merge {} into {}", other.0, self.0));
+    }
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    first: Tracked,
+    #[strategy(merge)]
+    second: Tracked,
+    third: Tracked,
+}
+
+fn main() {
+    let mut config = Config {
+        first: Tracked("old first"),
+        second: Tracked("old second"),
+        third: Tracked("old third"),
+    };
+
+    config.merge_in_place(PartialConfig {
+        first: Some(Tracked("new first")),
+        second: Some(Tracked("new second")),
+        third: Some(Tracked("new third")),
+    });
+
+    // Each overwritten value is dropped as soon as it is replaced, before the next field is merged.
+    assert_eq!(
+        take_events(),
+        [
+            "drop old first",
+            "merge new second into old second",
+            "drop new second",
+            "drop old third",
+        ],
+    );
+
+    drop(config);
+
+    assert_eq!(take_events(), ["drop new first", "drop old second", "drop new third"]);
+}
@@ -0,0 +1,112 @@
+use mergeme::Merge;
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialBackend, derive(Debug, PartialEq))]
+enum Backend {
+    Sqlite {
+        path: String,
+        #[strategy(merge)]
+        pragmas: Vec<String>,
+    },
+    Postgres {
+        url: String,
+        pool_size: u32,
+    },
+    Memory,
+}
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialValue)]
+// `T: Merge<T>` is only required to merge the `Merged` variant.
+enum Value<T> {
+    Replaced(T, u8),
+    Merged(#[strategy(merge)] T),
+}
+
+fn sqlite() -> Backend {
+    Backend::Sqlite {
+        path: "config.db".to_string(),
+        pragmas: vec!["journal_mode = wal".to_string()],
+    }
+}
+
+fn main() {
+    // Merging the same variant recurses into its fields.
+    let backend = sqlite().merge(PartialBackend::Sqlite {
+        path: None,
+        pragmas: Some(vec!["foreign_keys = on".to_string()]),
+    });
+
+    assert_eq!(
+        backend,
+        Backend::Sqlite {
+            path: "config.db".to_string(),
+            pragmas: vec![
+                "journal_mode = wal".to_string(),
+                "foreign_keys = on".to_string(),
+            ],
+        },
+    );
+
+    // Merging a different, complete variant replaces the whole value.
+    let postgres = PartialBackend::Postgres {
+        url: Some("postgres://localhost".to_string()),
+        pool_size: Some(8),
+    };
+
+    assert!(postgres.is_complete());
+
+    let backend = sqlite().merge(postgres);
+
+    assert_eq!(
+        backend,
+        Backend::Postgres {
+            url: "postgres://localhost".to_string(),
+            pool_size: 8,
+        },
+    );
+
+    // Merging a different, incomplete variant does nothing, since the missing fields have no
+    // value.
+    let incomplete = PartialBackend::Postgres {
+        url: None,
+        pool_size: Some(16),
+    };
+
+    assert!(!incomplete.is_complete());
+    assert_eq!(sqlite().merge(incomplete), sqlite());
+
+    // Unit variants are always complete.
+    assert_eq!(sqlite().merge(PartialBackend::Memory), Backend::Memory);
+
+    // Partial enums of the same variant combine their fields, while a different variant replaces
+    // the other.
+    let combined = PartialBackend::Postgres {
+        url: Some("postgres://localhost".to_string()),
+        pool_size: None,
+    }
+    .merge(PartialBackend::Postgres {
+        url: None,
+        pool_size: Some(4),
+    });
+
+    assert!(combined.is_complete());
+    assert_eq!(
+        PartialBackend::from(Backend::Memory).merge(PartialBackend::from(sqlite())),
+        PartialBackend::from(sqlite()),
+    );
+
+    // Tuple variants and generic fields work the same way.
+    let value = Value::Merged(vec![1]).merge(PartialValue::Merged(Some(vec![2])));
+
+    assert_eq!(value, Value::Merged(vec![1, 2]));
+
+    let value = value.merge(PartialValue::Replaced(Some(vec![3]), Some(0)));
+
+    assert_eq!(value, Value::Replaced(vec![3], 0));
+
+    // Partial enums of types that cannot be merged can still be created.
+    let partial = PartialValue::<u8>::Replaced(Some(1), None);
+
+    assert!(!partial.is_complete());
+}
@@ -0,0 +1,55 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use mergeme::{Merge, strategy::Exclude};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    #[strategy(merge)]
+    plugins: Exclude<Vec<String>>,
+}
+
+fn list(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn main() {
+    let config = Config {
+        plugins: Exclude(list(&["auth", "metrics", "tracing", "metrics"])),
+    };
+
+    // Every occurrence of an excluded item is removed, and missing items are ignored.
+    let config = config.merge(PartialConfig {
+        plugins: Some(list(&["metrics", "cache"]).into()),
+    });
+
+    assert_eq!(*config.plugins, ["auth", "tracing"]);
+
+    // A layer without the field leaves the list as-is.
+    let config = config.merge(PartialConfig::default());
+
+    assert_eq!(*config.plugins, ["auth", "tracing"]);
+
+    // Sets remove each item, ignoring those that are not present.
+    let set = Exclude(BTreeSet::from([1, 2, 3])).merge([2, 4]);
+
+    assert_eq!(*set, BTreeSet::from([1, 3]));
+
+    let set = Exclude(HashSet::from(["a", "b"])).merge(vec!["b", "c"]);
+
+    assert_eq!(*set, HashSet::from(["a"]));
+
+    // Maps remove the entry of each key.
+    let map = Exclude(BTreeMap::from([("a", 1), ("b", 2)])).merge(["a", "z"]);
+
+    assert_eq!(*map, BTreeMap::from([("b", 2)]));
+
+    let map = Exclude(HashMap::from([("a", 1), ("b", 2)])).merge(BTreeSet::from(["b"]));
+
+    assert_eq!(*map, HashMap::from([("a", 1)]));
+
+    // Excluding from an empty collection does nothing.
+    let empty = Exclude(Vec::<i32>::new()).merge([1, 2]);
+
+    assert!(empty.into_inner().is_empty());
+}
@@ -0,0 +1,37 @@
+use mergeme::Merge;
+
+mod patch {
+    // This struct predates `#[derive(Merge)]` and is written by hand.
+    pub struct ConfigPatch {
+        pub name: Option<String>,
+        pub version: Option<u32>,
+        pub dependencies: Option<Vec<String>>,
+    }
+}
+
+#[derive(Merge)]
+#[partial(existing = crate::patch::ConfigPatch)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    let config = config.merge(patch::ConfigPatch {
+        name: None,
+        version: Some(2),
+        dependencies: Some(vec!["quote".to_string()]),
+    });
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.version, 2);
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+}
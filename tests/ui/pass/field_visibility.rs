@@ -0,0 +1,44 @@
+mod outer {
+    pub mod config {
+        use mergeme::Merge;
+
+        #[derive(Merge)]
+        // Every field of `PartialConfig` is public, so that it can be deserialized anywhere.
+        #[partial(PartialConfig, default, field_vis = "pub")]
+        pub struct Config {
+            pub(crate) name: String,
+
+            // Field-level visibility takes precedence over `field_vis`.
+            #[partial(vis = "pub(super)")]
+            pub(crate) version: u32,
+        }
+    }
+
+    use config::PartialConfig;
+
+    pub fn version_override() -> PartialConfig {
+        PartialConfig {
+            version: Some(2),
+            ..Default::default()
+        }
+    }
+}
+
+use mergeme::Merge;
+use outer::config::{Config, PartialConfig};
+
+fn main() {
+    // `version` is not visible here, so the struct cannot be constructed directly.
+    let mut partial = PartialConfig::default();
+    partial.name = Some("mergeme_derive".to_string());
+
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+    }
+    .merge(partial)
+    .merge(outer::version_override());
+
+    assert_eq!(config.name, "mergeme_derive");
+    assert_eq!(config.version, 2);
+}
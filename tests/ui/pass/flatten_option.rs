@@ -0,0 +1,85 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialConfig, default, strip)]
+struct Config {
+    name: String,
+
+    // This is `Option<u32>` in the partial struct, not `Option<Option<u32>>`.
+    #[partial(flatten_option)]
+    version: Option<u32>,
+
+    // This is `Option<Option<String>>` in the partial struct.
+    description: Option<String>,
+
+    #[partial(flatten_option)]
+    #[strategy(merge)]
+    dependencies: Option<Vec<String>>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: Some(1),
+        description: Some("A derivable trait".to_string()),
+        dependencies: None,
+    };
+
+    // `None` keeps the base's value.
+    let keep = config.clone().merge(PartialConfig::default());
+
+    assert_eq!(keep.version, Some(1));
+
+    // `Some(value)` sets the base's value.
+    let set = config.clone().merge(PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    });
+
+    assert_eq!(set.version, Some(2));
+
+    // Flattened fields cannot be cleared, but unflattened `Option<T>` fields can with `Some(None)`.
+    let clear = config.clone().merge(PartialConfig {
+        description: Some(None),
+        ..Default::default()
+    });
+
+    assert_eq!(clear.description, None);
+
+    // Merged fields are set if the base's field is `None`, and merged otherwise.
+    let merged = config
+        .clone()
+        .merge(PartialConfig {
+            dependencies: Some(vec!["syn".to_string()]),
+            ..Default::default()
+        })
+        .merge(PartialConfig {
+            dependencies: Some(vec!["quote".to_string()]),
+            ..Default::default()
+        });
+
+    assert_eq!(merged.dependencies, Some(vec!["syn".to_string(), "quote".to_string()]));
+
+    // Flattened fields are never missing, since `None` is a valid value.
+    let partial = PartialConfig {
+        name: Some("mergeme".to_string()),
+        description: Some(None),
+        ..Default::default()
+    };
+
+    assert!(partial.is_complete());
+
+    let converted = Config::try_from(partial).unwrap();
+
+    assert_eq!(converted.version, None);
+
+    let error = Config::try_from(PartialConfig::default()).unwrap_err();
+
+    assert_eq!(error.iter().collect::<Vec<_>>(), ["name", "description"]);
+
+    // Conversions and `strip()` also use the flattened type.
+    let partial = PartialConfig::from(config.clone());
+
+    assert_eq!(partial.version, Some(1));
+    assert_eq!(config.strip(&PartialConfig::default()).version, Some(1));
+}
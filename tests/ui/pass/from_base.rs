@@ -0,0 +1,24 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 2,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    let partial = PartialConfig::from(config);
+
+    assert_eq!(partial.name.as_deref(), Some("mergeme"));
+    assert_eq!(partial.version, Some(2));
+    assert_eq!(partial.dependencies.as_deref(), Some(&["syn".to_string()][..]));
+}
@@ -0,0 +1,60 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialNamedData, default, by_ref, report)]
+// `T: Merge<T>` is added to the implementations that merge `data`, so it need not be written.
+struct NamedData<T> {
+    name: String,
+    #[strategy(merge)]
+    data: T,
+    #[strategy(merge)]
+    #[partial(flatten_option)]
+    extra: Option<Vec<T>>,
+}
+
+#[derive(Merge)]
+#[partial(CompactWrapper, compact)]
+struct Wrapper<T> {
+    #[strategy(merge)]
+    inner: T,
+}
+
+fn main() {
+    let data = NamedData {
+        name: "numbers".to_string(),
+        data: vec![1, 2],
+        extra: None,
+    };
+
+    let partial = PartialNamedData {
+        data: Some(vec![3]),
+        ..Default::default()
+    }
+    .merge(PartialNamedData {
+        data: Some(vec![4]),
+        extra: Some(vec![vec![5]]),
+        ..Default::default()
+    });
+
+    let mut data = data.merge(&partial);
+
+    assert_eq!(data.data, [1, 2, 3, 4]);
+    assert_eq!(data.extra, Some(vec![vec![5]]));
+
+    let report = data.merge_in_place_reporting(partial);
+
+    assert_eq!(report.set().collect::<Vec<_>>(), ["data", "extra"]);
+    assert_eq!(data.data, [1, 2, 3, 4, 3, 4]);
+
+    let wrapper = Wrapper { inner: data.data }.merge(CompactWrapper::default().with_inner(vec![6]));
+
+    assert_eq!(wrapper.inner, [1, 2, 3, 4, 3, 4, 6]);
+
+    // Partial structs of types that cannot be merged can still be created.
+    let partial = PartialNamedData::<u8> {
+        data: Some(1),
+        ..Default::default()
+    };
+
+    assert_eq!(partial.count_set(), 1);
+}
@@ -0,0 +1,69 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, getters)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+    #[partial(flatten_option)]
+    license: Option<String>,
+    #[partial(rename = "type")]
+    ty: String,
+}
+
+/// Checks a partial struct before it is merged, without depending on how its fields are stored.
+fn validate(partial: &PartialConfig) -> Result<(), &'static str> {
+    if partial.name().is_some_and(str::is_empty) {
+        return Err("name cannot be empty");
+    }
+
+    if partial.port() == Some(&0) {
+        return Err("port cannot be 0");
+    }
+
+    if partial
+        .dependencies()
+        .is_some_and(|dependencies| dependencies.iter().any(String::is_empty))
+    {
+        return Err("dependencies cannot be empty");
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let mut partial = PartialConfig {
+        name: Some(String::new()),
+        port: Some(8080),
+        dependencies: Some(vec!["syn".to_string()]),
+        ..Default::default()
+    };
+
+    assert_eq!(validate(&partial), Err("name cannot be empty"));
+
+    // Mutable getters can fix the partial in place.
+    partial.name_mut().unwrap().push_str("mergeme");
+
+    assert_eq!(partial.name(), Some("mergeme"));
+    assert_eq!(partial.dependencies(), Some(&["syn".to_string()][..]));
+    assert_eq!(validate(&partial), Ok(()));
+
+    // Flattened fields return their inner value, and renamed fields use their new name.
+    assert_eq!(partial.license(), None);
+    assert_eq!(partial.r#type(), None);
+    assert!(partial.type_mut().is_none());
+
+    let config = Config {
+        name: "default".to_string(),
+        port: 80,
+        dependencies: Vec::new(),
+        license: None,
+        ty: "binary".to_string(),
+    }
+    .merge(partial);
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.port, 8080);
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use mergeme::Merge;
+
+// Note that this does not implement `Clone`, `Debug`, or `PartialEq`.
+struct Secret;
+
+#[derive(Merge)]
+#[partial(PartialShared, default, impls(Clone))]
+struct Shared<T> {
+    name: String,
+    // `Arc<T>` implements `Clone` even if `T` does not.
+    data: Arc<T>,
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, impls(Clone, Debug, PartialEq))]
+struct Config {
+    name: String,
+    #[partial(flatten_option)]
+    version: Option<u32>,
+    r#type: Vec<String>,
+}
+
+#[derive(Merge)]
+#[partial(PartialUnit, default, impls(Clone, Debug, PartialEq))]
+struct Unit {}
+
+fn main() {
+    let partial = PartialShared::<Secret> {
+        data: Some(Arc::new(Secret)),
+        ..Default::default()
+    };
+
+    let cloned = partial.clone();
+
+    assert!(Arc::ptr_eq(
+        partial.data.as_ref().unwrap(),
+        cloned.data.as_ref().unwrap(),
+    ));
+
+    let partial = PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    };
+
+    assert_eq!(partial, partial.clone());
+    assert_ne!(partial, PartialConfig::default());
+
+    // Only `Some` fields are printed.
+    assert_eq!(format!("{partial:?}"), "PartialConfig { version: 2, .. }");
+    assert_eq!(format!("{:?}", PartialConfig::default()), "PartialConfig { .. }");
+
+    let complete = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(2),
+        r#type: Some(Vec::new()),
+    };
+
+    assert_eq!(
+        format!("{complete:?}"),
+        r#"PartialConfig { name: "mergeme", version: 2, type: [] }"#,
+    );
+
+    assert_eq!(PartialUnit::default(), PartialUnit::default().clone());
+    assert_eq!(format!("{:?}", PartialUnit::default()), "PartialUnit");
+}
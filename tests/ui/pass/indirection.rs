@@ -0,0 +1,68 @@
+use mergeme::{Merge, MergeInto};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn use_defaults<T: Merge<PartialConfig>>(config: &mut T) {
+    config.merge_in_place(PartialConfig {
+        port: Some(80),
+        ..Default::default()
+    });
+}
+
+fn main() {
+    let mut config = Box::new(Config {
+        name: "server".to_string(),
+        port: 8080,
+        plugins: Vec::new(),
+    });
+
+    // Method calls dereference the box to find `Config`'s implementation.
+    config.merge_in_place(PartialConfig {
+        name: Some("proxy".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(config.name, "proxy");
+
+    use_defaults(&mut *config);
+
+    assert_eq!(config.port, 80);
+
+    // Merging through a mutable reference changes the value it points to.
+    let reference = &mut *config;
+
+    reference.merge_in_place(PartialConfig {
+        plugins: Some(vec!["auth".to_string()]),
+        ..Default::default()
+    });
+
+    PartialConfig {
+        plugins: Some(vec!["metrics".to_string()]),
+        ..Default::default()
+    }
+    .merge_into(reference);
+
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+
+    // `merge()` moves the value out of the box.
+    let config: Config = config.merge(PartialConfig {
+        port: Some(3000),
+        ..Default::default()
+    });
+
+    assert_eq!(config.port, 3000);
+
+    // Boxed collections still merge through `Extend`, rather than through the box.
+    let mut plugins = Box::new(vec!["auth".to_string()]);
+
+    plugins.merge_in_place(["tracing".to_string()]);
+
+    assert_eq!(*plugins, ["auth", "tracing"]);
+}
@@ -0,0 +1,52 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+// Traits must be derived after `Merge` to be inherited. `Default` is not inherited, since it may
+// require fields' types to implement it.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[partial(PartialConfig, inherit_derives)]
+struct Config {
+    name: String,
+    version: u32,
+}
+
+#[derive(Merge)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+// `PartialEq` and `Eq` are excluded, and `Clone` is already implemented with `impls(...)`.
+#[partial(PartialPackage, inherit_derives(exclude(PartialEq, Eq)), impls(Clone))]
+struct Package {
+    name: String,
+}
+
+// These would conflict if `PartialEq` and `Eq` were inherited.
+impl PartialEq for PartialPackage {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for PartialPackage {}
+
+fn main() {
+    let partial = PartialConfig {
+        name: None,
+        version: Some(2),
+    };
+
+    // `PartialConfig` derives `Debug`, `Clone`, and `PartialEq`.
+    assert_eq!(partial.clone(), partial);
+    assert_eq!(
+        format!("{partial:?}"),
+        "PartialConfig { name: None, version: Some(2) }",
+    );
+
+    let partial = PartialPackage {
+        name: Some("mergeme".to_string()),
+    };
+
+    assert_eq!(partial.clone(), partial);
+    assert_ne!(partial, PartialPackage { name: None });
+
+    let mut set = std::collections::HashSet::new();
+    set.insert(partial);
+}
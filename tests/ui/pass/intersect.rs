@@ -0,0 +1,44 @@
+use std::collections::{BTreeSet, HashSet};
+
+use mergeme::{Merge, strategy::Intersect};
+
+#[derive(Merge)]
+#[partial(PartialRole, default)]
+struct Role {
+    #[strategy(merge)]
+    permissions: Intersect<HashSet<String>>,
+}
+
+fn set(items: &[&str]) -> HashSet<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn main() {
+    let role = Role {
+        permissions: Intersect(set(&["read", "write", "delete"])),
+    };
+
+    // Overlapping sets narrow the base to the items in both.
+    let role = role.merge(PartialRole {
+        permissions: Some(set(&["read", "write", "execute"]).into()),
+    });
+
+    assert_eq!(*role.permissions, set(&["read", "write"]));
+
+    // A layer without the field leaves the set as-is.
+    let role = role.merge(PartialRole::default());
+
+    assert_eq!(*role.permissions, set(&["read", "write"]));
+
+    // Disjoint sets have nothing in common.
+    let disjoint = Intersect(BTreeSet::from([1, 2])).merge(Intersect(BTreeSet::from([3, 4])));
+
+    assert!(disjoint.is_empty());
+
+    // An empty set restricts the base to nothing.
+    let role = role.merge(PartialRole {
+        permissions: Some(Intersect(HashSet::new())),
+    });
+
+    assert!(role.permissions.into_inner().is_empty());
+}
@@ -0,0 +1,27 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Default, Debug, PartialEq)]
+#[partial(PartialConfig)]
+#[partial(PartialConfigCli, default)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 2,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    // `into_partial()` returns the first partial struct.
+    let partial: PartialConfig = config.clone().into_partial();
+
+    assert!(partial.is_complete());
+
+    // Merging the partial struct into a default base reproduces the original.
+    assert_eq!(Config::default().merge(partial), config);
+}
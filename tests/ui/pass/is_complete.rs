@@ -0,0 +1,58 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    version: u32,
+}
+
+#[derive(Merge)]
+#[partial(PartialUnit, default)]
+struct Unit;
+
+fn main() {
+    assert!(!PartialConfig::default().is_complete());
+
+    let partial = PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    };
+
+    // `name` is missing.
+    assert!(!partial.is_complete());
+
+    let partial = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(2),
+    };
+
+    assert!(partial.is_complete());
+    assert!(Config::try_from(partial).is_ok());
+
+    // A partial struct with no fields is both empty and complete.
+    assert!(PartialUnit::default().is_complete());
+}
+
+#[derive(Merge)]
+#[partial(PartialVersion)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+// `is_empty()` and `is_complete()` can be used in constants.
+const EMPTY: PartialVersion = PartialVersion {
+    major: None,
+    minor: None,
+};
+
+const _: () = assert!(EMPTY.is_empty() && !EMPTY.is_complete());
+
+const COMPLETE: PartialVersion = PartialVersion {
+    major: Some(1),
+    minor: Some(0),
+};
+
+const _: () = assert!(COMPLETE.is_complete() && !COMPLETE.is_empty());
+const _: () = assert!(COMPLETE.count_set() == 2);
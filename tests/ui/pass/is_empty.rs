@@ -0,0 +1,25 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    version: u32,
+}
+
+#[derive(Merge)]
+#[partial(PartialUnit, default)]
+struct Unit;
+
+fn main() {
+    assert!(PartialConfig::default().is_empty());
+
+    let partial = PartialConfig {
+        version: Some(2),
+        ..Default::default()
+    };
+
+    assert!(!partial.is_empty());
+
+    assert!(PartialUnit::default().is_empty());
+}
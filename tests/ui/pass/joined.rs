@@ -0,0 +1,57 @@
+use mergeme::{Merge, strategy::Joined};
+
+#[derive(Merge)]
+#[partial(PartialBuild, default)]
+struct Build {
+    #[strategy(merge)]
+    rustflags: Joined<' '>,
+    #[strategy(merge)]
+    features: Joined<','>,
+}
+
+fn main() {
+    // An empty base takes the partial without a leading separator.
+    assert_eq!(*Joined::<','>::default().merge(Joined::<','>::from("a")), "a");
+    assert_eq!(*Joined::<','>::default().merge(Some("a")), "a");
+
+    // An empty partial leaves the base without a trailing separator.
+    assert_eq!(*Joined::<','>::from("a").merge(Joined::<','>::default()), "a");
+    assert_eq!(*Joined::<','>::from("a").merge(Some(String::new())), "a");
+    assert_eq!(*Joined::<','>::from("a").merge(None::<&str>), "a");
+
+    // Merging two empty strings stays empty.
+    assert!(Joined::<','>::default().merge(Some("")).is_empty());
+
+    // Repeated merges insert exactly one separator between each non-empty part.
+    let tags = Joined::<','>::default()
+        .merge(Some("a"))
+        .merge(Some(""))
+        .merge(Joined::<','>::from("b"))
+        .merge(Joined::<','>::default())
+        .merge(Some("c".to_string()));
+
+    assert_eq!(tags.into_inner(), "a,b,c");
+
+    // Every item of the partial is joined in order.
+    let tags = Joined::<','>::from("a").merge(vec!["b", "", "c"]);
+    assert_eq!(*tags, "a,b,c");
+
+    let build = Build {
+        rustflags: Joined::from("-C opt-level=3"),
+        features: Joined::default(),
+    };
+
+    let build = build
+        .merge(PartialBuild {
+            rustflags: Some(Joined::from("-C lto")),
+            features: Some(Joined::from("serde")),
+        })
+        .merge(PartialBuild::default())
+        .merge(PartialBuild {
+            features: Some(Joined::from("json")),
+            ..Default::default()
+        });
+
+    assert_eq!(*build.rustflags, "-C opt-level=3 -C lto");
+    assert_eq!(*build.features, "serde,json");
+}
@@ -0,0 +1,69 @@
+use mergeme::{Merge, strategy::Latest};
+
+#[derive(Merge)]
+#[partial(PartialNode, default)]
+struct Node {
+    #[strategy(merge)]
+    leader: Latest<String>,
+}
+
+/// Returns every ordering of `items`, using Heap's algorithm.
+fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    fn permute<T: Clone>(k: usize, items: &mut [T], out: &mut Vec<Vec<T>>) {
+        if k <= 1 {
+            out.push(items.to_vec());
+            return;
+        }
+
+        for i in 0..k {
+            permute(k - 1, items, out);
+            items.swap(if k % 2 == 0 { i } else { 0 }, k - 1);
+        }
+    }
+
+    let mut out = Vec::new();
+    permute(items.len(), &mut items.to_vec(), &mut out);
+    out
+}
+
+fn main() {
+    let updates = [
+        Latest::new("a", 3),
+        Latest::new("b", 1),
+        Latest::new("c", 4),
+        Latest::new("d", 2),
+        Latest::new("e", 0),
+    ];
+
+    // Merging distinct versions in any order keeps the value with the greatest version.
+    let orderings = permutations(&updates);
+
+    assert_eq!(orderings.len(), 120);
+
+    for ordering in orderings {
+        let mut iter = ordering.into_iter();
+        let base = iter.next().unwrap();
+
+        assert_eq!(base.merge_all(iter).into_parts(), ("c", 4));
+    }
+
+    // Equal versions keep the base's value.
+    assert_eq!(*Latest::new("a", 1).merge(Latest::new("b", 1)), "a");
+    assert_eq!(*Latest::new("b", 1).merge(Latest::new("a", 1)), "b");
+
+    // Older versions never replace newer ones, even if they are merged later.
+    let node = Node {
+        leader: Latest::new("node-1".to_string(), 10),
+    };
+
+    let node = node
+        .merge(PartialNode {
+            leader: Some(Latest::new("node-3".to_string(), 12)),
+        })
+        .merge(PartialNode {
+            leader: Some(Latest::new("node-2".to_string(), 11)),
+        });
+
+    assert_eq!(node.leader.value(), "node-3");
+    assert_eq!(node.leader.version(), &12);
+}
@@ -0,0 +1,70 @@
+use mergeme::{Merge, MergeRef};
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialRef, default, impls(Clone, Debug, PartialEq), builder, diff, by_ref)]
+struct Ref<'a> {
+    #[strategy(overwrite)]
+    name: &'a str,
+    count: u32,
+}
+
+#[derive(Merge)]
+#[partial(PartialRoutes, default)]
+struct Routes<'a, 'b: 'a, T: ?Sized + 'b> {
+    host: &'a str,
+    #[strategy(merge)]
+    paths: Vec<&'b T>,
+    #[partial(flatten_option)]
+    fallback: Option<&'b T>,
+}
+
+#[derive(Merge)]
+#[partial(CompactRef, compact)]
+struct Compact<'a> {
+    name: &'a str,
+}
+
+fn main() {
+    let base = Ref {
+        name: "base",
+        count: 1,
+    };
+
+    // The partial may borrow from a value that lives shorter than the base's borrows.
+    let name = String::from("override");
+    let partial = PartialRef::default().with_name(&name);
+    let merged = base.merge(partial.clone());
+
+    assert_eq!(merged.name, "override");
+    assert_eq!(merged.count, 1);
+
+    let old = Ref {
+        name: "base",
+        count: 1,
+    };
+
+    assert_eq!(PartialRef::diff(&old, &merged), partial);
+    assert_eq!(old.merge_ref(&partial), merged);
+
+    let routes: Routes<str> = Routes {
+        host: "api.internal",
+        paths: vec!["/v1"],
+        fallback: None,
+    };
+
+    let partial = PartialRoutes {
+        host: None,
+        paths: Some(vec!["/health"]),
+        fallback: Some("/404"),
+    };
+
+    let routes = routes.merge(partial);
+
+    assert_eq!(routes.host, "api.internal");
+    assert_eq!(routes.paths, ["/v1", "/health"]);
+    assert_eq!(routes.fallback, Some("/404"));
+
+    let compact = Compact { name: "a" }.merge(CompactRef::default().with_name("b"));
+
+    assert_eq!(compact.name, "b");
+}
@@ -0,0 +1,74 @@
+use std::{
+    panic,
+    sync::{Mutex, PoisonError, RwLock},
+};
+
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name: "server".to_string(),
+        port: 8080,
+    }
+}
+
+fn main() {
+    let mut mutex = Mutex::new(config());
+
+    mutex
+        .get_mut()
+        .unwrap_or_else(PoisonError::into_inner)
+        .merge_in_place(PartialConfig {
+            port: Some(3000),
+            ..Default::default()
+        });
+
+    assert_eq!(mutex.lock().unwrap().port, 3000);
+
+    let mut lock = RwLock::new(config());
+
+    lock.get_mut()
+        .unwrap_or_else(PoisonError::into_inner)
+        .merge_in_place(PartialConfig {
+            name: Some("proxy".to_string()),
+            ..Default::default()
+        });
+
+    assert_eq!(lock.read().unwrap().name, "proxy");
+
+    // Poison the mutex by panicking while it is locked, without printing the panic.
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = panic::catch_unwind(|| {
+        let _guard = mutex.lock().unwrap();
+        panic!("poisoned");
+    });
+
+    drop(panic::take_hook());
+
+    assert!(result.is_err());
+    assert!(mutex.is_poisoned());
+    assert!(mutex.get_mut().is_err());
+
+    // Ignoring the poison still merges into the value, but does not clear the poison.
+    mutex
+        .get_mut()
+        .unwrap_or_else(PoisonError::into_inner)
+        .merge_in_place(PartialConfig {
+            port: Some(1),
+            ..Default::default()
+        });
+
+    assert!(mutex.is_poisoned());
+
+    let config = mutex.into_inner().unwrap_or_else(PoisonError::into_inner);
+
+    assert_eq!(config.port, 1);
+}
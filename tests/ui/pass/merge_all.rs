@@ -0,0 +1,84 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, impls(Clone))]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "server".to_string(),
+        port: 80,
+        plugins: vec!["auth".to_string()],
+    };
+
+    let file = PartialConfig {
+        port: Some(8080),
+        plugins: Some(vec!["metrics".to_string()]),
+        ..Default::default()
+    };
+
+    let env = PartialConfig {
+        name: Some("staging".to_string()),
+        port: Some(9000),
+        ..Default::default()
+    };
+
+    let cli = PartialConfig {
+        port: Some(3000),
+        plugins: Some(vec!["tracing".to_string()]),
+        ..Default::default()
+    };
+
+    let config = config.merge_all([file, env, cli]);
+
+    // The last partial that sets an overwritten field wins.
+    assert_eq!(config.name, "staging");
+    assert_eq!(config.port, 3000);
+
+    // Merged fields accumulate every partial's values in order.
+    assert_eq!(config.plugins, ["auth", "metrics", "tracing"]);
+
+    // Merging no partials leaves the base unchanged.
+    let config = config.merge_all(std::iter::empty());
+
+    assert_eq!(config.port, 3000);
+
+    // The order of the partials matters: reversing them changes which overwritten value wins and
+    // the order that merged values are appended in.
+    let mut config = Config {
+        name: "server".to_string(),
+        port: 80,
+        plugins: Vec::new(),
+    };
+
+    let first = PartialConfig {
+        port: Some(1),
+        plugins: Some(vec!["a".to_string()]),
+        ..Default::default()
+    };
+
+    let second = PartialConfig {
+        port: Some(2),
+        plugins: Some(vec!["b".to_string()]),
+        ..Default::default()
+    };
+
+    config.merge_all_in_place([second.clone(), first.clone()]);
+
+    assert_eq!(config.port, 1);
+    assert_eq!(config.plugins, ["b", "a"]);
+
+    config.merge_all_in_place([first, second]);
+
+    assert_eq!(config.port, 2);
+    assert_eq!(config.plugins, ["b", "a", "a", "b"]);
+
+    config.merge_all_in_place(Vec::new());
+
+    assert_eq!(config.port, 2);
+}
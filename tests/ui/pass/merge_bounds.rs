@@ -0,0 +1,58 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialNamedData, default, builder, impls(Clone, Debug, PartialEq))]
+#[partial(CompactNamedData, compact)]
+struct NamedData<T: Merge<T>> {
+    name: String,
+    #[strategy(merge)]
+    data: T,
+}
+
+#[derive(Merge)]
+#[partial(PartialBounded, default)]
+struct Bounded<T>
+where
+    T: mergeme::Merge<T> + Clone,
+{
+    data: T,
+}
+
+/// A type that can be stored in a partial struct, but cannot be merged.
+#[derive(Clone, Debug, PartialEq)]
+struct Opaque(u32);
+
+fn main() {
+    // The partial structs do not require `T: Merge<T>`, since they only store `T`.
+    let partial = PartialNamedData::default()
+        .with_name("opaque".to_string())
+        .with_data(Opaque(1));
+
+    assert_eq!(partial.count_set(), 2);
+    assert_eq!(partial.clone(), partial);
+    assert_eq!(
+        format!("{partial:?}"),
+        r#"PartialNamedData { name: "opaque", data: Opaque(1) }"#,
+    );
+
+    let compact = CompactNamedData::default().with_data(Opaque(2));
+
+    assert_eq!(compact.data(), Some(&Opaque(2)));
+
+    // Other bounds are kept.
+    let bounded = PartialBounded {
+        data: Some(Opaque(3)),
+    };
+
+    assert!(bounded.is_complete());
+
+    // Merging still requires `T: Merge<T>`.
+    let data = NamedData {
+        name: "numbers".to_string(),
+        data: vec![1, 2],
+    };
+
+    let data = data.merge(PartialNamedData::default().with_data(vec![3]));
+
+    assert_eq!(data.data, [1, 2, 3]);
+}
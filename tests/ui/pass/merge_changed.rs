@@ -0,0 +1,67 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, PartialEq)]
+#[partial(PartialConfig, default, report(compare))]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn main() {
+    let mut config = Config {
+        name: "server".to_string(),
+        port: 80,
+        plugins: Vec::new(),
+    };
+
+    // A partial with every field `None` never changes the base.
+    assert!(!config.merge_changed(PartialConfig::default()));
+
+    // Neither does overwriting a field with an equal value.
+    assert!(!config.merge_changed(PartialConfig {
+        port: Some(80),
+        ..Default::default()
+    }));
+
+    assert!(config.merge_changed(PartialConfig {
+        port: Some(8080),
+        ..Default::default()
+    }));
+
+    // Merging an empty collection leaves the field as-is.
+    assert!(!config.merge_changed(PartialConfig {
+        plugins: Some(Vec::new()),
+        ..Default::default()
+    }));
+
+    assert!(config.merge_changed(PartialConfig {
+        plugins: Some(vec!["auth".to_string()]),
+        ..Default::default()
+    }));
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.plugins, ["auth"]);
+
+    // The report compares each field as it is merged, without cloning the base.
+    let report = config.merge_in_place_reporting(PartialConfig::default());
+
+    assert!(!report.has_changed());
+
+    let report = config.merge_in_place_reporting(PartialConfig {
+        name: Some("server".to_string()),
+        ..Default::default()
+    });
+
+    assert!(!report.is_empty());
+    assert!(!report.has_changed());
+
+    let report = config.merge_in_place_reporting(PartialConfig {
+        name: Some("proxy".to_string()),
+        ..Default::default()
+    });
+
+    assert!(report.has_changed());
+    assert_eq!(config.name, "proxy");
+}
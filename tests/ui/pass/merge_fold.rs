@@ -0,0 +1,61 @@
+use mergeme::{Merge, MergeIteratorExt};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn base() -> Config {
+    Config {
+        name: "server".to_string(),
+        port: 80,
+        plugins: Vec::new(),
+    }
+}
+
+fn main() {
+    let layers = vec![
+        PartialConfig {
+            port: Some(8080),
+            plugins: Some(vec!["auth".to_string()]),
+            ..Default::default()
+        },
+        PartialConfig::default(),
+        PartialConfig {
+            name: Some("proxy".to_string()),
+            port: Some(3000),
+            ..Default::default()
+        },
+        PartialConfig {
+            plugins: Some(vec!["metrics".to_string()]),
+            ..Default::default()
+        },
+    ];
+
+    // Only layers that set the port are folded into the base, in order.
+    let config = layers
+        .into_iter()
+        .filter(|layer| layer.port.is_some())
+        .merge_fold(base());
+
+    assert_eq!(config.name, "proxy");
+    assert_eq!(config.port, 3000);
+    assert_eq!(config.plugins, ["auth"]);
+
+    // Folding an empty iterator returns the base unchanged.
+    let config = std::iter::empty::<PartialConfig>().merge_fold(base());
+
+    assert_eq!(config.port, 80);
+
+    // Any item that the base can be merged with works, such as the items of a collection.
+    let plugins = ["tracing", "cache"]
+        .into_iter()
+        .map(|plugin| [plugin.to_string()])
+        .merge_fold(vec!["auth".to_string()]);
+
+    assert_eq!(plugins, ["auth", "tracing", "cache"]);
+}
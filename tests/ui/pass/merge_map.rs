@@ -0,0 +1,84 @@
+use std::collections::{BTreeMap, HashMap};
+
+use mergeme::{Merge, strategy::MergeMap};
+
+#[derive(Merge)]
+#[partial(PartialListener, default, impls(Clone, Debug, PartialEq))]
+struct Listener {
+    port: u16,
+    #[strategy(merge)]
+    hosts: Vec<String>,
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    #[strategy(merge)]
+    listeners: MergeMap<BTreeMap<String, PartialListener>>,
+}
+
+fn main() {
+    // Values of colliding keys are merged, while new keys are inserted.
+    let limits = MergeMap(HashMap::from([("requests", vec![1]), ("bytes", vec![2])]));
+    let limits = limits.merge([("requests", vec![3]), ("connections", vec![4])]);
+
+    assert_eq!(limits.len(), 3);
+    assert_eq!(limits["requests"], [1, 3]);
+    assert_eq!(limits["bytes"], [2]);
+    assert_eq!(limits["connections"], [4]);
+
+    // An empty partial leaves the map as-is.
+    let limits = limits.merge(HashMap::<_, Vec<i32>>::new());
+
+    assert_eq!(limits.len(), 3);
+
+    // Maps can be merged with each other, even if they are different types.
+    let limits = limits.merge(MergeMap(BTreeMap::from([("bytes", vec![5])])));
+
+    assert_eq!(limits["bytes"], [2, 5]);
+
+    let config = Config {
+        listeners: MergeMap(BTreeMap::from([(
+            "http".to_string(),
+            PartialListener {
+                port: Some(80),
+                hosts: Some(vec!["example.com".to_string()]),
+            },
+        )])),
+    };
+
+    let https = PartialListener {
+        port: Some(443),
+        ..Default::default()
+    };
+
+    let config = config
+        .merge(PartialConfig {
+            listeners: Some(MergeMap(BTreeMap::from([
+                (
+                    "http".to_string(),
+                    PartialListener {
+                        hosts: Some(vec!["example.org".to_string()]),
+                        ..Default::default()
+                    },
+                ),
+                ("https".to_string(), https.clone()),
+            ]))),
+        })
+        .merge(PartialConfig::default());
+
+    // `http` is merged field by field, rather than replaced.
+    assert_eq!(
+        config.listeners["http"],
+        PartialListener {
+            port: Some(80),
+            hosts: Some(vec!["example.com".to_string(), "example.org".to_string()]),
+        },
+    );
+    assert_eq!(config.listeners["https"], https);
+
+    let http = Listener::try_from(config.listeners.into_inner().remove("http").unwrap()).unwrap();
+
+    assert_eq!(http.port, 80);
+    assert_eq!(http.hosts, ["example.com", "example.org"]);
+}
@@ -0,0 +1,49 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let file = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(1),
+        dependencies: Some(vec!["syn".to_string()]),
+    };
+
+    let cli = PartialConfig {
+        version: Some(2),
+        dependencies: Some(vec!["quote".to_string()]),
+        ..Default::default()
+    };
+
+    let combined = file.merge(cli);
+
+    // `name` is not overwritten, because it is `None` in the later partial.
+    assert_eq!(combined.name.as_deref(), Some("mergeme"));
+
+    // `version` takes the later value.
+    assert_eq!(combined.version, Some(2));
+
+    // `dependencies` is combined.
+    assert_eq!(
+        combined.dependencies.as_deref(),
+        Some(&["syn".to_string(), "quote".to_string()][..]),
+    );
+
+    // Merging into an empty partial takes the value as-is.
+    let combined = PartialConfig::default().merge(PartialConfig {
+        dependencies: Some(vec!["proc-macro2".to_string()]),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        combined.dependencies.as_deref(),
+        Some(&["proc-macro2".to_string()][..]),
+    );
+}
@@ -0,0 +1,55 @@
+//! Merging two partial structs then merging the result into the base must be the same as merging
+//! each partial struct into the base in order.
+
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialConfig, derive(Clone, Debug))]
+struct Config {
+    name: String,
+    #[partial(flatten_option)]
+    version: Option<u32>,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+/// Returns every combination of set and unset fields, using `seed` to distinguish values.
+fn partials(seed: u32) -> Vec<PartialConfig> {
+    let mut partials = Vec::new();
+
+    for mask in 0..8 {
+        partials.push(PartialConfig {
+            name: (mask & 1 != 0).then(|| format!("name-{seed}")),
+            version: (mask & 2 != 0).then_some(seed),
+            dependencies: (mask & 4 != 0).then(|| vec![format!("dependency-{seed}")]),
+        });
+    }
+
+    partials
+}
+
+fn main() {
+    let bases = [
+        Config {
+            name: "mergeme".to_string(),
+            version: Some(0),
+            dependencies: vec!["syn".to_string()],
+        },
+        Config {
+            name: String::new(),
+            version: None,
+            dependencies: Vec::new(),
+        },
+    ];
+
+    for base in bases {
+        for first in partials(1) {
+            for second in partials(2) {
+                let in_order = base.clone().merge(first.clone()).merge(second.clone());
+                let stacked = base.clone().merge(first.clone().merge(second.clone()));
+
+                assert_eq!(in_order, stacked, "{first:?} then {second:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use mergeme::{Merge, MergeRef};
+
+#[derive(Merge)]
+#[partial(PartialServer, default, by_ref)]
+struct Server<T> {
+    host: String,
+    port: u16,
+    #[strategy(merge)]
+    routes: Vec<T>,
+}
+
+fn main() {
+    // A cached set of defaults that is applied to every server without being consumed.
+    let defaults = PartialServer {
+        port: Some(8080),
+        routes: Some(vec!["/health"]),
+        ..Default::default()
+    };
+
+    let mut api = Server {
+        host: "api.internal".to_string(),
+        port: 80,
+        routes: vec!["/v1"],
+    };
+
+    let admin = Server {
+        host: "admin.internal".to_string(),
+        port: 443,
+        routes: Vec::new(),
+    };
+
+    api.merge_ref_in_place(&defaults);
+    let admin = admin.merge_ref(&defaults);
+
+    assert_eq!(api.host, "api.internal");
+    assert_eq!(api.port, 8080);
+    assert_eq!(api.routes, ["/v1", "/health"]);
+
+    assert_eq!(admin.host, "admin.internal");
+    assert_eq!(admin.port, 8080);
+    assert_eq!(admin.routes, ["/health"]);
+
+    // The defaults are still usable, and can be merged by value afterward.
+    let api = api.merge(defaults);
+
+    assert_eq!(api.routes, ["/v1", "/health", "/health"]);
+
+    // Collections clone the items of a borrowed, possibly unsized, partial.
+    let tags = vec!["prod".to_string(), "eu".to_string()];
+
+    let a = vec!["api".to_string()].merge_ref(tags.as_slice());
+    let b = VecDeque::new().merge_ref(&tags[..]);
+
+    assert_eq!(a, ["api", "prod", "eu"]);
+    assert_eq!(b, ["prod", "eu"]);
+    assert_eq!(tags.len(), 2);
+
+    let limits = BTreeMap::from([("requests", 100)]);
+
+    let a = BTreeMap::from([("requests", 10), ("bytes", 1024)]).merge_ref(&limits);
+    let b = BTreeMap::new().merge_ref(&limits);
+
+    assert_eq!(a, BTreeMap::from([("requests", 100), ("bytes", 1024)]));
+    assert_eq!(b, limits);
+
+    let mut greeting = String::from("Hello");
+    greeting.merge_ref_in_place(", world");
+
+    assert_eq!(greeting, "Hello, world");
+}
@@ -0,0 +1,125 @@
+use mergeme::{Merge, MergeStack};
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialConfig, default, impls(Clone, Debug, PartialEq))]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn main() {
+    let defaults = Config {
+        name: "mergeme".to_string(),
+        port: 80,
+        plugins: vec!["core".to_string()],
+    };
+
+    let file = PartialConfig {
+        name: Some("file".to_string()),
+        port: Some(8080),
+        plugins: Some(vec!["file".to_string()]),
+    };
+
+    let env = PartialConfig {
+        port: Some(3000),
+        plugins: Some(vec!["env".to_string()]),
+        ..Default::default()
+    };
+
+    let args = PartialConfig {
+        name: Some("args".to_string()),
+        ..Default::default()
+    };
+
+    // An empty stack leaves the base unchanged.
+    let stack: MergeStack<Config, PartialConfig> = MergeStack::new();
+    assert!(stack.is_empty());
+    assert_eq!(stack.resolve(defaults.clone()), defaults);
+
+    // Later layers take precedence over earlier ones.
+    let mut stack = MergeStack::new();
+    stack.push(file.clone());
+    stack.push(env.clone());
+    stack.push(args.clone());
+
+    assert_eq!(stack.len(), 3);
+    assert_eq!(stack.layers(), [file.clone(), env.clone(), args.clone()]);
+
+    let config = stack.clone().resolve(defaults.clone());
+
+    assert_eq!(
+        config,
+        Config {
+            name: "args".to_string(),
+            port: 3000,
+            plugins: vec!["core".to_string(), "file".to_string(), "env".to_string()],
+        },
+    );
+
+    // Resolving is the same as merging every layer in order.
+    assert_eq!(
+        config,
+        defaults
+            .clone()
+            .merge(file.clone())
+            .merge(env.clone())
+            .merge(args.clone()),
+    );
+
+    let mut in_place = defaults.clone();
+    stack.resolve_in_place(&mut in_place);
+    assert_eq!(in_place, config);
+
+    // Reversing the layers reverses their precedence.
+    let stack: MergeStack<Config, PartialConfig> = [args.clone(), env.clone(), file.clone()]
+        .into_iter()
+        .collect();
+
+    let config = stack.resolve(defaults.clone());
+    assert_eq!(config.name, "file");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.plugins, ["core", "env", "file"]);
+
+    // A layer inserted in the middle takes precedence over the layers before it, but not the
+    // layers after it.
+    let override_port = PartialConfig {
+        name: Some("override".to_string()),
+        port: Some(9000),
+        ..Default::default()
+    };
+
+    let mut stack = MergeStack::from(vec![file.clone(), env.clone(), args.clone()]);
+    stack.insert(1, override_port.clone());
+
+    assert_eq!(
+        stack.layers(),
+        [file.clone(), override_port.clone(), env.clone(), args.clone()],
+    );
+
+    let config = stack.clone().resolve(defaults.clone());
+    assert_eq!(config.name, "args");
+    assert_eq!(config.port, 3000);
+
+    // Inserting at the start gives the lowest precedence, and at the end the highest.
+    let mut low = stack.clone();
+    low.insert(0, override_port.clone());
+    assert_eq!(low.resolve(defaults.clone()).port, 3000);
+
+    let mut high = stack.clone();
+    high.insert(high.len(), override_port.clone());
+    let config = high.resolve(defaults.clone());
+    assert_eq!(config.name, "override");
+    assert_eq!(config.port, 9000);
+
+    // Removing a layer restores the precedence of the others.
+    let mut stack = stack;
+    assert_eq!(stack.remove(2), env);
+    assert_eq!(stack.clone().resolve(defaults.clone()).port, 9000);
+
+    // Layers may be edited before resolving.
+    stack.layers_mut()[0].port = Some(1);
+    stack.layers_mut()[1].port = None;
+    assert_eq!(stack.resolve(defaults).port, 1);
+}
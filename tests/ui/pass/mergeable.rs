@@ -0,0 +1,61 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, mergeable)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    #[partial(flatten_option)]
+    proxy: Option<String>,
+}
+
+fn main() {
+    let defaults = PartialConfig {
+        port: Some(80),
+        plugins: Some(vec!["auth".to_string()]),
+        ..Default::default()
+    };
+
+    // `PartialPartialConfig` has the same fields as `PartialConfig`.
+    let system = PartialPartialConfig {
+        name: None,
+        port: Some(8080),
+        plugins: Some(vec!["metrics".to_string()]),
+        proxy: None,
+    };
+
+    let user = PartialPartialConfig {
+        name: Some("server".to_string()),
+        port: None,
+        plugins: Some(vec!["tracing".to_string()]),
+        proxy: Some("proxy.internal".to_string()),
+    };
+
+    // Layers of overrides can be combined before being merged into the partial struct.
+    let overrides = system.merge(user);
+
+    assert_eq!(overrides.port, Some(8080));
+    assert_eq!(overrides.plugins.as_deref(), Some(&["metrics".to_string(), "tracing".to_string()][..]));
+
+    let partial = defaults.merge(overrides);
+
+    assert_eq!(partial.name.as_deref(), Some("server"));
+    assert_eq!(partial.port, Some(8080));
+    assert_eq!(
+        partial.plugins.as_deref(),
+        Some(&["auth".to_string(), "metrics".to_string(), "tracing".to_string()][..]),
+    );
+    assert_eq!(partial.proxy.as_deref(), Some("proxy.internal"));
+
+    // A `None` in the nested partial struct leaves the partial struct's field as-is.
+    let partial = partial.merge(PartialPartialConfig::from(PartialConfig::default()));
+
+    assert_eq!(partial.port, Some(8080));
+
+    let config = Config::try_from(partial).unwrap();
+
+    assert_eq!(config.name, "server");
+    assert_eq!(config.plugins, ["auth", "metrics", "tracing"]);
+}
@@ -0,0 +1,97 @@
+use mergeme::{Merge, Mergeable};
+
+#[derive(Merge)]
+// `Mergeable::Partial` is the first partial struct.
+#[partial(PartialConfig, default)]
+#[partial(ConfigPatch, default)]
+struct Config {
+    name: String,
+    port: u16,
+}
+
+#[derive(Merge)]
+#[partial(PartialList, default)]
+struct List<T> {
+    #[strategy(merge)]
+    items: Vec<T>,
+}
+
+#[derive(Merge)]
+#[partial(CompactLimits, compact)]
+struct Limits {
+    requests: u32,
+}
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialBackend)]
+enum Backend {
+    Sqlite { path: String },
+    Memory,
+}
+
+/// Merges every layer into `base`, without knowing the name of its partial struct.
+fn load<T: Mergeable>(base: T, layers: Vec<T::Partial>) -> T {
+    base.merge_all(layers)
+}
+
+/// Returns true if `T`'s partial type is `P`.
+fn partial_is<T: Mergeable<Partial = P>, P>() -> bool {
+    true
+}
+
+fn main() {
+    let config = Config {
+        name: "server".to_string(),
+        port: 80,
+    };
+
+    let config = load(
+        config,
+        vec![
+            PartialConfig {
+                port: Some(8080),
+                ..Default::default()
+            },
+            PartialConfig {
+                name: Some("proxy".to_string()),
+                ..Default::default()
+            },
+        ],
+    );
+
+    assert_eq!(config.name, "proxy");
+    assert_eq!(config.port, 8080);
+
+    let list = load(
+        List { items: vec![1] },
+        vec![PartialList {
+            items: Some(vec![2, 3]),
+        }],
+    );
+
+    assert_eq!(list.items, [1, 2, 3]);
+
+    let limits = load(
+        Limits { requests: 10 },
+        vec![CompactLimits::default().with_requests(100)],
+    );
+
+    assert_eq!(limits.requests, 100);
+
+    let backend = load(
+        Backend::Memory,
+        vec![PartialBackend::Sqlite {
+            path: Some("config.db".to_string()),
+        }],
+    );
+
+    assert_eq!(
+        backend,
+        Backend::Sqlite {
+            path: "config.db".to_string(),
+        },
+    );
+
+    assert!(partial_is::<Config, PartialConfig>());
+    assert!(partial_is::<List<u8>, PartialList<u8>>());
+}
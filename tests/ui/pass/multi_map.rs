@@ -0,0 +1,51 @@
+use std::collections::{BTreeMap, HashMap};
+
+use mergeme::{Merge, strategy::MultiMap};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    #[strategy(merge)]
+    listeners: MultiMap<HashMap<u16, Vec<String>>>,
+}
+
+fn main() {
+    let system = PartialConfig {
+        listeners: Some(MultiMap(HashMap::from([
+            (80, vec!["a.internal".to_string()]),
+            (443, vec!["b.internal".to_string()]),
+        ]))),
+    };
+
+    let user = PartialConfig {
+        listeners: Some(MultiMap(HashMap::from([
+            (80, vec!["c.internal".to_string(), "d.internal".to_string()]),
+            (8080, vec!["e.internal".to_string()]),
+        ]))),
+    };
+
+    let config = Config {
+        listeners: MultiMap::default(),
+    };
+
+    // Colliding keys from two layers end with their lists concatenated, in the order merged.
+    let config = config.merge(system).merge(user);
+
+    assert_eq!(config.listeners.len(), 3);
+    assert_eq!(config.listeners[&80], ["a.internal", "c.internal", "d.internal"]);
+    assert_eq!(config.listeners[&443], ["b.internal"]);
+    assert_eq!(config.listeners[&8080], ["e.internal"]);
+
+    // Single values are appended to the list of their key.
+    let mut routes = MultiMap(BTreeMap::from([("/", vec![1])]));
+
+    routes.merge_in_place([("/", 2), ("/api", 3), ("/", 4)]);
+
+    assert_eq!(routes["/"], [1, 2, 4]);
+    assert_eq!(routes["/api"], [3]);
+
+    // Merging an empty partial leaves the map as-is.
+    routes.merge_in_place(BTreeMap::<_, Vec<_>>::new());
+
+    assert_eq!(routes.into_inner(), BTreeMap::from([("/", vec![1, 2, 4]), ("/api", vec![3])]));
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use mergeme::Merge;
+use serde::Deserialize;
+use serde::de::{
+    IntoDeserializer,
+    value::{Error, MapDeserializer},
+};
+
+#[derive(Merge, Debug, PartialEq)]
+#[partial(PartialConfigFile, derive(Deserialize), serde(deny_unknown_fields))]
+#[partial(PartialConfigCli, default)]
+struct Config {
+    // `#[serde(...)]` is only applied to the partial struct that derives `Deserialize`.
+    #[partial(PartialConfigFile, serde(default = "default_version"))]
+    #[partial(PartialConfigCli, vis = pub(crate))]
+    version: u32,
+
+    // Untargeted `#[partial(...)]` attributes are applied to every partial struct.
+    #[partial(allow(dead_code))]
+    name: String,
+
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn default_version() -> Option<u32> {
+    Some(3)
+}
+
+fn main() {
+    let mut config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    // Deserialize an empty hash map, forcing the default values to be used.
+    let map: HashMap<&'static str, u32> = HashMap::new();
+    let deserializer: MapDeserializer<'_, _, Error> = map.into_deserializer();
+    let file = PartialConfigFile::deserialize(deserializer).unwrap();
+
+    assert_eq!(file.version, Some(3));
+    assert!(file.name.is_none());
+
+    config.merge_in_place(file);
+
+    assert_eq!(config.version, 3);
+
+    let cli = PartialConfigCli {
+        name: Some("mergeme_derive".to_string()),
+        dependencies: Some(vec!["quote".to_string()]),
+        ..Default::default()
+    };
+
+    config.merge_in_place(cli);
+
+    assert_eq!(
+        config,
+        Config {
+            name: "mergeme_derive".to_string(),
+            version: 3,
+            dependencies: vec!["syn".to_string(), "quote".to_string()],
+        },
+    );
+
+    // Each partial struct has its own conversions.
+    assert!(PartialConfigCli::from(config).is_complete());
+}
@@ -0,0 +1,20 @@
+//! `#[partial(Name, no_docs)]` should stop doc comments from being copied, while still allowing
+//! custom documentation.
+
+#![deny(missing_docs)]
+
+/// Configuration types.
+pub mod config {
+    use mergeme::Merge;
+
+    /// The configuration for a package.
+    #[derive(Merge)]
+    #[partial(PartialConfig, no_docs, doc = "Overrides for a package's configuration.")]
+    pub struct Config {
+        /// The name of the package.
+        #[partial(doc = "Overrides the name of the package.")]
+        pub name: String,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use mergeme::{
+    Merge,
+    strategy::{Max, Min, Sum},
+};
+
+#[derive(Merge)]
+#[partial(PartialLimits, default)]
+struct Limits {
+    #[strategy(merge)]
+    timeout: Min<Duration>,
+    #[strategy(merge)]
+    max_connections: Max<u32>,
+    #[strategy(merge)]
+    retries: Sum<u32>,
+}
+
+// A hand-written partial struct that stores the inner values rather than the wrappers.
+struct Budget {
+    deadline: Min<Duration>,
+    spent: Sum<Duration>,
+}
+
+struct PartialBudget {
+    deadline: Option<Duration>,
+    spent: Option<Duration>,
+}
+
+impl Merge<PartialBudget> for Budget {
+    fn merge_in_place(&mut self, other: PartialBudget) {
+        self.deadline.merge_in_place(other.deadline);
+        self.spent.merge_in_place(other.spent);
+    }
+}
+
+fn main() {
+    let limits = Limits {
+        timeout: Min(Duration::from_secs(30)),
+        max_connections: Max(100),
+        retries: Sum(1),
+    };
+
+    let limits = limits.merge(PartialLimits {
+        timeout: Some(Duration::from_secs(10).into()),
+        max_connections: Some(Max(50)),
+        retries: Some(Sum(2)),
+    });
+
+    assert_eq!(*limits.timeout, Duration::from_secs(10));
+    assert_eq!(*limits.max_connections, 100);
+    assert_eq!(*limits.retries, 3);
+
+    let limits = limits.merge(PartialLimits {
+        timeout: Some(Min(Duration::from_secs(60))),
+        max_connections: Some(Max(250)),
+        ..Default::default()
+    });
+
+    assert_eq!(*limits.timeout, Duration::from_secs(10));
+    assert_eq!(*limits.max_connections, 250);
+    assert_eq!(*limits.retries, 3);
+
+    let budget = Budget {
+        deadline: Min(Duration::from_secs(5)),
+        spent: Sum(Duration::from_millis(500)),
+    };
+
+    let budget = budget
+        .merge(PartialBudget {
+            deadline: Some(Duration::from_secs(2)),
+            spent: Some(Duration::from_millis(250)),
+        })
+        .merge(PartialBudget {
+            deadline: None,
+            spent: None,
+        });
+
+    assert_eq!(budget.deadline.into_inner(), Duration::from_secs(2));
+    assert_eq!(budget.spent.into_inner(), Duration::from_millis(750));
+
+    // Equal values keep the base's value.
+    assert_eq!(*Max(1).merge(Max(1)), 1);
+    assert_eq!(*Min(-1).merge([-1, 0]), -1);
+}
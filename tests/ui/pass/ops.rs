@@ -0,0 +1,78 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, ops)]
+struct Config<T> {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<T>,
+}
+
+#[derive(Merge)]
+#[partial(CompactLimits, compact, ops)]
+struct Limits {
+    requests: u32,
+    bytes: u64,
+}
+
+fn main() {
+    let config = Config {
+        name: "server".to_string(),
+        port: 80,
+        plugins: vec!["auth"],
+    };
+
+    let file = PartialConfig {
+        port: Some(8080),
+        plugins: Some(vec!["metrics"]),
+        ..Default::default()
+    };
+
+    let cli = PartialConfig {
+        name: Some("proxy".to_string()),
+        ..Default::default()
+    };
+
+    // `|` is left-associative, so partial structs are merged from left to right.
+    let mut config = config | file | cli;
+
+    assert_eq!(config.name, "proxy");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+
+    config |= PartialConfig {
+        port: Some(3000),
+        plugins: Some(vec!["tracing"]),
+        ..Default::default()
+    };
+
+    assert_eq!(config.port, 3000);
+    assert_eq!(config.plugins, ["auth", "metrics", "tracing"]);
+
+    // Partial structs can be combined with the operators as well.
+    let mut overrides = PartialConfig::default()
+        | PartialConfig {
+            port: Some(1),
+            ..Default::default()
+        };
+
+    overrides |= PartialConfig {
+        plugins: Some(vec!["cache"]),
+        ..Default::default()
+    };
+
+    // Comparisons bind tighter than `|`, so the merge must be wrapped in parentheses.
+    assert!((config | overrides).port == 1);
+
+    let mut limits = Limits {
+        requests: 10,
+        bytes: 1024,
+    };
+
+    limits |= CompactLimits::default().with_requests(100);
+
+    let limits = limits | CompactLimits::default().with_bytes(2048);
+
+    assert_eq!((limits.requests, limits.bytes), (100, 2048));
+}
@@ -0,0 +1,59 @@
+use mergeme::{Merge, Overridable};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    license: Option<String>,
+}
+
+fn main() {
+    // Each state has a different effect on an `Option<T>`.
+    let mut value = Some(1);
+
+    value.merge_in_place(Overridable::Unset);
+    assert_eq!(value, Some(1));
+
+    value.merge_in_place(Overridable::Set(2));
+    assert_eq!(value, Some(2));
+
+    value.merge_in_place(Overridable::Clear);
+    assert_eq!(value, None);
+
+    value.merge_in_place(Overridable::Set(3));
+    assert_eq!(value, Some(3));
+
+    // Overrides can be combined, where later ones take precedence unless they are unset.
+    let layered = Overridable::Set(1)
+        .merge(Overridable::Unset)
+        .merge(Overridable::Clear)
+        .merge(Overridable::Unset);
+
+    assert_eq!(layered, Overridable::Clear);
+    assert_eq!(Overridable::Unset.or(Overridable::Set(1)), Overridable::Set(1));
+
+    // Derived partial structs store optional fields as `Option<Option<T>>`.
+    let config = Config {
+        name: "mergeme".to_string(),
+        license: Some("MIT".to_string()),
+    };
+
+    let config = config.merge(PartialConfig {
+        license: Overridable::Clear.into(),
+        ..Default::default()
+    });
+
+    assert_eq!(config.license, None);
+
+    for (state, option) in [
+        (Overridable::Unset, None),
+        (Overridable::Clear, Some(None)),
+        (Overridable::Set(1), Some(Some(1))),
+    ] {
+        assert_eq!(Option::<Option<i32>>::from(state), option);
+        assert_eq!(Overridable::from(option), state);
+    }
+
+    assert_eq!(Overridable::<i32>::default(), Overridable::Unset);
+    assert_eq!(Overridable::Set(2).as_ref().map(|n| n * 2), Overridable::Set(4));
+}
@@ -0,0 +1,66 @@
+use mergeme::{Merge, partial};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+    #[partial(flatten_option)]
+    license: Option<String>,
+}
+
+mod nested {
+    use mergeme::Merge;
+
+    #[derive(Merge)]
+    #[partial(PartialPoint, default, vis = "pub")]
+    pub struct Point<T> {
+        pub x: T,
+        pub y: T,
+    }
+}
+
+fn main() {
+    // Every field is `None` when none are specified.
+    let empty = partial!(PartialConfig {});
+
+    assert!(empty.is_empty());
+
+    let one = partial!(PartialConfig { version: 3 });
+
+    assert_eq!(one.count_set(), 1);
+    assert_eq!(one.version, Some(3));
+
+    // Flattened fields are wrapped in `Some` as well, since `None` is their unset value.
+    let several = partial!(PartialConfig {
+        name: "mergeme".to_string(),
+        dependencies: vec!["syn".to_string()],
+        license: "MIT".to_string(),
+    });
+
+    assert_eq!(several.count_set(), 3);
+    assert_eq!(several.version, None);
+    assert_eq!(several.license.as_deref(), Some("MIT"));
+
+    // Every field may be specified.
+    let all = partial!(PartialConfig {
+        name: "mergeme".to_string(),
+        version: 4,
+        dependencies: Vec::new(),
+        license: "MIT".to_string(),
+    });
+
+    assert!(all.is_complete());
+
+    let config = Config::try_from(several.merge(one)).unwrap();
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.version, 3);
+
+    // Paths and generic partial structs are supported.
+    let point = partial!(nested::PartialPoint<i32> { y: 2 });
+
+    assert_eq!((point.x, point.y), (None, Some(2)));
+}
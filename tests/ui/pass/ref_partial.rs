@@ -0,0 +1,82 @@
+use mergeme::Merge;
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, ref_partial(PartialConfigRef))]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    #[partial(flatten_option)]
+    proxy: Option<String>,
+}
+
+// The base struct's lifetime is kept separate from the borrowed partial struct's.
+#[derive(Merge)]
+#[partial(PartialLabel, ref_partial(PartialLabelRef))]
+struct Label<'a, T> {
+    text: &'a str,
+    value: T,
+}
+
+fn main() {
+    let source = Config {
+        name: "source".to_string(),
+        port: 8080,
+        plugins: vec!["metrics".to_string()],
+        proxy: None,
+    };
+
+    let mut target = Config {
+        name: "target".to_string(),
+        port: 80,
+        plugins: vec!["auth".to_string()],
+        proxy: Some("proxy.internal".to_string()),
+    };
+
+    // Borrow only some fields of `source`, cloning them when they are applied.
+    let overlay = PartialConfigRef {
+        port: Some(&source.port),
+        plugins: Some(&source.plugins),
+        ..Default::default()
+    };
+
+    target.merge_in_place(overlay);
+    target.merge_in_place(overlay);
+
+    assert_eq!(target.name, "target");
+    assert_eq!(target.port, 8080);
+    assert_eq!(target.plugins, ["auth", "metrics", "metrics"]);
+    assert_eq!(target.proxy.as_deref(), Some("proxy.internal"));
+
+    // Borrowing the whole struct sets every field, although `None` stays unset when flattened.
+    let target = target.merge(PartialConfigRef::from(&source));
+
+    assert_eq!(target.name, "source");
+    assert_eq!(target.proxy.as_deref(), Some("proxy.internal"));
+
+    // Partial structs can be borrowed as well.
+    let partial = PartialConfig {
+        name: Some("partial".to_string()),
+        ..Default::default()
+    };
+
+    let target = target.merge(PartialConfigRef::from(&partial));
+
+    assert_eq!(target.name, "partial");
+    assert_eq!(source.name, "source");
+    assert_eq!(partial.name.as_deref(), Some("partial"));
+
+    let label = Label {
+        text: "count",
+        value: 1,
+    };
+
+    let label = label.merge(PartialLabelRef {
+        text: Some(&"total"),
+        value: Some(&2),
+    });
+
+    assert_eq!(label.text, "total");
+    assert_eq!(label.value, 2);
+}
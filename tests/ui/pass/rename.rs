@@ -0,0 +1,68 @@
+use mergeme::Merge;
+use serde::Deserialize;
+
+#[derive(Merge)]
+#[partial(
+    PartialPackage,
+    default,
+    builder,
+    impls(Clone, Debug, PartialEq),
+    derive(Deserialize)
+)]
+struct Package {
+    // Keywords are converted into raw identifiers, so serde sees the name `type`.
+    #[partial(rename = "type")]
+    ty: String,
+
+    #[partial(rename = "version_req")]
+    #[partial(flatten_option)]
+    version: Option<String>,
+
+    name: String,
+}
+
+fn main() {
+    let partial = PartialPackage {
+        r#type: Some("library".to_string()),
+        version_req: Some("1.0".to_string()),
+        name: None,
+    };
+
+    let package = Package {
+        ty: "binary".to_string(),
+        version: None,
+        name: "mergeme".to_string(),
+    }
+    .merge(partial.clone());
+
+    assert_eq!(package.ty, "library");
+    assert_eq!(package.version.as_deref(), Some("1.0"));
+    assert_eq!(package.name, "mergeme");
+
+    // `From` and `TryFrom` map the renamed fields back to the base fields.
+    let partial = PartialPackage::from(package);
+
+    assert_eq!(partial.r#type.as_deref(), Some("library"));
+    assert_eq!(partial.version_req.as_deref(), Some("1.0"));
+
+    let package = Package::try_from(partial).unwrap();
+
+    assert_eq!(package.ty, "library");
+
+    // Missing fields are reported by their name in the partial struct.
+    let error = Package::try_from(PartialPackage::default().with_name("mergeme".to_string()))
+        .err()
+        .unwrap();
+
+    assert_eq!(error.to_string(), "missing field `type`");
+
+    // Setters are named after the renamed field.
+    let partial = PartialPackage::default()
+        .with_type("library".to_string())
+        .with_version_req("2.0".to_string());
+
+    assert_eq!(
+        format!("{partial:?}"),
+        r#"PartialPackage { type: "library", version_req: "2.0", .. }"#,
+    );
+}
@@ -0,0 +1,43 @@
+use mergeme::{Merge, strategy::Replace};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default)]
+struct Config {
+    #[strategy(merge)]
+    plugins: Vec<String>,
+    #[strategy(merge)]
+    hosts: Replace<Vec<String>>,
+}
+
+fn main() {
+    // Merging by hand replaces the wrapped list rather than appending to it.
+    let mut hosts = Replace(vec!["localhost".to_string()]);
+
+    hosts.merge_in_place(Replace(vec!["example.com".to_string()]));
+
+    assert_eq!(*hosts, ["example.com"]);
+
+    // The wrapper derefs to the list, so it can be used like one.
+    hosts.push("example.org".to_string());
+
+    assert_eq!(hosts.len(), 2);
+    assert_eq!(hosts.into_inner(), ["example.com", "example.org"]);
+
+    let config = Config {
+        plugins: vec!["auth".to_string()],
+        hosts: vec!["localhost".to_string()].into(),
+    };
+
+    let config = config
+        .merge(PartialConfig {
+            plugins: Some(vec!["metrics".to_string()]),
+            hosts: Some(vec!["a.internal".to_string(), "b.internal".to_string()].into()),
+        })
+        .merge(PartialConfig {
+            plugins: Some(vec!["tracing".to_string()]),
+            hosts: None,
+        });
+
+    assert_eq!(config.plugins, ["auth", "metrics", "tracing"]);
+    assert_eq!(*config.hosts, ["a.internal", "b.internal"]);
+}
@@ -0,0 +1,98 @@
+use mergeme::{Merge, MergeReport};
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, report(compare))]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+    #[partial(flatten_option)]
+    license: Option<String>,
+}
+
+#[derive(Merge)]
+#[partial(PartialFlags, default, report)]
+struct Flags {
+    verbose: bool,
+    // Fields named like the generated locals must not shadow them.
+    set: bool,
+}
+
+fn base_config() -> Config {
+    Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: vec!["syn".to_string()],
+        license: None,
+    }
+}
+
+fn main() {
+    // Nothing is set, so nothing changes.
+    let mut config = base_config();
+    let report: MergeReport<4> = config.merge_in_place_reporting(PartialConfig::default());
+
+    assert!(report.is_empty());
+    assert!(report.is_compared());
+    assert_eq!(report.set().count(), 0);
+    assert_eq!(report.changed().count(), 0);
+
+    // Every field is set to its current value, so nothing changes.
+    let mut config = base_config();
+    let report = config.merge_in_place_reporting(PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(1),
+        dependencies: Some(Vec::new()),
+        license: None,
+    });
+
+    assert!(!report.is_empty());
+    assert_eq!(
+        report.set().collect::<Vec<_>>(),
+        ["name", "version", "dependencies"],
+    );
+    assert_eq!(report.changed().count(), 0);
+    assert!(report.is_set("version"));
+    assert!(!report.is_changed("version"));
+
+    // Some fields are set to new values, while others are set to the same value.
+    let mut config = base_config();
+    let report = config.merge_in_place_reporting(PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(2),
+        dependencies: Some(vec!["quote".to_string()]),
+        license: Some("MIT".to_string()),
+    });
+
+    assert_eq!(
+        report.set().collect::<Vec<_>>(),
+        ["name", "version", "dependencies", "license"],
+    );
+    assert_eq!(
+        report.changed().collect::<Vec<_>>(),
+        ["version", "dependencies", "license"],
+    );
+    assert_eq!(config.version, 2);
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+    assert_eq!(config.license.as_deref(), Some("MIT"));
+
+    // Without `compare`, every set field is reported as changed.
+    let mut flags = Flags {
+        verbose: true,
+        set: false,
+    };
+
+    let report = flags.merge_in_place_reporting(PartialFlags {
+        verbose: Some(true),
+        set: Some(true),
+    });
+
+    assert!(!report.is_compared());
+    assert_eq!(report.changed().collect::<Vec<_>>(), ["verbose", "set"]);
+    assert!(flags.set);
+
+    let report = flags.merge_in_place_reporting(PartialFlags::default());
+
+    assert!(report.is_empty());
+}
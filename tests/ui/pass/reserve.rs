@@ -0,0 +1,60 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mergeme::Merge;
+
+/// An allocator that counts how many times memory is allocated or reallocated.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Returns how many times `f` allocated or reallocated memory.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn main() {
+    let base: Vec<u32> = vec![0; 16];
+    let partial: Vec<u32> = (0..100_000).collect();
+
+    // Merging a `Vec` reserves capacity up front, rather than growing once per doubling of its
+    // capacity. The exact number of allocations is up to the standard library, so this only checks
+    // that it is far fewer than the 13 doublings from 16 to 100,016 items.
+    let count = allocations(|| {
+        let merged = base.clone().merge(partial.clone());
+        assert_eq!(merged.len(), 100_016);
+    });
+
+    // One allocation each for the clones, and one reallocation for the merge.
+    assert!(count <= 3, "{count} allocations");
+
+    // Iterators with an exact size are reserved for as well.
+    let count = allocations(|| {
+        let merged = base.clone().merge(partial.iter().map(|n| n * 2));
+        assert_eq!(merged.len(), 100_016);
+    });
+
+    // One allocation for the clone, and one reallocation for the merge.
+    assert!(count <= 2, "{count} allocations");
+}
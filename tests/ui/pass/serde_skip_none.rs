@@ -0,0 +1,71 @@
+use mergeme::Merge;
+use serde::{Deserialize, Serialize};
+
+fn is_empty_or_none(features: &Option<Vec<String>>) -> bool {
+    features.as_ref().is_none_or(Vec::is_empty)
+}
+
+#[derive(Merge)]
+#[partial(
+    PartialConfig,
+    default,
+    impls(Debug, PartialEq),
+    derive(Serialize, Deserialize),
+    serde(default),
+    serde_skip_none
+)]
+struct Config {
+    name: String,
+    version: u32,
+    #[partial(flatten_option)]
+    license: Option<String>,
+    // An explicit `skip_serializing_if` is kept instead of the generated one.
+    #[partial(serde(skip_serializing_if = "is_empty_or_none"))]
+    features: Vec<String>,
+}
+
+fn main() {
+    let partial = PartialConfig {
+        version: Some(2),
+        features: Some(Vec::new()),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&partial).unwrap();
+
+    // Only the set keys are written, and `features` uses its own condition.
+    assert_eq!(json, r#"{"version":2}"#);
+
+    let partial = PartialConfig {
+        name: Some("mergeme".to_string()),
+        license: Some("MIT".to_string()),
+        features: Some(vec!["derive".to_string()]),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&partial).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"name":"mergeme","license":"MIT","features":["derive"]}"#,
+    );
+
+    // The minimal output reads back into the same partial struct.
+    let round_trip: PartialConfig = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(round_trip, partial);
+    assert_eq!(
+        serde_json::to_string(&PartialConfig::default()).unwrap(),
+        "{}"
+    );
+
+    // Reading an override, changing it, and writing it back does not add `null`s for unset keys.
+    let mut partial: PartialConfig = serde_json::from_str(r#"{"name":"mergeme"}"#).unwrap();
+
+    partial.version = Some(3);
+
+    assert_eq!(
+        serde_json::to_string(&partial).unwrap(),
+        r#"{"name":"mergeme","version":3}"#
+    );
+}
@@ -0,0 +1,61 @@
+use std::{rc::Rc, sync::Arc};
+
+use mergeme::Merge;
+
+#[derive(Merge, Clone)]
+#[partial(PartialConfig, default)]
+struct Config {
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "server".to_string(),
+        port: 8080,
+        plugins: vec!["auth".to_string()],
+    }
+}
+
+fn main() {
+    let mut config = Arc::new(config());
+    let outstanding = Arc::clone(&config);
+
+    // The value is shared, so it is cloned before being merged into.
+    Arc::make_mut(&mut config).merge_in_place(PartialConfig {
+        port: Some(3000),
+        plugins: Some(vec!["metrics".to_string()]),
+        ..Default::default()
+    });
+
+    assert_eq!(config.port, 3000);
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+
+    assert_eq!(outstanding.port, 8080);
+    assert_eq!(outstanding.plugins, ["auth"]);
+    assert!(!Arc::ptr_eq(&config, &outstanding));
+
+    // Now that the value is unique, it is merged into without being cloned.
+    let before = Arc::as_ptr(&config);
+
+    Arc::make_mut(&mut config).merge_in_place(PartialConfig {
+        name: Some("proxy".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(config.name, "proxy");
+    assert_eq!(Arc::as_ptr(&config), before);
+
+    let mut local = Rc::new(self::config());
+    let outstanding = Rc::clone(&local);
+
+    Rc::make_mut(&mut local).merge_in_place(PartialConfig {
+        port: Some(1),
+        ..Default::default()
+    });
+
+    assert_eq!(local.port, 1);
+    assert_eq!(outstanding.port, 8080);
+}
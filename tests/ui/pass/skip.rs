@@ -0,0 +1,44 @@
+use mergeme::Merge;
+
+// Does not implement `Clone`, `Debug`, or `PartialEq`, which the partial struct's
+// implementations would otherwise require.
+struct Cache(Vec<String>);
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, builder, impls(Clone, Debug, PartialEq), report)]
+struct Config {
+    name: String,
+    #[partial(skip)]
+    cache: Cache,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let mut config = Config {
+        name: "mergeme".to_string(),
+        cache: Cache(vec!["syn".to_string()]),
+        dependencies: vec!["syn".to_string()],
+    };
+
+    // The partial struct has no `cache` field, so it can be constructed without it.
+    let partial = PartialConfig {
+        name: Some("mergeme-derive".to_string()),
+        dependencies: Some(vec!["quote".to_string()]),
+    };
+
+    assert!(partial.is_complete());
+    assert_eq!(partial.count_set(), 2);
+
+    let report = config.merge_in_place_reporting(partial.clone());
+
+    assert_eq!(report.set().collect::<Vec<_>>(), ["name", "dependencies"]);
+    assert_eq!(config.name, "mergeme-derive");
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+
+    // Merging leaves the skipped field untouched.
+    assert_eq!(config.cache.0, ["syn"]);
+
+    // `From` discards the skipped field.
+    assert_eq!(PartialConfig::from(config), partial.with_dependencies(vec!["syn".to_string(), "quote".to_string()]));
+}
@@ -0,0 +1,88 @@
+use mergeme::{Merge, strategy::Sorted};
+
+#[derive(Merge)]
+#[partial(PartialRouting, default)]
+struct Routing {
+    #[strategy(merge)]
+    targets: Sorted<Vec<Target>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Target {
+    priority: u8,
+    id: u32,
+}
+
+/// A key that only compares by priority, to check that equal items keep their order.
+#[derive(Debug)]
+struct Key(u8, &'static str);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+fn main() {
+    // Merging unsorted data into a sorted base keeps it sorted, including duplicates.
+    let ids = Sorted::new(vec![5, 1, 3]).merge(vec![4, 1, 6, 2]);
+
+    assert_eq!(*ids, [1, 1, 2, 3, 4, 5, 6]);
+
+    // Sorted collections can be merged with each other, and with an empty partial.
+    let ids = ids.merge(Sorted::new(vec![0, 7])).merge([]);
+
+    assert_eq!(*ids, [0, 1, 1, 2, 3, 4, 5, 6, 7]);
+    assert!(ids.is_sorted());
+
+    // A partial that is larger than the base is sorted as well.
+    let mut small = Sorted::from_sorted_unchecked(vec![50]);
+
+    small.merge_in_place((0..100).rev());
+
+    assert_eq!(small.len(), 101);
+    assert!(small.is_sorted());
+    assert_eq!(small[50..52], [50, 50]);
+
+    // Items that compare equal keep their order, with the base's before the partial's.
+    let keys = Sorted::new(vec![Key(2, "base"), Key(1, "base")]).merge([Key(2, "partial"), Key(0, "partial")]);
+    let keys: Vec<_> = keys.iter().map(|key| (key.0, key.1)).collect();
+
+    assert_eq!(keys, [(0, "partial"), (1, "base"), (2, "base"), (2, "partial")]);
+
+    let routing = Routing {
+        targets: Sorted::new(vec![
+            Target { priority: 2, id: 1 },
+            Target { priority: 0, id: 2 },
+        ]),
+    };
+
+    let routing = routing.merge(PartialRouting {
+        targets: Some(
+            vec![
+                Target { priority: 1, id: 3 },
+                Target { priority: 0, id: 2 },
+            ]
+            .into(),
+        ),
+    });
+
+    let priorities: Vec<_> = routing.targets.iter().map(|target| target.priority).collect();
+
+    assert_eq!(priorities, [0, 0, 1, 2]);
+    assert_eq!(routing.targets.into_inner().len(), 4);
+}
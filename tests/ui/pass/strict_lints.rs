@@ -0,0 +1,128 @@
+// The generated code should not trip any lints, even strict ones that are allowed by default.
+#![deny(
+    warnings,
+    unused_qualifications,
+    unused_results,
+    unused_lifetimes,
+    single_use_lifetimes,
+    elided_lifetimes_in_paths,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_op_in_unsafe_fn,
+    clippy::all,
+    clippy::pedantic
+)]
+
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Debug, PartialEq, Eq)]
+#[partial(
+    PartialConfig,
+    default,
+    strip,
+    impls(Clone, Debug, PartialEq),
+    builder,
+    getters,
+    base_builder,
+    by_ref,
+    report(compare),
+    track_source(Source),
+    ref_partial(PartialConfigRef),
+    mergeable
+)]
+#[partial(CompactConfig, compact)]
+struct Config {
+    proxy: Option<String>,
+    name: String,
+    port: u16,
+    #[strategy(merge)]
+    plugins: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Defaults,
+}
+
+// Every field is overwritten, so none of them are merged.
+#[derive(Merge, Clone, PartialEq, Eq)]
+#[partial(PartialDiffed, default, diff, strip)]
+#[partial(FlatDiffed)]
+struct Diffed {
+    level: u8,
+    proxy: Option<String>,
+    #[partial(skip)]
+    _state: (),
+}
+
+#[derive(Merge, Debug, PartialEq, Eq)]
+#[partial(PartialBackend)]
+enum Backend {
+    Sqlite {
+        path: String,
+        #[strategy(merge)]
+        pragmas: Vec<String>,
+    },
+    Fixed(u16),
+    Memory,
+}
+
+#[derive(Merge)]
+#[partial(PartialData, default, by_ref)]
+struct Data<T> {
+    #[strategy(merge)]
+    values: T,
+}
+
+#[derive(Merge)]
+#[partial(PartialUnit)]
+struct Unit;
+
+fn main() {
+    let config = Config {
+        name: "server".to_string(),
+        proxy: None,
+        port: 80,
+        plugins: vec!["auth".to_string()],
+    };
+
+    let mut config = config.merge(PartialConfig::default().with_port(8080));
+
+    assert_eq!(config.port, 8080);
+
+    let mut sources = ConfigSources::default();
+
+    config.merge_in_place_from(
+        PartialConfig::default().with_port(80),
+        Source::Defaults,
+        &mut sources,
+    );
+
+    assert_eq!(sources.port, Some(Source::Defaults));
+
+    let diffed = Diffed {
+        level: 1,
+        proxy: None,
+        _state: (),
+    };
+
+    let diffed = diffed.merge(PartialDiffed {
+        level: Some(2),
+        ..Default::default()
+    });
+
+    assert_eq!(diffed.level, 2);
+    assert!(diffed.proxy.is_none());
+
+    let backend = Backend::Memory.merge(PartialBackend::Fixed(Some(1)));
+
+    assert_eq!(backend, Backend::Fixed(1));
+
+    let data = Data { values: vec![1] }.merge(PartialData {
+        values: Some(vec![2]),
+    });
+
+    assert_eq!(data.values, [1, 2]);
+
+    let Unit = Unit.merge(PartialUnit {});
+}
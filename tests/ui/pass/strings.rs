@@ -0,0 +1,34 @@
+use std::borrow::Cow;
+
+use mergeme::{Merge, MergeRef};
+
+fn main() {
+    let suffix = String::from("-suffix");
+    let cow: Cow<str> = Cow::Borrowed("-cow");
+
+    // `&str`, `&String`, and `&Cow<str>` are all appended through `MergeRef<str>`.
+    let a = String::from("a")
+        .merge_ref("-str")
+        .merge_ref(&suffix)
+        .merge_ref(&cow);
+
+    assert_eq!(a, "a-str-suffix-cow");
+
+    // Partials can be merged by value by wrapping them in an array or `Option`.
+    let b = String::from("b")
+        .merge([suffix])
+        .merge([cow])
+        .merge(Some("-option"))
+        .merge(None::<&str>);
+
+    assert_eq!(b, "b-suffix-cow-option");
+
+    // Merging an empty partial does not allocate.
+    let mut c = String::new();
+
+    c.merge_ref_in_place("");
+    c.merge_in_place([String::new()]);
+    c.merge_in_place([Cow::Borrowed("")]);
+
+    assert_eq!(c.capacity(), 0);
+}
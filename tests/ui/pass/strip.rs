@@ -0,0 +1,44 @@
+use mergeme::Merge;
+
+#[derive(Merge, Clone, Debug, PartialEq)]
+#[partial(PartialConfig, default, strip)]
+#[partial(PartialConfigCli)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: vec!["syn".to_string()],
+    };
+
+    let applied = PartialConfig {
+        version: Some(2),
+        dependencies: Some(vec!["quote".to_string()]),
+        ..Default::default()
+    };
+
+    let merged = config.clone().merge(applied);
+
+    let stripped = merged.strip(&PartialConfig {
+        version: Some(2),
+        dependencies: Some(vec!["quote".to_string()]),
+        ..Default::default()
+    });
+
+    // Only fields that were not overridden are kept.
+    assert_eq!(stripped.name.as_deref(), Some("mergeme"));
+    assert_eq!(stripped.version, None);
+    assert_eq!(stripped.dependencies, None);
+
+    // Stripping an empty partial struct keeps every field.
+    assert!(config.strip(&PartialConfig::default()).is_complete());
+
+    // Stripping a complete partial struct keeps no fields.
+    assert!(config.strip(&config.clone().into()).is_empty());
+}
@@ -0,0 +1,68 @@
+use mergeme::Merge;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Source {
+    File,
+    Env,
+    Cli,
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, builder, track_source(Source))]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+    #[partial(flatten_option)]
+    license: Option<String>,
+    #[partial(skip)]
+    cache: Vec<String>,
+}
+
+fn main() {
+    let mut config = Config {
+        name: "mergeme".to_string(),
+        version: 1,
+        dependencies: Vec::new(),
+        license: None,
+        cache: Vec::new(),
+    };
+
+    let mut sources = ConfigSources::default();
+
+    let file = PartialConfig::default()
+        .with_name("mergeme-file".to_string())
+        .with_version(2)
+        .with_dependencies(vec!["syn".to_string()]);
+
+    let env = PartialConfig::default()
+        .with_version(3)
+        .with_license("MIT".to_string());
+
+    let cli = PartialConfig::default().with_dependencies(vec!["quote".to_string()]);
+
+    config.merge_in_place_from(file, Source::File, &mut sources);
+    config.merge_in_place_from(env, Source::Env, &mut sources);
+    config.merge_in_place_from(cli, Source::Cli, &mut sources);
+
+    assert_eq!(config.name, "mergeme-file");
+    assert_eq!(config.version, 3);
+    assert_eq!(config.dependencies, ["syn", "quote"]);
+    assert_eq!(config.license.as_deref(), Some("MIT"));
+
+    assert_eq!(sources.name, Some(Source::File));
+    assert_eq!(sources.version, Some(Source::Env));
+    assert_eq!(sources.license, Some(Source::Env));
+
+    // Merged fields record the last source that contributed to them.
+    assert_eq!(sources.dependencies, Some(Source::Cli));
+
+    // Fields that were never set have no source.
+    let mut sources = ConfigSources::default();
+
+    config.merge_in_place_from(PartialConfig::default(), Source::Cli, &mut sources);
+
+    assert_eq!(sources.name, None);
+    assert_eq!(sources.version, None);
+}
@@ -0,0 +1,88 @@
+use mergeme::{Merge, MissingFields};
+
+#[derive(Merge)]
+#[partial(PartialConfig)]
+struct Config {
+    name: String,
+    version: u32,
+    #[strategy(merge)]
+    dependencies: Vec<String>,
+}
+
+fn main() {
+    let complete = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: Some(2),
+        dependencies: Some(vec!["syn".to_string()]),
+    };
+
+    let config = Config::try_from(complete).unwrap();
+
+    assert_eq!(config.name, "mergeme");
+    assert_eq!(config.version, 2);
+    assert_eq!(config.dependencies, ["syn"]);
+
+    let incomplete = PartialConfig {
+        name: Some("mergeme".to_string()),
+        version: None,
+        dependencies: Some(Vec::new()),
+    };
+
+    let error: MissingFields = Config::try_from(incomplete).err().unwrap();
+
+    assert_eq!(error.iter().collect::<Vec<_>>(), ["version"]);
+    assert_eq!(error.len(), 1);
+    assert_eq!(error.to_string(), "missing field `version`");
+
+    let empty = PartialConfig {
+        name: None,
+        version: None,
+        dependencies: None,
+    };
+
+    let error = Config::try_from(empty).err().unwrap();
+
+    // Every missing field is reported, not just the first.
+    assert_eq!(
+        error.iter().collect::<Vec<_>>(),
+        ["name", "version", "dependencies"],
+    );
+    assert_eq!(
+        error.to_string(),
+        "missing fields `name`, `version`, `dependencies`",
+    );
+
+    // `MissingFields` can be used as a boxed error.
+    let error: Box<dyn std::error::Error> = Box::new(error);
+
+    assert!(error.to_string().starts_with("missing fields"));
+
+    // Raw identifiers are reported without the `r#` prefix.
+    let error = Package::try_from(PartialPackage { r#type: None }).err().unwrap();
+
+    assert_eq!(error.to_string(), "missing field `type`");
+
+    // The error type does not depend on the struct, so one function can convert several.
+    let error = load(
+        PartialConfig {
+            name: Some("mergeme".to_string()),
+            version: Some(2),
+            dependencies: Some(Vec::new()),
+        },
+        PartialPackage { r#type: None },
+    )
+    .err()
+    .unwrap();
+
+    assert_eq!(error.to_string(), "missing field `type`");
+}
+
+fn load(config: PartialConfig, package: PartialPackage) -> Result<(Config, Package), MissingFields> {
+    Ok((config.try_into()?, package.try_into()?))
+}
+
+#[derive(Merge)]
+#[partial(PartialPackage)]
+struct Package {
+    r#type: String,
+}
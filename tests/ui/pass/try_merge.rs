@@ -0,0 +1,82 @@
+use mergeme::{Merge, TryMerge};
+
+#[derive(Debug, PartialEq)]
+struct TooManyWorkers;
+
+/// Adds more workers to the pool, failing if there would be more than 16.
+fn add_workers(workers: &mut u8, more: u8) -> Result<(), TooManyWorkers> {
+    match workers.checked_add(more) {
+        Some(total) if total <= 16 => {
+            *workers = total;
+            Ok(())
+        }
+        _ => Err(TooManyWorkers),
+    }
+}
+
+#[derive(Merge, Debug)]
+#[partial(PartialPool, default, error = TooManyWorkers)]
+struct Pool {
+    name: String,
+    #[strategy(try_with = "add_workers")]
+    workers: u8,
+    #[strategy(merge)]
+    tags: Vec<String>,
+}
+
+// Without `try_with` fields, `TryMerge` is still implemented through `Merge`, and never fails.
+#[derive(Merge)]
+#[partial(PartialLabel)]
+struct Label {
+    text: String,
+}
+
+fn main() {
+    let pool = Pool {
+        name: "default".to_string(),
+        workers: 4,
+        tags: vec!["fast".to_string()],
+    };
+
+    let pool = pool
+        .try_merge(PartialPool {
+            name: Some("primary".to_string()),
+            workers: Some(8),
+            tags: Some(vec!["local".to_string()]),
+        })
+        .unwrap();
+
+    assert_eq!(pool.name, "primary");
+    assert_eq!(pool.workers, 12);
+    assert_eq!(pool.tags, ["fast", "local"]);
+
+    // Partial structs are combined with the same function, so they may also fail.
+    let mut combined = PartialPool {
+        workers: Some(4),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        combined.try_merge_in_place(PartialPool {
+            workers: Some(4),
+            ..Default::default()
+        }),
+        Ok(()),
+    );
+    assert_eq!(combined.workers, Some(8));
+
+    let mut pool = pool;
+
+    assert_eq!(pool.try_merge_in_place(combined), Err(TooManyWorkers));
+    assert_eq!(pool.workers, 12);
+
+    let label = Label {
+        text: "hello".to_string(),
+    };
+
+    let label: Result<Label, std::convert::Infallible> = label.try_merge(PartialLabel {
+        text: Some("world".to_string()),
+    });
+
+    assert_eq!(label.unwrap().text, "world");
+}
@@ -0,0 +1,56 @@
+use mergeme::Merge;
+use std::path::PathBuf;
+
+fn expand_home(path: String) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => PathBuf::from("/home/user").join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[derive(Merge)]
+#[partial(PartialConfig, default, builder, impls(Clone, Debug, PartialEq), getters)]
+struct Config {
+    name: String,
+    #[partial(type = Option<String>, with = "expand_home")]
+    cache_dir: PathBuf,
+}
+
+#[derive(Merge)]
+#[partial(PartialServer, base_builder)]
+struct Server {
+    #[partial(type = Option<u32>, with = "u64::from")]
+    timeout: u64,
+}
+
+fn main() {
+    let partial = PartialConfig::default().with_cache_dir("~/.cache/mergeme".to_string());
+
+    assert_eq!(partial.cache_dir(), Some("~/.cache/mergeme"));
+
+    let config = Config {
+        name: "mergeme".to_string(),
+        cache_dir: PathBuf::from("/tmp"),
+    }
+    .merge(partial.clone());
+
+    // The partial's `String` is converted into the base's `PathBuf` while merging.
+    assert_eq!(config.cache_dir, PathBuf::from("/home/user/.cache/mergeme"));
+
+    // `TryFrom` converts the value as well.
+    let config = Config::try_from(partial.with_name("mergeme".to_string())).unwrap();
+
+    assert_eq!(config.cache_dir, PathBuf::from("/home/user/.cache/mergeme"));
+
+    // Partials with overridden types still merge with each other.
+    let partial = PartialConfig::default()
+        .with_cache_dir("/var/cache".to_string())
+        .merge(PartialConfig::default().with_cache_dir("~/cache".to_string()));
+
+    assert_eq!(partial.cache_dir(), Some("~/cache"));
+
+    // The base builder accepts the partial's type.
+    let server = Server::builder().timeout(30u32).build().unwrap();
+
+    assert_eq!(server.timeout, 30);
+}
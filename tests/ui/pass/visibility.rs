@@ -0,0 +1,46 @@
+mod loader {
+    use mergeme::Merge;
+
+    #[derive(Merge)]
+    // `PartialConfig` is an implementation detail, and should not be as visible as `Config`.
+    #[partial(PartialConfig, vis = pub(crate))]
+    pub struct Config {
+        // These fields are private, but the partial's fields may be accessed throughout the crate.
+        #[partial(vis = pub(crate))]
+        name: String,
+        #[partial(vis = pub(crate))]
+        version: u32,
+    }
+
+    impl Config {
+        pub fn new(name: &str, version: u32) -> Self {
+            Self {
+                name: name.to_string(),
+                version,
+            }
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn version(&self) -> u32 {
+            self.version
+        }
+    }
+}
+
+use loader::{Config, PartialConfig};
+use mergeme::Merge;
+
+fn main() {
+    let partial = PartialConfig {
+        name: None,
+        version: Some(2),
+    };
+
+    let config = Config::new("mergeme", 1).merge(partial);
+
+    assert_eq!(config.name(), "mergeme");
+    assert_eq!(config.version(), 2);
+}
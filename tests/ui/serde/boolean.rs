@@ -0,0 +1,29 @@
+use mergeme::{
+    Merge,
+    strategy::{All, Any},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge, Serialize, Deserialize)]
+#[partial(PartialFlags, derive(Deserialize))]
+struct Flags {
+    #[strategy(merge)]
+    verbose: Any,
+    #[strategy(merge)]
+    allow_network: All,
+}
+
+fn main() {
+    let flags: Flags = serde_json::from_str(r#"{"verbose":false,"allow_network":true}"#).unwrap();
+
+    let partial: PartialFlags =
+        serde_json::from_str(r#"{"verbose":true,"allow_network":false}"#).unwrap();
+
+    let flags = flags.merge(partial);
+
+    // Wrapping a field does not change how it is (de)serialized.
+    assert_eq!(
+        serde_json::to_string(&flags).unwrap(),
+        r#"{"verbose":true,"allow_network":false}"#,
+    );
+}
@@ -0,0 +1,28 @@
+use mergeme::{Merge, strategy::Capped};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge)]
+#[partial(PartialHistory, derive(Deserialize, Serialize))]
+struct History {
+    #[strategy(merge)]
+    recent_files: Capped<Vec<String>, 2>,
+}
+
+fn main() {
+    // Deserialized lists are capped, while the wire format is unchanged.
+    let partial: PartialHistory =
+        serde_json::from_str(r#"{ "recent_files": ["a.txt", "b.txt", "c.txt"] }"#).unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&partial).unwrap(),
+        r#"{"recent_files":["b.txt","c.txt"]}"#,
+    );
+
+    let history = History {
+        recent_files: Capped::new(Vec::new()),
+    };
+
+    let history = history.merge(partial);
+
+    assert_eq!(*history.recent_files, ["b.txt", "c.txt"]);
+}
@@ -0,0 +1,23 @@
+use mergeme::{Merge, strategy::Joined};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge, Serialize, Deserialize)]
+#[partial(PartialBuild, derive(Deserialize))]
+struct Build {
+    #[strategy(merge)]
+    rustflags: Joined<' '>,
+}
+
+fn main() {
+    let build: Build = serde_json::from_str(r#"{"rustflags":"-C opt-level=3"}"#).unwrap();
+
+    let partial: PartialBuild = serde_json::from_str(r#"{"rustflags":"-C lto"}"#).unwrap();
+
+    let build = build.merge(partial);
+
+    // Wrapping a field does not change how it is (de)serialized.
+    assert_eq!(
+        serde_json::to_string(&build).unwrap(),
+        r#"{"rustflags":"-C opt-level=3 -C lto"}"#,
+    );
+}
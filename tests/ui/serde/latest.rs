@@ -0,0 +1,16 @@
+use mergeme::{Merge, strategy::Latest};
+
+fn main() {
+    let base: Latest<String> =
+        serde_json::from_str(r#"{"value":"localhost","version":2}"#).unwrap();
+
+    let partial: Latest<String> =
+        serde_json::from_str(r#"{"value":"example.com","version":5}"#).unwrap();
+
+    let merged = base.merge(partial);
+
+    assert_eq!(
+        serde_json::to_string(&merged).unwrap(),
+        r#"{"value":"example.com","version":5}"#,
+    );
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+use mergeme::{Merge, strategy::MergeMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge)]
+#[partial(PartialConfig, derive(Deserialize, Serialize))]
+struct Config {
+    #[strategy(merge)]
+    features: MergeMap<BTreeMap<String, Vec<String>>>,
+}
+
+fn main() {
+    // Wrapping a field does not change how it is (de)serialized.
+    let json = r#"{"features":{"default":["std"],"full":["serde"]}}"#;
+    let partial: PartialConfig = serde_json::from_str(json).unwrap();
+
+    assert_eq!(serde_json::to_string(&partial).unwrap(), json);
+
+    let config = Config {
+        features: MergeMap(BTreeMap::from([("default".to_string(), vec!["alloc".to_string()])])),
+    };
+
+    let config = config.merge(partial);
+
+    assert_eq!(config.features["default"], ["alloc", "std"]);
+    assert_eq!(config.features["full"], ["serde"]);
+}
@@ -0,0 +1,32 @@
+use mergeme::{
+    Merge,
+    strategy::{Max, Min, Sum},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge, Serialize, Deserialize, Debug, PartialEq)]
+#[partial(PartialLimits, derive(Deserialize))]
+struct Limits {
+    #[strategy(merge)]
+    timeout: Min<u64>,
+    #[strategy(merge)]
+    max_connections: Max<u32>,
+    #[strategy(merge)]
+    retries: Sum<u32>,
+}
+
+fn main() {
+    let limits: Limits =
+        serde_json::from_str(r#"{"timeout":30,"max_connections":100,"retries":1}"#).unwrap();
+
+    let partial: PartialLimits =
+        serde_json::from_str(r#"{"timeout":10,"max_connections":50,"retries":2}"#).unwrap();
+
+    let limits = limits.merge(partial);
+
+    // The wrappers are (de)serialized as the values they wrap.
+    assert_eq!(
+        serde_json::to_string(&limits).unwrap(),
+        r#"{"timeout":10,"max_connections":100,"retries":3}"#,
+    );
+}
@@ -0,0 +1,57 @@
+use mergeme::{Merge, Overridable};
+use serde::{Deserialize, Serialize};
+
+struct Config {
+    proxy: Option<String>,
+    timeout: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct PartialConfig {
+    #[serde(default, skip_serializing_if = "Overridable::is_unset")]
+    proxy: Overridable<String>,
+    #[serde(default, skip_serializing_if = "Overridable::is_unset")]
+    timeout: Overridable<u32>,
+}
+
+impl Merge<PartialConfig> for Config {
+    fn merge_in_place(&mut self, other: PartialConfig) {
+        self.proxy.merge_in_place(other.proxy);
+        self.timeout.merge_in_place(other.timeout);
+    }
+}
+
+fn main() {
+    // A missing key is unset, `null` is cleared, and a value is set.
+    let partial: PartialConfig = serde_json::from_str(r#"{"proxy":null,"timeout":10}"#).unwrap();
+
+    assert_eq!(partial.proxy, Overridable::Clear);
+    assert_eq!(partial.timeout, Overridable::Set(10));
+
+    let partial: PartialConfig = serde_json::from_str(r#"{"timeout":null}"#).unwrap();
+
+    assert_eq!(partial.proxy, Overridable::Unset);
+    assert_eq!(partial.timeout, Overridable::Clear);
+
+    // Each state is written back the same way it was read.
+    for json in [
+        r#"{}"#,
+        r#"{"proxy":null}"#,
+        r#"{"proxy":"localhost:8080","timeout":null}"#,
+        r#"{"timeout":30}"#,
+    ] {
+        let partial: PartialConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&partial).unwrap(), json);
+    }
+
+    let config = Config {
+        proxy: Some("localhost:8080".to_string()),
+        timeout: Some(30),
+    };
+
+    let config = config.merge(serde_json::from_str::<PartialConfig>(r#"{"proxy":null}"#).unwrap());
+
+    assert_eq!(config.proxy, None);
+    assert_eq!(config.timeout, Some(30));
+}
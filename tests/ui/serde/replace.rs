@@ -0,0 +1,24 @@
+use mergeme::{Merge, strategy::Replace};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge)]
+#[partial(PartialConfig, derive(Deserialize, Serialize))]
+struct Config {
+    #[strategy(merge)]
+    hosts: Replace<Vec<String>>,
+}
+
+fn main() {
+    // Wrapping a field does not change how it is (de)serialized.
+    let partial: PartialConfig = serde_json::from_str(r#"{ "hosts": ["example.com"] }"#).unwrap();
+
+    assert_eq!(serde_json::to_string(&partial).unwrap(), r#"{"hosts":["example.com"]}"#);
+
+    let config = Config {
+        hosts: Replace(vec!["localhost".to_string()]),
+    };
+
+    let config = config.merge(partial);
+
+    assert_eq!(*config.hosts, ["example.com"]);
+}
@@ -0,0 +1,24 @@
+use mergeme::{Merge, strategy::Sorted};
+use serde::{Deserialize, Serialize};
+
+#[derive(Merge)]
+#[partial(PartialConfig, derive(Deserialize, Serialize))]
+struct Config {
+    #[strategy(merge)]
+    ports: Sorted<Vec<u16>>,
+}
+
+fn main() {
+    // Deserialized lists are sorted, while the wire format is unchanged.
+    let partial: PartialConfig = serde_json::from_str(r#"{ "ports": [8080, 80, 443] }"#).unwrap();
+
+    assert_eq!(serde_json::to_string(&partial).unwrap(), r#"{"ports":[80,443,8080]}"#);
+
+    let config = Config {
+        ports: Sorted::new(vec![3000]),
+    };
+
+    let config = config.merge(partial);
+
+    assert_eq!(*config.ports, [80, 443, 3000, 8080]);
+}